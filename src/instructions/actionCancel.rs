@@ -0,0 +1,63 @@
+use core::convert::TryFrom;
+use pinocchio::{error::ProgramError, AccountView, ProgramResult};
+use pinocchio_log::log;
+
+use crate::{
+  check_pda, close_pda, data_len, instructions::check_signer, writable, Action, Config, Ee,
+};
+
+/// Cancel Action: the prog_owner drops a queued config change. Rent goes back to the proposer
+pub struct ActionCancel<'a> {
+  pub prog_owner: &'a AccountView, //signer
+  pub config_pda: &'a AccountView,
+  pub action_pda: &'a AccountView,
+  pub proposer: &'a AccountView,
+}
+impl<'a> ActionCancel<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &38;
+
+  pub fn process(self) -> ProgramResult {
+    let ActionCancel {
+      prog_owner: _,
+      config_pda: _,
+      action_pda,
+      proposer,
+    } = self;
+    log!("ActionCancel process()");
+    close_pda(action_pda, proposer)
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for ActionCancel<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("ActionCancel try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+    data_len(data, 0)?;
+
+    let [prog_owner, config_pda, action_pda, proposer] = accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(prog_owner)?;
+    writable(action_pda)?;
+    writable(proposer)?;
+    check_pda(config_pda)?;
+
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    if config.prog_owner().ne(prog_owner.address()) {
+      return Err(Ee::OnlyProgOwner.into());
+    }
+    let action: &mut Action = Action::from_account_view(action_pda)?;
+    if action.proposer().ne(proposer.address()) {
+      return Err(Ee::ActionPDA.into());
+    }
+    action.check_config(config_pda)?;
+    Ok(Self {
+      prog_owner,
+      config_pda,
+      action_pda,
+      proposer,
+    })
+  }
+}
@@ -0,0 +1,119 @@
+use core::convert::TryFrom;
+use pinocchio::{
+  cpi::{Seed, Signer},
+  error::ProgramError,
+  sysvars::rent::Rent,
+  AccountView, Address, ProgramResult,
+};
+use pinocchio_log::log;
+
+use crate::{
+  check_pda, check_sysprog, get_time, instructions::check_signer, not_initialized, parse_u64,
+  to32bytes, versioned_payload, writable, Action, Config, Ee, PROG_ADDR,
+};
+
+/// Enqueue Action: an admin queues a sensitive config change by its payload hash. It can be executed by anyone after the config timelock delay
+pub struct ActionEnqueue<'a> {
+  pub proposer: &'a AccountView, //signer
+  pub config_pda: &'a AccountView,
+  pub admin_pda: &'a AccountView,
+  pub action_pda: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub id: u64,
+  pub kind: u8,
+  pub payload_hash: [u8; 32],
+  pub eta: u32,
+}
+impl<'a> ActionEnqueue<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &36;
+
+  pub fn process(self) -> ProgramResult {
+    let ActionEnqueue {
+      proposer,
+      config_pda,
+      admin_pda: _,
+      action_pda,
+      system_program: _,
+      id,
+      kind,
+      payload_hash,
+      eta,
+    } = self;
+    log!("ActionEnqueue process()");
+    let id_bytes = &id.to_le_bytes();
+    let seed = [Action::SEED, proposer.address().as_array(), id_bytes];
+    let (expected_action, bump) = Address::find_program_address(&seed[..], &PROG_ADDR);
+    if expected_action.ne(action_pda.address()) {
+      return Ee::ActionPDA.e();
+    }
+    let rent = Rent::from_account_view(action_pda)?;
+    let lamports = Rent::try_minimum_balance(&rent, Action::LEN)?;
+    let seeds = [
+      Seed::from(Action::SEED),
+      Seed::from(proposer.address().as_ref()),
+      Seed::from(id_bytes),
+      Seed::from(core::slice::from_ref(&bump)),
+    ];
+    let seed_signer = Signer::from(&seeds);
+
+    pinocchio_system::instructions::CreateAccount {
+      from: proposer,
+      to: action_pda,
+      lamports,
+      space: Action::LEN as u64,
+      owner: &PROG_ADDR,
+    }
+    .invoke_signed(&[seed_signer])?;
+
+    let action: &mut Action = Action::init_account_view(action_pda)?;
+    action.set_config(config_pda.address());
+    action.set_proposer(proposer.address());
+    action.set_payload_hash(payload_hash);
+    action.set_id(id);
+    action.set_eta(eta);
+    action.set_kind(kind);
+    action.set_bump(bump);
+    log!("kind: {}, eta: {}", kind, eta);
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for ActionEnqueue<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("ActionEnqueue try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+    let payload = versioned_payload(data, 41)?;
+    let id = parse_u64(&payload[0..8])?;
+    let kind = payload[8];
+    let payload_hash = *to32bytes(&payload[9..41])?;
+
+    let [proposer, config_pda, admin_pda, action_pda, system_program] = accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(proposer)?;
+    writable(proposer)?;
+    writable(action_pda)?;
+    not_initialized(action_pda)?;
+    check_pda(config_pda)?;
+    check_sysprog(system_program)?;
+
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    Action::check_proposer(kind, config_pda, config, proposer, admin_pda)?;
+    let eta = get_time()?
+      .checked_add(config.timelock_delay())
+      .ok_or(ProgramError::ArithmeticOverflow)?;
+    Ok(Self {
+      proposer,
+      config_pda,
+      admin_pda,
+      action_pda,
+      system_program,
+      id,
+      kind,
+      payload_hash,
+      eta,
+    })
+  }
+}
@@ -0,0 +1,217 @@
+use core::convert::TryFrom;
+use pinocchio::{error::ProgramError, AccountView, Address, ProgramResult};
+use pinocchio_log::log;
+
+use crate::{
+  check_mint_interface, check_pda, check_sysprog, close_pda, derive_pda2, get_time, grant_admin,
+  init_mint_reg, instructions::check_signer, load_mint_reg, none_zero_u32, not_initialized,
+  parse_u32, parse_u64, rent_exempt_mint_interface, revoke_admin, sha256, to32bytes, u8_to_bool,
+  u8_to_status, update_rate, writable, Action, Admin, Config, Ee,
+};
+
+/// Execute Action: anyone can apply a queued config change once its eta has passed, by giving the payload matching the queued hash. The proposer must still hold the permission, checked with its Admin PDA. Rent goes back to the proposer
+pub struct ActionExecute<'a> {
+  pub executor: &'a AccountView, //signer
+  pub config_pda: &'a AccountView,
+  pub action_pda: &'a AccountView,
+  pub proposer: &'a AccountView,
+  pub admin_pda: &'a AccountView,
+  pub remaining: &'a [AccountView],
+  pub payload: &'a [u8],
+  pub config: &'a mut Config,
+}
+impl<'a> ActionExecute<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &37;
+
+  pub fn process(self) -> ProgramResult {
+    let ActionExecute {
+      executor,
+      config_pda,
+      action_pda,
+      proposer,
+      admin_pda: _,
+      remaining,
+      payload,
+      config,
+    } = self;
+    log!("ActionExecute process()");
+    apply_action(config_pda, config, payload, executor, proposer, remaining)?;
+    config.set_updated_at(get_time()?);
+
+    log!("Close Action PDA");
+    close_pda(action_pda, proposer)
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for ActionExecute<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("ActionExecute try_from");
+    let (payload, accounts) = value;
+    log!(
+      "accounts len: {}, data len: {}",
+      accounts.len(),
+      payload.len()
+    );
+
+    let [executor, config_pda, action_pda, proposer, admin_pda, remaining @ ..] = accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(executor)?;
+    writable(executor)?;
+    writable(config_pda)?;
+    writable(action_pda)?;
+    writable(proposer)?;
+    check_pda(config_pda)?;
+
+    action_pda.check_borrow_mut()?;
+    let action: &mut Action = Action::from_account_view(action_pda)?;
+    if action.proposer().ne(proposer.address()) {
+      return Err(Ee::ActionPDA.into());
+    }
+    action.check_config(config_pda)?;
+    let Some(kind) = payload.first() else {
+      return Err(Ee::InputDataLen.into());
+    };
+    if *kind != action.kind() {
+      return Err(Ee::ActionKind.into());
+    }
+    if payload.len() != Action::payload_len(*kind)? {
+      return Err(Ee::InputDataLen.into());
+    }
+    if sha256(payload).ne(action.payload_hash()) {
+      return Err(Ee::ActionPayloadHash.into());
+    }
+    action.check_ready(get_time()?)?;
+
+    config_pda.check_borrow_mut()?;
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    Action::check_proposer(*kind, config_pda, config, proposer, admin_pda)?;
+    Ok(Self {
+      executor,
+      config_pda,
+      action_pda,
+      proposer,
+      admin_pda,
+      remaining,
+      payload,
+      config,
+    })
+  }
+}
+
+/// apply a queued config change. payload: kind + arguments, already checked against its hash. payer funds a new MintReg or Admin PDA, rent_dest gets the rent of a removed one. remaining: [mint, mint_reg, system_program] for MINT_ADD, [mint, mint_reg] for MINT_REMOVE, [mint, token_program] for SET_RATE, [admin_pda, system_program] for ADMIN_GRANT, [admin_pda] for ADMIN_REVOKE
+pub fn apply_action(
+  config_pda: &AccountView,
+  config: &mut Config,
+  payload: &[u8],
+  payer: &AccountView,
+  rent_dest: &AccountView,
+  remaining: &[AccountView],
+) -> ProgramResult {
+  let kind = payload[0];
+  log!("kind: {}", kind);
+  match kind {
+    Action::SET_FEE => {
+      config.set_fee(parse_u64(&payload[1..9])?)?;
+      config.set_fee_on_x(u8_to_bool(payload[9])?);
+    }
+    Action::SET_DELAY => {
+      config.set_timelock_delay(parse_u32(&payload[1..5])?)?;
+    }
+    Action::SET_EXT_POLICY => {
+      config.set_ext_policy(parse_u64(&payload[1..9])?, u8_to_bool(payload[9])?);
+    }
+    Action::PROPOSE_OWNER => {
+      config.set_pending_owner(&Address::new_from_array(*to32bytes(&payload[1..33])?));
+    }
+    Action::SET_STATUS => {
+      u8_to_status(payload[1])?;
+      config.set_status(payload[1]);
+    }
+    Action::SET_LABEL => {
+      config.set_str_u8array(*to32bytes(&payload[1..33])?);
+    }
+    Action::SET_AUTHORIZED => {
+      config.set_is_authorized(u8_to_bool(payload[1])?);
+    }
+    Action::MINT_ADD => {
+      let [mint, mint_reg, system_program] = remaining else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+      };
+      if mint.address().as_ref().ne(&payload[1..33]) {
+        return Ee::MintNotAccepted.e();
+      }
+      check_sysprog(system_program)?;
+      not_initialized(mint_reg)?;
+      check_mint_interface(mint)?;
+      rent_exempt_mint_interface(mint)?;
+      init_mint_reg(
+        payer,
+        config_pda.address(),
+        mint,
+        mint_reg,
+        parse_u64(&payload[33..41])?,
+      )?;
+    }
+    Action::MINT_REMOVE => {
+      let [mint, mint_reg] = remaining else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+      };
+      if mint.address().as_ref().ne(&payload[1..33]) {
+        return Ee::MintNotAccepted.e();
+      }
+      writable(mint_reg)?;
+      mint_reg.check_borrow_mut()?;
+      if load_mint_reg(config_pda.address(), mint, mint_reg)?.deposited() > 0 {
+        return Ee::MintRegHasDeposits.e();
+      }
+      close_pda(mint_reg, rent_dest)?;
+    }
+    Action::SET_RATE => {
+      let [mint, token_program] = remaining else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+      };
+      if mint.address().as_ref().ne(&payload[1..33]) {
+        return Ee::MintNotAccepted.e();
+      }
+      update_rate(
+        config_pda,
+        config,
+        mint,
+        token_program,
+        i16::from_le_bytes([payload[33], payload[34]]),
+      )?;
+    }
+    Action::ADMIN_GRANT => {
+      let [admin_pda, system_program] = remaining else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+      };
+      check_sysprog(system_program)?;
+      writable(admin_pda)?;
+      let permissions = parse_u32(&payload[33..37])?;
+      none_zero_u32(permissions)?;
+      if permissions & !Admin::ALL != 0 {
+        return Ee::AdminPermission.e();
+      }
+      let admin = Address::new_from_array(*to32bytes(&payload[1..33])?);
+      grant_admin(payer, config_pda.address(), &admin, admin_pda, permissions)?;
+    }
+    Action::ADMIN_REVOKE => {
+      let [admin_pda] = remaining else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+      };
+      writable(admin_pda)?;
+      let permissions = parse_u32(&payload[33..37])?;
+      none_zero_u32(permissions)?;
+      let admin = Address::new_from_array(*to32bytes(&payload[1..33])?);
+      let (expected_admin_pda, _bump) = derive_pda2(config_pda.address(), &admin, Admin::SEED)?;
+      if expected_admin_pda.ne(admin_pda.address()) {
+        return Ee::AdminPDA.e();
+      }
+      revoke_admin(admin_pda, permissions, rent_dest)?;
+    }
+    _ => return Ee::ActionKind.e(),
+  }
+  Ok(())
+}
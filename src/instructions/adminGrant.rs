@@ -0,0 +1,80 @@
+use core::convert::TryFrom;
+use pinocchio::{error::ProgramError, AccountView, ProgramResult};
+use pinocchio_log::log;
+
+use crate::{
+  check_pda, check_sysprog, grant_admin, instructions::check_signer, none_zero_u32, parse_u32,
+  versioned_payload, writable, Admin, Config, Ee,
+};
+
+/// Grant Admin Permissions: only the prog_owner can make or add permissions to an Admin PDA, which is seeded by the config and the admin. While a timelock delay is set, grants are queued as ADMIN_GRANT actions
+pub struct AdminGrant<'a> {
+  pub prog_owner: &'a AccountView, //signer
+  pub config_pda: &'a AccountView,
+  pub admin_pda: &'a AccountView,
+  pub admin: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub permissions: u32,
+}
+impl<'a> AdminGrant<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &21;
+
+  pub fn process(self) -> ProgramResult {
+    let AdminGrant {
+      prog_owner,
+      config_pda,
+      admin_pda,
+      admin,
+      system_program: _,
+      permissions,
+    } = self;
+    log!("AdminGrant process()");
+    grant_admin(
+      prog_owner,
+      config_pda.address(),
+      admin.address(),
+      admin_pda,
+      permissions,
+    )
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for AdminGrant<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("AdminGrant try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+
+    let [prog_owner, config_pda, admin_pda, admin, system_program] = accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(prog_owner)?;
+    check_sysprog(system_program)?;
+    writable(admin_pda)?;
+    check_pda(config_pda)?;
+
+    let payload = versioned_payload(data, 4)?;
+    let permissions = parse_u32(payload)?;
+    log!("permissions: {}", permissions);
+    none_zero_u32(permissions)?;
+    if permissions & !Admin::ALL != 0 {
+      return Err(Ee::AdminPermission.into());
+    }
+
+    config_pda.check_borrow_mut()?;
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    if config.prog_owner().ne(prog_owner.address()) {
+      return Err(Ee::OnlyProgOwner.into());
+    }
+    config.check_no_timelock()?;
+    Ok(Self {
+      prog_owner,
+      config_pda,
+      admin_pda,
+      admin,
+      system_program,
+      permissions,
+    })
+  }
+}
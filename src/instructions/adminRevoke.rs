@@ -0,0 +1,71 @@
+use core::convert::TryFrom;
+use pinocchio::{error::ProgramError, AccountView, ProgramResult};
+use pinocchio_log::log;
+
+use crate::{
+  check_pda, instructions::check_signer, none_zero_u32, parse_u32, revoke_admin, versioned_payload,
+  writable, Admin, Config, Ee,
+};
+
+/// Revoke Admin Permissions: only the prog_owner can remove permissions. It takes effect at once even while a timelock delay is set, so a compromised admin can be cut off. The Admin PDA is closed when none is left
+pub struct AdminRevoke<'a> {
+  pub prog_owner: &'a AccountView, //signer
+  pub config_pda: &'a AccountView,
+  pub admin_pda: &'a AccountView,
+  pub permissions: u32,
+}
+impl<'a> AdminRevoke<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &22;
+
+  pub fn process(self) -> ProgramResult {
+    let AdminRevoke {
+      prog_owner,
+      config_pda: _,
+      admin_pda,
+      permissions,
+    } = self;
+    log!("AdminRevoke process()");
+    revoke_admin(admin_pda, permissions, prog_owner)
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for AdminRevoke<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("AdminRevoke try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+
+    let [prog_owner, config_pda, admin_pda] = accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(prog_owner)?;
+    writable(prog_owner)?;
+    writable(admin_pda)?;
+    check_pda(config_pda)?;
+
+    let payload = versioned_payload(data, 4)?;
+    let permissions = parse_u32(payload)?;
+    log!("permissions: {}", permissions);
+    none_zero_u32(permissions)?;
+
+    config_pda.check_borrow_mut()?;
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    if config.prog_owner().ne(prog_owner.address()) {
+      return Err(Ee::OnlyProgOwner.into());
+    }
+    admin_pda.check_borrow_mut()?;
+    if Admin::from_account_view(admin_pda)?
+      .config()
+      .ne(config_pda.address())
+    {
+      return Err(Ee::AdminConfig.into());
+    }
+    Ok(Self {
+      prog_owner,
+      config_pda,
+      admin_pda,
+      permissions,
+    })
+  }
+}
@@ -2,11 +2,13 @@ use core::convert::TryFrom;
 use pinocchio::{error::ProgramError, AccountView, ProgramResult};
 use pinocchio_log::log;
 
-use crate::{check_pda, data_len, instructions::check_signer, writable, Config};
+use crate::{
+  check_pda, close_pda, data_len, instructions::check_signer, u8_to_bool, writable, Config, Ee,
+};
 
-/// Close PDA
+/// Close Config PDA: only the prog_owner can close it, sending the rent to a system-owned wallet. Refused while escrows or user positions still reference the config, and while it still records SOL or token balances unless forced
 pub struct CloseConfigPda<'a> {
-  pub authority: &'a AccountView,
+  pub prog_owner: &'a AccountView, //signer
   pub config_pda: &'a AccountView,
   pub dest: &'a AccountView,
 }
@@ -15,30 +17,12 @@ impl<'a> CloseConfigPda<'a> {
 
   pub fn process(self) -> ProgramResult {
     let CloseConfigPda {
-      authority: _,
+      prog_owner: _,
       config_pda,
       dest,
     } = self;
     log!("CloseConfigPda process()");
-    //set the first byte to 255
-    {
-      let mut data = config_pda.try_borrow_mut()?;
-      data[0] = 0xff;
-    }
-    log!("CloseConfigPda 1");
-    let sum_lam = dest
-      .lamports()
-      .checked_add(config_pda.lamports())
-      .ok_or_else(|| ProgramError::ArithmeticOverflow)?;
-    dest.set_lamports(sum_lam);
-    config_pda.set_lamports(0);
-    //https://learn.blueshift.gg/en/courses/pinocchio-for-dummies/pinocchio-accounts
-    //*dest.try_borrow_mut_lamports()? += *config_pda.try_borrow_lamports()?;
-
-    log!("CloseConfigPda 2"); //resize the account to only the 1st byte
-    config_pda.resize(1)?;
-    config_pda.close()?;
-    Ok(())
+    close_pda(config_pda, dest)
   }
 }
 impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for CloseConfigPda<'a> {
@@ -48,24 +32,52 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for CloseConfigPda<'a> {
     log!("CloseConfigPda try_from");
     let (data, accounts) = value;
     log!("accounts len: {}, data len: {}", accounts.len(), data.len());
-    data_len(data, 0)?;
+    data_len(data, 1)?;
+    let force = u8_to_bool(data[0])?;
 
-    let [authority, config_pda, dest] = accounts else {
+    let [prog_owner, config_pda, dest] = accounts else {
       return Err(ProgramError::NotEnoughAccountKeys);
     };
-    check_signer(authority)?;
+    check_signer(prog_owner)?;
     writable(config_pda)?;
     check_pda(config_pda)?;
 
     config_pda.check_borrow_mut()?;
-    let config: &mut Config = Config::from_account_view(&config_pda)?;
-    if config.admin().ne(authority.address()) && config.prog_owner().ne(authority.address()) {
-      return Err(ProgramError::IncorrectAuthority);
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    if config.prog_owner().ne(prog_owner.address()) {
+      return Err(Ee::OnlyProgOwner.into());
     }
+    check_closable(config_pda, config, dest, force)?;
     Ok(Self {
-      authority,
+      prog_owner,
       config_pda,
       dest,
     })
   }
 }
+
+/// the rent goes to a system-owned wallet. Refused while escrows or user positions reference the config, and while it records balances unless forced
+pub fn check_closable(
+  config_pda: &AccountView,
+  config: &Config,
+  dest: &AccountView,
+  force: bool,
+) -> ProgramResult {
+  writable(dest)?;
+  if !dest.owned_by(&pinocchio_system::ID) || dest.address().eq(config_pda.address()) {
+    return Ee::DestNotWallet.e();
+  }
+  let (sol_balance, token_balance) = (config.sol_balance(), config.token_balance());
+  log!(
+    "sol_balance: {}, token_balance: {}, open_escrows: {}, open_positions: {}",
+    sol_balance,
+    token_balance,
+    config.open_escrows(),
+    config.open_positions()
+  );
+  config.check_no_open_accounts()?;
+  if (sol_balance > 0 || token_balance > 0) && !force {
+    return Ee::ConfigHasBalances.e();
+  }
+  Ok(())
+}
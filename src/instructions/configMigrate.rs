@@ -0,0 +1,128 @@
+use core::convert::TryFrom;
+use pinocchio::{
+  error::ProgramError,
+  sysvars::{rent::Rent, Sysvar},
+  AccountView, Address, ProgramResult,
+};
+use pinocchio_log::log;
+use pinocchio_system::instructions::Transfer as SystemTransfer;
+
+use crate::{
+  check_pda, check_sysprog, data_len, derive_pda1, get_time, instructions::check_signer, writable,
+  Config, ConfigBaseline, Ee,
+};
+
+/// Migrate Config PDA: the prog_owner resizes the config in place to the current layout and rewrites the headerless baseline layout. The prog_owner tops up any rent shortfall and receives any surplus. Baseline configs record the seed_owner, which is the prog_owner unless an optional seed_owner account is given after an ownership transfer
+pub struct ConfigMigrate<'a> {
+  pub prog_owner: &'a AccountView, //signer
+  pub config_pda: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub seed_owner: &'a Address,
+  pub version: u8,
+}
+impl<'a> ConfigMigrate<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &19;
+
+  pub fn process(self) -> ProgramResult {
+    let ConfigMigrate {
+      prog_owner,
+      config_pda,
+      system_program: _,
+      seed_owner,
+      version,
+    } = self;
+    log!("ConfigMigrate process()");
+    migrate_config(prog_owner, prog_owner, config_pda, version, seed_owner)
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for ConfigMigrate<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("ConfigMigrate try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+    data_len(data, 0)?;
+
+    let [prog_owner, config_pda, system_program, rest @ ..] = accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(prog_owner)?;
+    writable(prog_owner)?;
+    writable(config_pda)?;
+    check_pda(config_pda)?;
+    check_sysprog(system_program)?;
+
+    let seed_owner = match rest {
+      [] => prog_owner.address(),
+      [seed_owner] => seed_owner.address(),
+      _ => return Err(Ee::UnexpectedAccounts.into()),
+    };
+    let version = Config::check_migratable(config_pda, prog_owner.address())?;
+    Ok(Self {
+      prog_owner,
+      config_pda,
+      system_program,
+      seed_owner,
+      version,
+    })
+  }
+}
+
+/// resize config_pda to the current layout and rewrite a baseline config of version 0. payer tops up any rent shortfall and rent_dest receives any surplus
+pub fn migrate_config(
+  payer: &AccountView,
+  rent_dest: &AccountView,
+  config_pda: &AccountView,
+  version: u8,
+  seed_owner: &Address,
+) -> ProgramResult {
+  log!(
+    "version: {} -> {}, size: {} -> {}",
+    version,
+    Config::VERSION,
+    config_pda.data_len(),
+    Config::LEN
+  );
+  //the baseline layout is longer than the current one, so it is read before resizing
+  let baseline = if version == 0 {
+    Some(ConfigBaseline::read(config_pda)?)
+  } else {
+    None
+  };
+  let min_lamports = Rent::get()?.try_minimum_balance(Config::LEN)?;
+  let lamports = config_pda.lamports();
+  if lamports < min_lamports {
+    log!("top up rent: {}", min_lamports - lamports);
+    SystemTransfer {
+      from: payer,
+      to: config_pda,
+      lamports: min_lamports - lamports,
+    }
+    .invoke()?;
+  }
+  config_pda.resize(Config::LEN)?;
+
+  if lamports > min_lamports {
+    let surplus = lamports - min_lamports;
+    log!("refund rent surplus: {}", surplus);
+    let sum_lam = rent_dest
+      .lamports()
+      .checked_add(surplus)
+      .ok_or(ProgramError::ArithmeticOverflow)?;
+    config_pda.set_lamports(min_lamports);
+    rent_dest.set_lamports(sum_lam);
+  }
+
+  if let Some(old) = baseline {
+    Config::rewrite_from_baseline(config_pda, &old, seed_owner)?;
+  }
+  config_pda.check_borrow_mut()?;
+  let config: &mut Config = Config::from_account_view(config_pda)?;
+  let (expected_config_pda, bump) = derive_pda1(config.seed_owner(), Config::SEED)?;
+  if expected_config_pda.ne(config_pda.address()) || bump != config.bump() {
+    return Ee::ConfigPDA.e();
+  }
+  config.set_updated_at(get_time()?);
+  Ok(())
+}
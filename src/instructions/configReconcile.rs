@@ -0,0 +1,125 @@
+use core::convert::TryFrom;
+use pinocchio::{
+  error::ProgramError,
+  sysvars::{rent::Rent, Sysvar},
+  AccountView, ProgramResult,
+};
+use pinocchio_log::log;
+use pinocchio_token_2022::state::TokenAccount as TokenAccount22;
+
+use crate::{
+  ata_amount_interface, check_pda, check_pool, check_sol_vault, instructions::check_signer, Admin,
+  Config, Ee, PROG_ADDR, SOL_VAULT_SIZE,
+};
+
+/// Reconcile Config: admins with the TREASURY permission compare sol_balance and token_balance against the given SOL vaults bound to the config and config vault ATAs, then log the drift
+pub struct ConfigReconcile<'a> {
+  pub signer: &'a AccountView, //signer
+  pub config_pda: &'a AccountView,
+  pub admin_pda: &'a AccountView,
+  pub accounts: &'a [AccountView],
+  pub config: &'a mut Config,
+}
+impl<'a> ConfigReconcile<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &31;
+
+  pub fn process(self) -> ProgramResult {
+    let ConfigReconcile {
+      signer: _,
+      config_pda,
+      admin_pda: _,
+      accounts,
+      config,
+    } = self;
+    log!("ConfigReconcile process()");
+    let rent = Rent::get()?;
+    let vault_min_lam = rent.try_minimum_balance(SOL_VAULT_SIZE)?;
+
+    let mut actual_sol: u64 = 0;
+    let mut actual_token: u64 = 0;
+    for (i, account) in accounts.iter().enumerate() {
+      if accounts[..i]
+        .iter()
+        .any(|prev| prev.address().eq(account.address()))
+      {
+        return Ee::DuplicateAccount.e();
+      }
+      if account.owned_by(&PROG_ADDR) {
+        //SOL vault of a user. The pool vault is not booked in sol_balance
+        if check_pool(account).is_ok() {
+          return Ee::PoolPDA.e();
+        }
+        check_sol_vault(account, config_pda.address())?;
+        let lamports = account.lamports().saturating_sub(vault_min_lam);
+        actual_sol = actual_sol
+          .checked_add(lamports)
+          .ok_or(ProgramError::ArithmeticOverflow)?;
+      } else {
+        //ATA of the config vault
+        let amount = ata_amount_interface(account)?;
+        {
+          let data = account.try_borrow()?;
+          // SAFETY: ata_amount_interface has checked the owner and the data length
+          let ata_info = unsafe { TokenAccount22::from_bytes_unchecked(&data) };
+          if ata_info.owner().ne(config.vault()) {
+            return Ee::AtaOrOwner.e();
+          }
+        }
+        actual_token = actual_token
+          .checked_add(amount)
+          .ok_or(ProgramError::ArithmeticOverflow)?;
+      }
+    }
+    log_drift("sol_balance", config.sol_balance(), actual_sol);
+    log_drift("token_balance", config.token_balance(), actual_token);
+    Ok(())
+  }
+}
+fn log_drift(name: &str, recorded: u64, actual: u64) {
+  if actual >= recorded {
+    log!(
+      "Reconcile {}: recorded {}, actual {}, surplus {}",
+      name,
+      recorded,
+      actual,
+      actual - recorded
+    );
+  } else {
+    log!(
+      "Reconcile {}: recorded {}, actual {}, shortfall {}",
+      name,
+      recorded,
+      actual,
+      recorded - actual
+    );
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for ConfigReconcile<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("ConfigReconcile try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+
+    let [signer, config_pda, admin_pda, accounts @ ..] = accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(signer)?;
+    check_pda(config_pda)?;
+
+    config_pda.check_borrow_mut()?;
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    let permissions = Admin::signer_permissions(config_pda, config, signer, admin_pda)?;
+    if permissions & Admin::TREASURY == 0 {
+      return Err(Ee::AdminPermission.into());
+    }
+    Ok(Self {
+      signer,
+      config_pda,
+      admin_pda,
+      accounts,
+      config,
+    })
+  }
+}
@@ -0,0 +1,62 @@
+use core::convert::TryFrom;
+use pinocchio::{error::ProgramError, AccountView, ProgramResult};
+use pinocchio_log::log;
+
+use crate::{
+  check_pda, get_time, instructions::check_signer, u8_to_bool, versioned_payload, writable, Config,
+  Ee,
+};
+
+/// Set Config Authorization Flag: the prog_owner sets is_authorized
+pub struct ConfigSetAuthorized<'a> {
+  pub prog_owner: &'a AccountView, //signer
+  pub config_pda: &'a AccountView,
+  pub is_authorized: bool,
+  pub config: &'a mut Config,
+}
+impl<'a> ConfigSetAuthorized<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &35;
+
+  pub fn process(self) -> ProgramResult {
+    let ConfigSetAuthorized {
+      prog_owner: _,
+      config_pda: _,
+      is_authorized,
+      config,
+    } = self;
+    log!("ConfigSetAuthorized process()");
+    config.set_is_authorized(is_authorized);
+    config.set_updated_at(get_time()?);
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for ConfigSetAuthorized<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("ConfigSetAuthorized try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+    let payload = versioned_payload(data, 1)?;
+    let is_authorized = u8_to_bool(payload[0])?;
+
+    let [prog_owner, config_pda] = accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(prog_owner)?;
+    writable(config_pda)?;
+    check_pda(config_pda)?;
+
+    config_pda.check_borrow_mut()?;
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    if config.prog_owner().ne(prog_owner.address()) {
+      return Err(Ee::OnlyProgOwner.into());
+    }
+    Ok(Self {
+      prog_owner,
+      config_pda,
+      is_authorized,
+      config,
+    })
+  }
+}
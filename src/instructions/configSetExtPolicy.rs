@@ -0,0 +1,72 @@
+use core::convert::TryFrom;
+use pinocchio::{error::ProgramError, AccountView, ProgramResult};
+use pinocchio_log::log;
+
+use crate::{
+  check_pda, get_time, instructions::check_signer, parse_u64, u8_to_bool, versioned_payload,
+  writable, Admin, Config, Ee,
+};
+
+/// Set Config Extension Policy: admins with the MINTS permission set the bitmask of Token2022 mint extension types, and whether it is an allowlist or a denylist. Deposits and escrow offers refuse mints breaking it. Queue it as an Action while a timelock delay is set
+pub struct ConfigSetExtPolicy<'a> {
+  pub signer: &'a AccountView,
+  pub config_pda: &'a AccountView,
+  pub admin_pda: &'a AccountView,
+  pub ext_mask: u64,
+  pub allowlist: bool,
+  pub config: &'a mut Config,
+}
+impl<'a> ConfigSetExtPolicy<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &46;
+
+  pub fn process(self) -> ProgramResult {
+    let ConfigSetExtPolicy {
+      signer: _,
+      config_pda: _,
+      admin_pda: _,
+      ext_mask,
+      allowlist,
+      config,
+    } = self;
+    log!("ConfigSetExtPolicy process()");
+    config.set_ext_policy(ext_mask, allowlist);
+    config.set_updated_at(get_time()?);
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for ConfigSetExtPolicy<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("ConfigSetExtPolicy try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+    let payload = versioned_payload(data, 9)?;
+    let ext_mask = parse_u64(&payload[0..8])?;
+    let allowlist = u8_to_bool(payload[8])?;
+    log!("ext_mask: {}, allowlist: {}", ext_mask, payload[8]);
+
+    let [signer, config_pda, admin_pda] = accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(signer)?;
+    writable(config_pda)?;
+    check_pda(config_pda)?;
+
+    config_pda.check_borrow_mut()?;
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    config.check_no_timelock()?;
+    let permissions = Admin::signer_permissions(config_pda, config, signer, admin_pda)?;
+    if permissions & Admin::MINTS == 0 {
+      return Err(Ee::AdminPermission.into());
+    }
+    Ok(Self {
+      signer,
+      config_pda,
+      admin_pda,
+      ext_mask,
+      allowlist,
+      config,
+    })
+  }
+}
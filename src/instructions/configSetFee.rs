@@ -0,0 +1,73 @@
+use core::convert::TryFrom;
+use pinocchio::{error::ProgramError, AccountView, ProgramResult};
+use pinocchio_log::log;
+
+use crate::{
+  check_pda, get_time, instructions::check_signer, parse_u64, u8_to_bool, versioned_payload,
+  writable, Admin, Config, Ee,
+};
+
+/// Set Config Fee: admins with the FEE permission set the fee in basis points and whether it is charged on token X. Queue it as an Action while a timelock delay is set
+pub struct ConfigSetFee<'a> {
+  pub signer: &'a AccountView,
+  pub config_pda: &'a AccountView,
+  pub admin_pda: &'a AccountView,
+  pub fee: u64,
+  pub fee_on_x: bool,
+  pub config: &'a mut Config,
+}
+impl<'a> ConfigSetFee<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &13;
+
+  pub fn process(self) -> ProgramResult {
+    let ConfigSetFee {
+      signer: _,
+      config_pda: _,
+      admin_pda: _,
+      fee,
+      fee_on_x,
+      config,
+    } = self;
+    log!("ConfigSetFee process()");
+    config.set_fee(fee)?;
+    config.set_fee_on_x(fee_on_x);
+    config.set_updated_at(get_time()?);
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for ConfigSetFee<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("ConfigSetFee try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+    let payload = versioned_payload(data, 9)?;
+    let fee = parse_u64(&payload[0..8])?;
+    let fee_on_x = u8_to_bool(payload[8])?;
+    log!("fee: {}, fee_on_x: {}", fee, payload[8]);
+
+    let [signer, config_pda, admin_pda] = accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(signer)?;
+    writable(config_pda)?;
+    check_pda(config_pda)?;
+
+    config_pda.check_borrow_mut()?;
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    config.check_no_timelock()?;
+    let permissions = Admin::signer_permissions(config_pda, config, signer, admin_pda)?;
+    if permissions & Admin::FEE == 0 {
+      return Err(Ee::AdminPermission.into());
+    }
+    Ok(Self {
+      signer,
+      config_pda,
+      admin_pda,
+      fee,
+      fee_on_x,
+      config,
+    })
+  }
+}
@@ -0,0 +1,63 @@
+use core::convert::TryFrom;
+use pinocchio::{error::ProgramError, AccountView, ProgramResult};
+use pinocchio_log::log;
+
+use crate::{
+  check_pda, get_time, instructions::check_signer, to32bytes, versioned_payload, writable, Config,
+  Ee,
+};
+
+/// Set Config Label: the prog_owner replaces the 32-byte label string
+pub struct ConfigSetLabel<'a> {
+  pub prog_owner: &'a AccountView, //signer
+  pub config_pda: &'a AccountView,
+  pub str_u8array: [u8; 32],
+  pub config: &'a mut Config,
+}
+impl<'a> ConfigSetLabel<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &34;
+
+  pub fn process(self) -> ProgramResult {
+    let ConfigSetLabel {
+      prog_owner: _,
+      config_pda: _,
+      str_u8array,
+      config,
+    } = self;
+    log!("ConfigSetLabel process()");
+    config.set_str_u8array(str_u8array);
+    config.set_updated_at(get_time()?);
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for ConfigSetLabel<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("ConfigSetLabel try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+    let payload = versioned_payload(data, 32)?;
+    let str_u8array = *to32bytes(payload)?;
+    log!("str_u8array: {}", &str_u8array);
+
+    let [prog_owner, config_pda] = accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(prog_owner)?;
+    writable(config_pda)?;
+    check_pda(config_pda)?;
+
+    config_pda.check_borrow_mut()?;
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    if config.prog_owner().ne(prog_owner.address()) {
+      return Err(Ee::OnlyProgOwner.into());
+    }
+    Ok(Self {
+      prog_owner,
+      config_pda,
+      str_u8array,
+      config,
+    })
+  }
+}
@@ -0,0 +1,68 @@
+use core::convert::TryFrom;
+use pinocchio::{error::ProgramError, AccountView, ProgramResult};
+use pinocchio_log::log;
+
+use crate::{
+  check_pda, get_time, instructions::check_signer, u8_to_status, versioned_payload, writable,
+  Admin, Config, Ee,
+};
+
+/// Set Config Status: admins with the PAUSE permission set the program status
+pub struct ConfigSetStatus<'a> {
+  pub signer: &'a AccountView,
+  pub config_pda: &'a AccountView,
+  pub admin_pda: &'a AccountView,
+  pub status: u8,
+  pub config: &'a mut Config,
+}
+impl<'a> ConfigSetStatus<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &32;
+
+  pub fn process(self) -> ProgramResult {
+    let ConfigSetStatus {
+      signer: _,
+      config_pda: _,
+      admin_pda: _,
+      status,
+      config,
+    } = self;
+    log!("ConfigSetStatus process()");
+    config.set_status(status);
+    config.set_updated_at(get_time()?);
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for ConfigSetStatus<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("ConfigSetStatus try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+    let payload = versioned_payload(data, 1)?;
+    let status = payload[0];
+    u8_to_status(status)?;
+    log!("status: {}", status);
+
+    let [signer, config_pda, admin_pda] = accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(signer)?;
+    writable(config_pda)?;
+    check_pda(config_pda)?;
+
+    config_pda.check_borrow_mut()?;
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    let permissions = Admin::signer_permissions(config_pda, config, signer, admin_pda)?;
+    if permissions & Admin::PAUSE == 0 {
+      return Err(Ee::AdminPermission.into());
+    }
+    Ok(Self {
+      signer,
+      config_pda,
+      admin_pda,
+      status,
+      config,
+    })
+  }
+}
@@ -1,28 +1,24 @@
 use core::convert::TryFrom;
-use pinocchio::{
-  cpi::{Seed, Signer},
-  error::ProgramError,
-  sysvars::rent::Rent,
-  AccountView, ProgramResult,
-};
+use pinocchio::{error::ProgramError, AccountView, ProgramResult};
 use pinocchio_log::log;
 use pinocchio_system::instructions::Transfer as SystemTransfer;
 
 use crate::{
-  check_sysprog,
-  instructions::{check_pda, check_signer, derive_pda1, parse_u64},
-  none_zero_u64, sol_balc, Ee, PROG_ADDR, VAULT_SEED, VAULT_SIZE,
+  check_sysprog, ensure_sol_vault,
+  instructions::{check_pda, check_signer, parse_u64},
+  none_zero_u64, sol_balc, writable, Config,
 };
 
 // Deposit SOL to program PDA
-// make and rent-funds the vault PDA
-// check the PDA exists and is owned by the program
+// make and rent-funds the vault PDA seeded by the config and the user
+// check the PDA exists, is owned by the program and is bound to the config
 // transfer the SOL amount to the vault
 
 //Deposit Accounts
 pub struct DepositSol<'a> {
   pub user: &'a AccountView,
   pub vault: &'a AccountView,
+  pub config_pda: &'a AccountView,
   pub amount: u64,
 }
 impl<'a> DepositSol<'a> {
@@ -32,10 +28,11 @@ impl<'a> DepositSol<'a> {
     let DepositSol {
       user,
       vault,
+      config_pda,
       amount,
     } = self;
     log!("DepositSol process");
-    ensure_deposit_accounts(user, vault)?;
+    ensure_sol_vault(user, config_pda.address(), vault)?;
 
     log!("DepositSol 2");
     SystemTransfer {
@@ -44,6 +41,7 @@ impl<'a> DepositSol<'a> {
       lamports: amount,
     }
     .invoke()?;
+    Config::from_account_view(config_pda)?.add_sol_balance(amount)?;
     log!("success: {} Lamports deposited to vault", amount);
     Ok(())
   }
@@ -56,11 +54,15 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for DepositSol<'a> {
     let (data, accounts) = value;
     log!("accounts len: {}, data len: {}", accounts.len(), data.len());
 
-    let [user, vault, system_program] = accounts else {
+    let [user, vault, config_pda, system_program] = accounts else {
       return Err(ProgramError::NotEnoughAccountKeys);
     };
     check_signer(user)?;
     check_sysprog(system_program)?;
+    writable(config_pda)?;
+    check_pda(config_pda)?;
+    config_pda.check_borrow_mut()?;
+    Config::check(config_pda)?;
 
     log!("DepositSol data: {}", data);
     let amount = parse_u64(data)?;
@@ -71,47 +73,8 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for DepositSol<'a> {
     Ok(Self {
       user,
       vault,
+      config_pda,
       amount,
     })
   }
 }
-
-/// Ensure the vault exists; if not, create it with PDA seeds. user must be a signer, vault must be writable, and rent minimum must be respected for creation.
-fn ensure_deposit_accounts(user: &AccountView, vault: &AccountView) -> ProgramResult {
-  log!("ensure_deposit_accounts");
-  // Create when empty and fund rent-exempt.
-  if vault.lamports() == 0 {
-    let (expected_vault_pda, bump) = derive_pda1(user.address(), VAULT_SEED)?;
-    if vault.address() != &expected_vault_pda {
-      return Ee::VaultPDA.e();
-    }
-    let signer_seeds = [
-      Seed::from(VAULT_SEED),
-      Seed::from(user.address().as_ref()),
-      Seed::from(core::slice::from_ref(&bump)),
-    ];
-    let signer = Signer::from(&signer_seeds);
-
-    // Make the account rent-exempt.
-    let rent = Rent::from_account_view(vault)?;
-    let needed_lamports = Rent::try_minimum_balance(&rent, VAULT_SIZE)?;
-    log!("needed_lamports: {}", needed_lamports);
-    log!("VAULT_SIZE: {}", VAULT_SIZE);
-
-    pinocchio_system::instructions::CreateAccount {
-      from: user,
-      to: vault,
-      lamports: needed_lamports,
-      space: VAULT_SIZE as u64,
-      owner: &PROG_ADDR,
-    }
-    .invoke_signed(&[signer])?;
-
-    log!("Vault created");
-  } else {
-    // If vault already exists
-    check_pda(vault)?;
-    log!("Vault already exists");
-  }
-  Ok(())
-}
@@ -0,0 +1,250 @@
+use core::convert::TryFrom;
+use pinocchio::{
+  cpi::{Seed, Signer},
+  error::ProgramError,
+  AccountView, ProgramResult,
+};
+use pinocchio_log::log;
+
+use crate::{
+  ata_amount_interface, check_ata_interface, check_atoken_gpvbd, check_decimals_interface,
+  check_mint_interface_prog, check_sysprog, close_pda, data_len, executable, get_time,
+  instructions::check_signer, rent_exempt_mint_interface, rent_exempt_tokacct_interface, writable,
+  Config, Ee, Escrow, TransferCheckedInterface,
+};
+/// Expire Escrow: anyone can crank an expired escrow to return token_x and any token_y to the maker, then close the escrow. Rent goes back to the maker
+pub struct EscrowTokExpire<'a> {
+  pub cranker: &'a AccountView, //signer
+  pub maker: &'a AccountView,
+  pub maker_ata_x: &'a AccountView,
+  pub maker_ata_y: &'a AccountView,
+  pub escrow_ata_x: &'a AccountView,
+  pub escrow_ata_y: &'a AccountView,
+  pub mint_x: &'a AccountView,
+  pub mint_y: &'a AccountView,
+  pub escrow_pda: &'a AccountView,
+  pub config_pda: &'a AccountView,
+  pub token_program_x: &'a AccountView,
+  pub token_program_y: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub atoken_program: &'a AccountView,
+}
+impl<'a> EscrowTokExpire<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &20;
+
+  pub fn process(self) -> ProgramResult {
+    let EscrowTokExpire {
+      cranker,
+      maker,
+      maker_ata_x,
+      maker_ata_y,
+      escrow_ata_x,
+      escrow_ata_y,
+      mint_x,
+      mint_y,
+      escrow_pda,
+      config_pda,
+      token_program_x,
+      token_program_y,
+      system_program,
+      atoken_program: _,
+    } = self;
+    log!("---------== process()");
+    escrow_pda.check_borrow_mut()?;
+    let escrow: &mut Escrow = Escrow::from_account_view(escrow_pda)?;
+    //only the address is checked, so the maker can still exit after the config is closed
+    escrow.check_config(config_pda)?;
+
+    log!("Check args against EscrowPDA fields");
+    let id = escrow.id();
+    let bump = escrow.bump();
+    if maker.address().ne(escrow.maker()) {
+      return Ee::EscrowMaker.e();
+    }
+    if escrow.mint_x().ne(mint_x.address()) {
+      return Ee::EscrowMintX.e();
+    }
+    if escrow.mint_y().ne(mint_y.address()) {
+      return Ee::EscrowMintY.e();
+    }
+    if !escrow.is_expired(get_time()?) {
+      return Ee::EscrowNotExpired.e();
+    }
+
+    let decimal_x = escrow.decimal_x();
+    let decimal_y = escrow.decimal_y();
+    check_decimals_interface(mint_x, decimal_x)?;
+    check_decimals_interface(mint_y, decimal_y)?;
+
+    log!("Make Seed Signer");
+    let id_bytes = &id.to_le_bytes();
+    let signer_seeds = [
+      Seed::from(Escrow::SEED),
+      Seed::from(maker.address().as_ref()),
+      Seed::from(id_bytes),
+      Seed::from(core::slice::from_ref(&bump)),
+    ];
+    let seed_signer = Signer::from(&signer_seeds);
+
+    let amount_x = ata_amount_interface(escrow_ata_x)?;
+    log!("decimal_x: {}, amount_x: {}", decimal_x, amount_x);
+    if amount_x > 0 {
+      log!("Check Maker ATA X");
+      if maker_ata_x.is_data_empty() {
+        log!("Make maker_ata_x");
+        pinocchio_associated_token_account::instructions::Create {
+          funding_account: cranker,
+          account: maker_ata_x,
+          wallet: maker,
+          mint: mint_x,
+          system_program,
+          token_program: token_program_x,
+        }
+        .invoke()?;
+        //Please upgrade to SPL Token 2022 for immutable owner support
+      } else {
+        log!("maker_ata_x has data");
+        check_ata_interface(maker_ata_x, maker, mint_x)?;
+      }
+      writable(maker_ata_x)?;
+      rent_exempt_tokacct_interface(maker_ata_x)?;
+
+      log!("Transfer Token X to Maker ATA X");
+      TransferCheckedInterface {
+        from: escrow_ata_x,
+        mint: mint_x,
+        to: maker_ata_x,
+        authority: escrow_pda,
+        amount: amount_x,
+        decimals: decimal_x,
+        token_program: token_program_x.address(),
+      }
+      .invoke_signed(core::slice::from_ref(&seed_signer))?;
+    }
+
+    if escrow_ata_y.data_len() > 0 {
+      let amount_y = ata_amount_interface(escrow_ata_y)?;
+      log!("decimal_y: {}, amount_y: {}", decimal_y, amount_y);
+      if amount_y > 0 {
+        log!("Check Maker ATA Y");
+        if maker_ata_y.is_data_empty() {
+          log!("Make maker_ata_y");
+          pinocchio_associated_token_account::instructions::Create {
+            funding_account: cranker,
+            account: maker_ata_y,
+            wallet: maker,
+            mint: mint_y,
+            system_program,
+            token_program: token_program_y,
+          }
+          .invoke()?;
+          //Please upgrade to SPL Token 2022 for immutable owner support
+        } else {
+          log!("maker_ata_y has data");
+          check_ata_interface(maker_ata_y, maker, mint_y)?;
+        }
+        writable(maker_ata_y)?;
+        rent_exempt_tokacct_interface(maker_ata_y)?;
+
+        log!("Transfer Token Y to Maker ATA Y");
+        TransferCheckedInterface {
+          from: escrow_ata_y,
+          mint: mint_y,
+          to: maker_ata_y,
+          authority: escrow_pda,
+          amount: amount_y,
+          decimals: decimal_y,
+          token_program: token_program_y.address(),
+        }
+        .invoke_signed(core::slice::from_ref(&seed_signer))?;
+      }
+
+      log!("Close Escrow ATA Y");
+      pinocchio_token_2022::instructions::CloseAccount {
+        account: escrow_ata_y,
+        authority: escrow_pda,
+        destination: maker,
+        token_program: token_program_y.address(),
+      }
+      .invoke_signed(core::slice::from_ref(&seed_signer))?;
+    } else {
+      log!("Escrow_ATA_Y has no data");
+    }
+
+    log!("Close Escrow ATA X");
+    pinocchio_token_2022::instructions::CloseAccount {
+      account: escrow_ata_x,
+      authority: escrow_pda,
+      destination: maker,
+      token_program: token_program_x.address(),
+    }
+    .invoke_signed(&[seed_signer])?;
+
+    //a closed config no longer counts escrows; it cannot be closed while it counts this one
+    if !config_pda.is_data_empty() {
+      Config::from_account_view(config_pda)?.sub_open_escrow();
+    }
+    log!("Close EscrowPDA");
+    close_pda(escrow_pda, maker)
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for EscrowTokExpire<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("EscrowTokExpire try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+    data_len(data, 0)?;
+
+    let [cranker, maker, maker_ata_x, maker_ata_y, escrow_ata_x, escrow_ata_y, mint_x, mint_y, escrow_pda, config_pda, token_program_x, token_program_y, system_program, atoken_program] =
+      accounts
+    else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(cranker)?;
+    executable(token_program_x)?;
+    executable(token_program_y)?;
+    check_sysprog(system_program)?;
+    check_atoken_gpvbd(atoken_program)?;
+    log!("EscrowTokExpire try_from 1");
+
+    writable(maker)?;
+    writable(escrow_ata_x)?;
+    check_ata_interface(escrow_ata_x, escrow_pda, mint_x)?;
+
+    log!("EscrowTokExpire try_from 2");
+    writable(escrow_ata_y)?;
+    //escrow_ata_y may not exist if nobody has taken this escrow
+
+    writable(escrow_pda)?;
+    writable(config_pda)?;
+    if escrow_pda.is_data_empty() {
+      return Err(Ee::EscrowDataEmpty.into());
+    }
+    log!("EscrowTokExpire try_from 5");
+    rent_exempt_mint_interface(mint_x)?;
+    rent_exempt_mint_interface(mint_y)?;
+
+    log!("EscrowTokExpire try_from 6");
+    check_mint_interface_prog(mint_x, token_program_x)?;
+    check_mint_interface_prog(mint_y, token_program_y)?;
+
+    Ok(Self {
+      cranker,
+      maker,
+      maker_ata_x,
+      maker_ata_y,
+      escrow_ata_x,
+      escrow_ata_y,
+      mint_x,
+      mint_y,
+      escrow_pda,
+      config_pda,
+      token_program_x,
+      token_program_y,
+      system_program,
+      atoken_program,
+    })
+  }
+}
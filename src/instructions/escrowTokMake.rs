@@ -8,12 +8,14 @@ use pinocchio::{
 use pinocchio_log::log;
 
 use crate::{
-  ata_balc, check_ata, check_ata_escrow, check_atoken_gpvbd, check_decimals, check_escrow_mints,
-  check_mint0a, check_sysprog, data_len, executable, instructions::check_signer, none_zero_u64,
-  parse_u64, rent_exempt_mint, rent_exempt_tokacct, writable, Config, Ee, Escrow, ID, PROG_ADDR,
+  ata_balc_interface, check_ata_interface, check_atoken_gpvbd, check_decimals_interface,
+  check_escrow_mints, check_mint_interface, check_mint_interface_prog, check_sysprog, data_len,
+  executable, get_time, instructions::check_signer, none_zero_u64, parse_u32, parse_u64,
+  rent_exempt_mint_interface, rent_exempt_tokacct_interface, to32bytes, writable, Config, Ee,
+  Escrow, TransferCheckedInterface, ID, PROG_ADDR,
 };
 
-/// Make Escrow Token Offer
+/// Make Escrow Token Offer: mint_x and mint_y can be from TokenLgc or Token2022; token_program must own mint_x
 pub struct EscrowTokMake<'a> {
   pub maker: &'a AccountView, //signer
   pub maker_ata_x: &'a AccountView,
@@ -28,6 +30,8 @@ pub struct EscrowTokMake<'a> {
   pub amount_x: u64,
   pub amount_y: u64,
   pub id: u64,
+  pub expires_at: u32,
+  pub taker: Address,
   pub decimal_x: u8,
   pub decimal_y: u8,
 }
@@ -51,10 +55,15 @@ impl<'a> EscrowTokMake<'a> {
       amount_x,
       amount_y,
       id,
+      expires_at,
+      taker,
     } = self;
     log!("---------== process()");
     config_pda.check_borrow_mut()?;
-    let _config: &mut Config = Config::from_account_view(&config_pda)?;
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    config.check_not_paused()?;
+    config.check_mint_extensions(mint_x)?;
+    config.check_mint_extensions(mint_y)?;
 
     /*let bump = unsafe { *(data.as_ptr() as *const u8) }.to_le_bytes();
     if bump.len() != 1 { return Err(..);  };   bump.as_ref()*/
@@ -113,33 +122,41 @@ impl<'a> EscrowTokMake<'a> {
       //Please upgrade to SPL Token 2022 for immutable owner support
     } else {
       log!("escrow_ata_x has data");
-      check_ata_escrow(escrow_ata_x, escrow_pda, mint_x)?;
+      check_ata_interface(escrow_ata_x, escrow_pda, mint_x)?;
     }
     writable(escrow_ata_x)?;
-    rent_exempt_tokacct(escrow_ata_x)?;
+    rent_exempt_tokacct_interface(escrow_ata_x)?;
     log!("Vault ATA is found/verified");
 
-    pinocchio_token::instructions::TransferChecked {
+    let fee_x = TransferCheckedInterface {
       from: maker_ata_x,
       mint: mint_x,
       to: escrow_ata_x,
       authority: maker,
       amount: amount_x, // *(data.as_ptr().add(1 + 8) as *const u64)
       decimals: decimal_x,
+      token_program: token_program.address(),
     }
     .invoke()?;
     log!("tokens sent from maker_ata_x");
 
-    let escrow: &mut Escrow = Escrow::from_account_view(&escrow_pda)?;
+    let escrow: &mut Escrow = Escrow::init_account_view(escrow_pda)?;
+    escrow.set_config(config_pda.address());
     escrow.set_maker(maker.address());
     escrow.set_mint_x(mint_x.address());
     escrow.set_mint_y(mint_y.address());
     escrow.set_id(id)?;
     escrow.set_amount_x(amount_x)?;
     escrow.set_amount_y(amount_y)?; // unsafe { *(data.as_ptr().add(1) as *const u64) };
+                                    //the escrow can only hand out what it received after any Token2022 transfer fee
+    escrow.set_remaining_x(amount_x - fee_x);
+    escrow.set_received_y(0);
+    escrow.set_expires_at(expires_at);
+    escrow.set_taker(&taker);
     escrow.set_decimal_x(decimal_x);
     escrow.set_decimal_y(decimal_y);
     escrow.set_bump(bump); // unsafe { *data.as_ptr() };
+    config.add_open_escrow()?;
 
     Ok(())
   }
@@ -164,18 +181,18 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for EscrowTokMake<'a> {
     log!("EscrowTokMake try_from 3");
 
     writable(maker_ata_x)?;
-    check_ata(maker_ata_x, maker, mint_x)?;
+    check_ata_interface(maker_ata_x, maker, mint_x)?;
     writable(escrow_pda)?;
     writable(config_pda)?;
     log!("EscrowTokMake try_from 4");
 
-    //2x u8 takes 2 + 2x u64 takes 16 bytes
-    data_len(data, 26)?;
+    //2x u8 takes 2 + 3x u64 takes 24 + 1x u32 takes 4 + 1x Address takes 32 bytes
+    data_len(data, 62)?;
     let decimal_x = data[0];
     let amount_x = parse_u64(&data[1..9])?;
     log!("decimal_x: {}, amount_x: {}", decimal_x, amount_x);
     none_zero_u64(amount_x)?;
-    ata_balc(maker_ata_x, amount_x)?;
+    ata_balc_interface(maker_ata_x, amount_x)?;
 
     let decimal_y = data[9];
     let amount_y = parse_u64(&data[10..18])?;
@@ -185,17 +202,25 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for EscrowTokMake<'a> {
     let id = parse_u64(&data[18..26])?;
     log!("id: {}", id);
 
+    let expires_at = parse_u32(&data[26..30])?;
+    log!("expires_at: {}", expires_at);
+    if expires_at > 0 && expires_at <= get_time()? {
+      return Err(Ee::EscrowExpired.into());
+    }
+
+    //zeroed taker address makes this escrow public
+    let taker = Address::new_from_array(*to32bytes(&data[30..62])?);
+
     log!("EscrowTokMake try_from 5");
     check_escrow_mints(mint_x, mint_y)?;
-    rent_exempt_mint(mint_x)?;
-    rent_exempt_mint(mint_y)?;
-    //TODO: fee is part of exchange amount
+    rent_exempt_mint_interface(mint_x)?;
+    rent_exempt_mint_interface(mint_y)?;
 
     log!("EscrowTokMake try_from 6");
-    check_decimals(mint_x, decimal_x)?;
-    check_decimals(mint_y, decimal_y)?;
-    check_mint0a(mint_x, token_program)?;
-    check_mint0a(mint_y, token_program)?; // Not needed since CPI since deposit will fail if not owned by token program
+    check_mint_interface_prog(mint_x, token_program)?;
+    check_mint_interface(mint_y)?; // mint_y can be owned by the other token program
+    check_decimals_interface(mint_x, decimal_x)?;
+    check_decimals_interface(mint_y, decimal_y)?;
 
     Ok(Self {
       maker,
@@ -211,6 +236,8 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for EscrowTokMake<'a> {
       amount_x,
       amount_y,
       id,
+      expires_at,
+      taker,
       decimal_x,
       decimal_y,
     })
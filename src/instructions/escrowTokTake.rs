@@ -5,15 +5,20 @@ use pinocchio::{
   AccountView, ProgramResult,
 };
 use pinocchio_log::log;
-use pinocchio_token::state::TokenAccount;
 
 use crate::{
-  check_ata, check_ata_escrow, check_atoken_gpvbd, check_decimals, check_escrow_mints,
-  check_mint0a, check_sysprog, data_len, executable, instructions::check_signer, none_zero_u64,
-  parse_u64, rent_exempt_mint, rent_exempt_tokacct, writable, Config, Ee, Escrow,
+  ata_amount_interface, check_ata_interface, check_atoken_gpvbd, check_decimals_interface,
+  check_escrow_mints, check_mint_interface_prog, check_sysprog, check_vault, close_pda, data_len,
+  executable, get_time, instructions::check_signer, mul_div_ceil, none_zero_u64, parse_u64,
+  rent_exempt_mint_interface, rent_exempt_tokacct_interface, writable, Config, Ee, Escrow,
+  TransferCheckedInterface,
 };
-//TODO: add Token2022 interface
-/// Take Escrow Token Offer
+
+/// Take Escrow Token Offer: token_program_x and token_program_y must own mint_x and mint_y
+/// amount_x is the token_x to take, amount_y is the max token_y the taker would pay.
+/// The offer can be partially filled at the fixed amount_y / amount_x price. When fully filled, all token_y is sent to the maker and the escrow is closed
+/// A private escrow can only be taken by its designated taker
+/// The protocol fee in Config is taken from the token_x or token_y leg into the vault ATA of that leg
 pub struct EscrowTokTake<'a> {
   pub taker: &'a AccountView, //signer
   pub taker_ata_x: &'a AccountView,
@@ -24,7 +29,12 @@ pub struct EscrowTokTake<'a> {
   pub mint_y: &'a AccountView,
   pub escrow_pda: &'a AccountView,
   pub config_pda: &'a AccountView,
-  pub token_program: &'a AccountView,
+  pub maker: &'a AccountView,
+  pub maker_ata_y: &'a AccountView,
+  pub vault: &'a AccountView,
+  pub vault_ata: &'a AccountView,
+  pub token_program_x: &'a AccountView,
+  pub token_program_y: &'a AccountView,
   pub system_program: &'a AccountView,
   pub atoken_program: &'a AccountView,
   pub amount_x: u64,
@@ -47,7 +57,12 @@ impl<'a> EscrowTokTake<'a> {
       mint_y,
       escrow_pda,
       config_pda,
-      token_program,
+      maker,
+      maker_ata_y,
+      vault,
+      vault_ata,
+      token_program_x,
+      token_program_y,
       system_program,
       atoken_program: _,
       amount_x,
@@ -58,30 +73,88 @@ impl<'a> EscrowTokTake<'a> {
     } = self;
     log!("---------== process()");
     config_pda.check_borrow_mut()?;
-    let _config: &mut Config = Config::from_account_view(&config_pda)?;
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    config.check_not_paused()?;
+    check_vault(vault, config.vault())?;
 
     escrow_pda.check_borrow_mut()?;
-    let escrow: &mut Escrow = Escrow::from_account_view(&escrow_pda)?;
+    let escrow: &mut Escrow = Escrow::from_account_view(escrow_pda)?;
+    escrow.check_config(config_pda)?;
+    //the bound config's policy may have tightened since this offer was made
+    config.check_mint_extensions(mint_x)?;
+    config.check_mint_extensions(mint_y)?;
 
     log!("Check args against EscrowPDA fields");
     //cannot convert the maker in EscrowPDA from Pubkey to AccountView! Also hide the maker
     let bump = escrow.bump();
-    let maker = escrow.maker();
+    if maker.address().ne(escrow.maker()) {
+      return Ee::EscrowMaker.e();
+    }
     if escrow.mint_x().ne(mint_x.address()) {
       return Ee::EscrowMintX.e();
     }
     if escrow.mint_y().ne(mint_y.address()) {
       return Ee::EscrowMintY.e();
     }
-    if escrow.amount_x() != amount_x {
+    if escrow.id() != id {
+      return Ee::EscrowId.e();
+    }
+    if !escrow.is_public() && escrow.taker().ne(taker.address()) {
+      return Ee::EscrowTaker.e();
+    }
+    if escrow.is_expired(get_time()?) {
+      return Ee::EscrowExpired.e();
+    }
+    let remaining_x = escrow.remaining_x();
+    if amount_x > remaining_x {
       return Ee::InputAmountX.e();
     }
-    if escrow.amount_y() != amount_y {
+    //round up so the taker never pays less than the escrow price
+    let amount_y_due = mul_div_ceil(amount_x, escrow.amount_y(), escrow.amount_x())?;
+    log!(
+      "remaining_x: {}, amount_y_due: {}",
+      remaining_x,
+      amount_y_due
+    );
+    if amount_y_due > amount_y {
       return Ee::InputAmountY.e();
     }
-    if escrow.id() != id {
-      return Ee::EscrowId.e();
+    if ata_amount_interface(taker_ata_y)? < amount_y_due {
+      return Ee::TakerInsuffTokenY.e();
+    } //ata_balc(taker_ata_y, amount_y_due)?;
+
+    let fee_on_x = config.fee_on_x();
+    let (fee_x, fee_y) = if fee_on_x {
+      (config.fee_amount(amount_x)?, 0)
+    } else {
+      (0, config.fee_amount(amount_y_due)?)
+    };
+    log!("fee_x: {}, fee_y: {}", fee_x, fee_y);
+    let (fee_mint, fee_token_program) = if fee_on_x {
+      (mint_x, token_program_x)
+    } else {
+      (mint_y, token_program_y)
+    };
+
+    log!("Check Vault ATA");
+    if vault_ata.is_data_empty() {
+      log!("Make vault_ata");
+      pinocchio_associated_token_account::instructions::Create {
+        funding_account: taker,
+        account: vault_ata,
+        wallet: vault,
+        mint: fee_mint,
+        system_program,
+        token_program: fee_token_program,
+      }
+      .invoke()?;
+      //Please upgrade to SPL Token 2022 for immutable owner support
+    } else {
+      log!("vault_ata has data");
+      check_ata_interface(vault_ata, vault, fee_mint)?;
     }
+    writable(vault_ata)?;
+    rent_exempt_tokacct_interface(vault_ata)?;
 
     log!("Check Escrow ATA Y");
     if escrow_ata_y.is_data_empty() {
@@ -92,16 +165,16 @@ impl<'a> EscrowTokTake<'a> {
         wallet: escrow_pda,
         mint: mint_y,
         system_program,
-        token_program,
+        token_program: token_program_y,
       }
       .invoke()?;
       //Please upgrade to SPL Token 2022 for immutable owner support
     } else {
       log!("escrow_ata_y has data");
-      check_ata_escrow(escrow_ata_y, escrow_pda, mint_y)?;
+      check_ata_interface(escrow_ata_y, escrow_pda, mint_y)?;
     }
     writable(escrow_ata_y)?;
-    rent_exempt_tokacct(escrow_ata_y)?;
+    rent_exempt_tokacct_interface(escrow_ata_y)?;
 
     log!("Check Taker ATA X");
     if taker_ata_x.is_data_empty() {
@@ -112,49 +185,154 @@ impl<'a> EscrowTokTake<'a> {
         wallet: taker,
         mint: mint_x,
         system_program,
-        token_program,
+        token_program: token_program_x,
       }
       .invoke()?;
       //Please upgrade to SPL Token 2022 for immutable owner support
     } else {
       log!("taker_ata_x has data");
-      check_ata(taker_ata_x, taker, mint_x)?;
+      check_ata_interface(taker_ata_x, taker, mint_x)?;
     }
     writable(taker_ata_x)?;
-    rent_exempt_tokacct(taker_ata_x)?;
+    rent_exempt_tokacct_interface(taker_ata_x)?;
 
     log!("Transfer Token Y to Escrow ATA Y");
-    pinocchio_token::instructions::TransferChecked {
+    let withheld_y = TransferCheckedInterface {
       from: taker_ata_y,
       mint: mint_y,
       to: escrow_ata_y,
       authority: taker,
-      amount: amount_y,
+      amount: amount_y_due - fee_y,
       decimals: decimal_y,
+      token_program: token_program_y.address(),
     }
     .invoke()?;
 
+    //Token2022 transfer fees withheld from the program fees sent to the vault
+    let mut withheld_fees = 0;
+    if fee_y > 0 {
+      log!("Transfer Token Y fee to Vault ATA");
+      withheld_fees += TransferCheckedInterface {
+        from: taker_ata_y,
+        mint: mint_y,
+        to: vault_ata,
+        authority: taker,
+        amount: fee_y,
+        decimals: decimal_y,
+        token_program: token_program_y.address(),
+      }
+      .invoke()?;
+    }
+
     log!("Make Seed Signer");
     let id_bytes = &id.to_le_bytes();
     let signer_seeds = [
       Seed::from(Escrow::SEED),
-      Seed::from(maker.as_ref()),
+      Seed::from(maker.address().as_ref()),
       Seed::from(id_bytes),
       Seed::from(core::slice::from_ref(&bump)),
     ];
     let seed_signer = Signer::from(&signer_seeds);
 
     log!("Transfer Token X to Taker ATA X");
-    pinocchio_token::instructions::TransferChecked {
+    TransferCheckedInterface {
       from: escrow_ata_x,
       mint: mint_x,
       to: taker_ata_x,
       authority: escrow_pda,
-      amount: amount_x,
+      amount: amount_x - fee_x,
       decimals: decimal_x,
+      token_program: token_program_x.address(),
+    }
+    .invoke_signed(core::slice::from_ref(&seed_signer))?;
+
+    if fee_x > 0 {
+      log!("Transfer Token X fee to Vault ATA");
+      withheld_fees += TransferCheckedInterface {
+        from: escrow_ata_x,
+        mint: mint_x,
+        to: vault_ata,
+        authority: escrow_pda,
+        amount: fee_x,
+        decimals: decimal_x,
+        token_program: token_program_x.address(),
+      }
+      .invoke_signed(core::slice::from_ref(&seed_signer))?;
+    }
+
+    config.add_token_balance(fee_x + fee_y - withheld_fees)?;
+
+    let remaining_x = remaining_x - amount_x;
+    let received_y = escrow
+      .received_y()
+      .checked_add(amount_y_due - fee_y - withheld_y)
+      .ok_or(ProgramError::ArithmeticOverflow)?;
+    escrow.set_remaining_x(remaining_x);
+    escrow.set_received_y(received_y);
+    log!("remaining_x: {}, received_y: {}", remaining_x, received_y);
+    if remaining_x > 0 {
+      return Ok(());
+    }
+
+    log!("Escrow fully filled. Check Maker ATA Y");
+    if maker_ata_y.is_data_empty() {
+      log!("Make maker_ata_y");
+      pinocchio_associated_token_account::instructions::Create {
+        funding_account: taker,
+        account: maker_ata_y,
+        wallet: maker,
+        mint: mint_y,
+        system_program,
+        token_program: token_program_y,
+      }
+      .invoke()?;
+      //Please upgrade to SPL Token 2022 for immutable owner support
+    } else {
+      log!("maker_ata_y has data");
+      check_ata_interface(maker_ata_y, maker, mint_y)?;
     }
-    .invoke_signed(&[seed_signer])?;
-    Ok(())
+    writable(maker_ata_y)?;
+    rent_exempt_tokacct_interface(maker_ata_y)?;
+
+    log!("Transfer Token Y to Maker ATA Y");
+    TransferCheckedInterface {
+      from: escrow_ata_y,
+      mint: mint_y,
+      to: maker_ata_y,
+      authority: escrow_pda,
+      amount: ata_amount_interface(escrow_ata_y)?,
+      decimals: decimal_y,
+      token_program: token_program_y.address(),
+    }
+    .invoke_signed(core::slice::from_ref(&seed_signer))?;
+    escrow.set_received_y(0);
+
+    if ata_amount_interface(escrow_ata_x)? > 0 {
+      log!("Found unknown token in Escrow ATA X. Maker to cancel this escrow");
+      return Ok(());
+    }
+
+    log!("Close Escrow ATA Y");
+    pinocchio_token_2022::instructions::CloseAccount {
+      account: escrow_ata_y,
+      authority: escrow_pda,
+      destination: maker,
+      token_program: token_program_y.address(),
+    }
+    .invoke_signed(core::slice::from_ref(&seed_signer))?;
+
+    log!("Close Escrow ATA X");
+    pinocchio_token_2022::instructions::CloseAccount {
+      account: escrow_ata_x,
+      authority: escrow_pda,
+      destination: maker,
+      token_program: token_program_x.address(),
+    }
+    .invoke_signed(core::slice::from_ref(&seed_signer))?;
+
+    config.sub_open_escrow();
+    log!("Close EscrowPDA");
+    close_pda(escrow_pda, maker)
   }
 }
 impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for EscrowTokTake<'a> {
@@ -165,27 +343,29 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for EscrowTokTake<'a> {
     let (data, accounts) = value;
     log!("accounts len: {}, data len: {}", accounts.len(), data.len());
 
-    let [taker, taker_ata_x, taker_ata_y, escrow_ata_x, escrow_ata_y, mint_x, mint_y, escrow_pda, config_pda, token_program, system_program, atoken_program] =
+    let [taker, taker_ata_x, taker_ata_y, escrow_ata_x, escrow_ata_y, mint_x, mint_y, escrow_pda, config_pda, maker, maker_ata_y, vault, vault_ata, token_program_x, token_program_y, system_program, atoken_program] =
       accounts
     else {
       return Err(ProgramError::NotEnoughAccountKeys);
     };
     check_signer(taker)?;
-    executable(token_program)?;
+    executable(token_program_x)?;
+    executable(token_program_y)?;
     check_sysprog(system_program)?;
     check_atoken_gpvbd(atoken_program)?;
     log!("EscrowTokTake try_from 1");
 
     writable(taker_ata_y)?;
-    check_ata(taker_ata_y, taker, mint_y)?;
+    check_ata_interface(taker_ata_y, taker, mint_y)?;
     log!("EscrowTokTake try_from 2");
 
     writable(escrow_ata_x)?;
-    check_ata(escrow_ata_x, escrow_pda, mint_x)?;
+    check_ata_interface(escrow_ata_x, escrow_pda, mint_x)?;
     log!("EscrowTokTake try_from 3");
 
     writable(escrow_pda)?;
     writable(config_pda)?;
+    writable(maker)?;
     if escrow_pda.is_data_empty() {
       return Err(Ee::EscrowDataEmpty.into());
     }
@@ -198,35 +378,28 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for EscrowTokTake<'a> {
     log!("decimal_x: {}, amount_x: {}", decimal_x, amount_x);
     none_zero_u64(amount_x)?;
 
-    let escrow_ata_x_info = TokenAccount::from_account_view(escrow_ata_x)?;
-    if escrow_ata_x_info.amount() < amount_x {
+    if ata_amount_interface(escrow_ata_x)? < amount_x {
       return Err(Ee::EscrowInsuffTokenX.into());
     } //ata_balc(escrow_ata_x, amount_x)?;
-      //TODO: unknown token received by Escrow
 
     let decimal_y = data[9];
     let amount_y = parse_u64(&data[10..18])?;
     log!("decimal_y: {}, amount_y: {}", decimal_y, amount_y);
     none_zero_u64(amount_y)?;
-    let taker_ata_y_info = TokenAccount::from_account_view(taker_ata_y)?;
-    if taker_ata_y_info.amount() < amount_y {
-      return Err(Ee::TakerInsuffTokenY.into());
-    } //ata_balc(taker_ata_y, amount_y)?;
 
     let id = parse_u64(&data[18..26])?;
     log!("id: {}", id);
 
     log!("EscrowTokTake try_from 5");
     check_escrow_mints(mint_x, mint_y)?;
-    rent_exempt_mint(mint_x)?;
-    rent_exempt_mint(mint_y)?;
-    //TODO: fee is part of exchange amount
+    rent_exempt_mint_interface(mint_x)?;
+    rent_exempt_mint_interface(mint_y)?;
 
     log!("EscrowTokTake try_from 6");
-    check_decimals(mint_x, decimal_x)?;
-    check_decimals(mint_y, decimal_y)?;
-    check_mint0a(mint_x, token_program)?;
-    check_mint0a(mint_y, token_program)?; // Not needed since CPI since deposit will fail if not owned by token program
+    check_mint_interface_prog(mint_x, token_program_x)?;
+    check_mint_interface_prog(mint_y, token_program_y)?;
+    check_decimals_interface(mint_x, decimal_x)?;
+    check_decimals_interface(mint_y, decimal_y)?;
 
     Ok(Self {
       taker,
@@ -238,7 +411,12 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for EscrowTokTake<'a> {
       mint_y,
       escrow_pda,
       config_pda,
-      token_program,
+      maker,
+      maker_ata_y,
+      vault,
+      vault_ata,
+      token_program_x,
+      token_program_y,
       system_program,
       atoken_program,
       amount_x,
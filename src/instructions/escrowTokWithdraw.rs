@@ -5,15 +5,14 @@ use pinocchio::{
   AccountView, ProgramResult,
 };
 use pinocchio_log::log;
-use pinocchio_token::state::TokenAccount;
 
 use crate::{
-  check_ata, check_atoken_gpvbd, check_decimals, check_mint0a, check_sysprog, data_len, executable,
-  instructions::check_signer, none_zero_u64, rent_exempt_mint, rent_exempt_tokacct, writable,
-  Config, Ee, Escrow,
+  ata_amount_interface, check_ata_interface, check_atoken_gpvbd, check_decimals_interface,
+  check_mint_interface_prog, check_sysprog, data_len, executable, instructions::check_signer,
+  rent_exempt_mint_interface, rent_exempt_tokacct_interface, writable, Ee, Escrow,
+  TransferCheckedInterface,
 };
-//TODO: add Token2022 interface
-/// Make Withdraw Escrow Token Y
+/// Make Withdraw Escrow Token Y: sweep token_y paid by takers so far. The escrow stays open for the remaining token_x
 pub struct EscrowTokWithdraw<'a> {
   pub maker: &'a AccountView, //signer
   pub maker_ata_x: &'a AccountView,
@@ -24,7 +23,8 @@ pub struct EscrowTokWithdraw<'a> {
   pub mint_y: &'a AccountView,
   pub escrow_pda: &'a AccountView,
   pub config_pda: &'a AccountView,
-  pub token_program: &'a AccountView,
+  pub token_program_x: &'a AccountView,
+  pub token_program_y: &'a AccountView,
   pub system_program: &'a AccountView,
   pub atoken_program: &'a AccountView,
 }
@@ -34,24 +34,24 @@ impl<'a> EscrowTokWithdraw<'a> {
   pub fn process(self) -> ProgramResult {
     let EscrowTokWithdraw {
       maker,
-      maker_ata_x,
+      maker_ata_x: _,
       maker_ata_y,
-      escrow_ata_x,
+      escrow_ata_x: _,
       escrow_ata_y,
       mint_x,
       mint_y,
       escrow_pda,
       config_pda,
-      token_program,
+      token_program_x: _,
+      token_program_y,
       system_program,
       atoken_program: _,
     } = self;
     log!("---------== process()");
-    config_pda.check_borrow_mut()?;
-    let _config: &mut Config = Config::from_account_view(&config_pda)?;
-
     escrow_pda.check_borrow_mut()?;
-    let escrow: &mut Escrow = Escrow::from_account_view(&escrow_pda)?;
+    let escrow: &mut Escrow = Escrow::from_account_view(escrow_pda)?;
+    //only the address is checked, so the maker can still exit after the config is closed
+    escrow.check_config(config_pda)?;
 
     log!("Check args against EscrowPDA fields");
     let id = escrow.id();
@@ -68,18 +68,18 @@ impl<'a> EscrowTokWithdraw<'a> {
 
     let decimal_x = escrow.decimal_x();
     log!("decimal_: {}", decimal_x);
-    let amount_y = escrow.amount_y();
+    let amount_y = escrow.received_y();
     let decimal_y = escrow.decimal_y();
-    log!("decimal_y: {}, amount_y: {}", decimal_y, amount_y);
-    none_zero_u64(amount_y)?;
-    check_decimals(mint_x, decimal_x)?;
-    check_decimals(mint_y, decimal_y)?;
+    log!("decimal_y: {}, received_y: {}", decimal_y, amount_y);
+    if amount_y == 0 {
+      return Ee::EscrowInsuffTokenY.e();
+    }
+    check_decimals_interface(mint_x, decimal_x)?;
+    check_decimals_interface(mint_y, decimal_y)?;
 
-    let escrow_ata_y_info = TokenAccount::from_account_view(escrow_ata_y)?;
-    if escrow_ata_y_info.amount() < amount_y {
+    if ata_amount_interface(escrow_ata_y)? < amount_y {
       return Ee::EscrowInsuffTokenY.e();
     } //ata_balc(escrow_ata_y, amount_y)?;
-    drop(escrow_ata_y_info);
 
     log!("Check Maker ATA Y");
     if maker_ata_y.is_data_empty() {
@@ -90,16 +90,16 @@ impl<'a> EscrowTokWithdraw<'a> {
         wallet: maker,
         mint: mint_y,
         system_program,
-        token_program,
+        token_program: token_program_y,
       }
       .invoke()?;
       //Please upgrade to SPL Token 2022 for immutable owner support
     } else {
       log!("maker_ata_y has data");
-      check_ata(maker_ata_y, maker, mint_y)?;
+      check_ata_interface(maker_ata_y, maker, mint_y)?;
     }
     writable(maker_ata_y)?;
-    rent_exempt_tokacct(maker_ata_y)?;
+    rent_exempt_tokacct_interface(maker_ata_y)?;
 
     log!("Make Seed Signer");
     let id_bytes = &id.to_le_bytes();
@@ -114,95 +114,17 @@ impl<'a> EscrowTokWithdraw<'a> {
     log!("Transfer Token Y to Maker ATA Y");
     //escrow_pda.check_borrow_mut()?;
     //escrow_ata_y.check_borrow_mut()?;
-    pinocchio_token::instructions::TransferChecked {
+    TransferCheckedInterface {
       from: escrow_ata_y,
       mint: mint_y,
       to: maker_ata_y,
       authority: escrow_pda,
       amount: amount_y,
       decimals: decimal_y,
+      token_program: token_program_y.address(),
     }
-    .invoke_signed(&[seed_signer.clone()])?;
-
-    log!("Check Unknown token in Escrow ATA X");
-    let escrow_ata_x_info = TokenAccount::from_account_view(escrow_ata_x)?;
-    let unknown_amt_x = escrow_ata_x_info.amount();
-    drop(escrow_ata_x_info);
-    if unknown_amt_x > 0 {
-      log!("Found unknown token in Escrow ATA X");
-      if maker_ata_x.is_data_empty() {
-        log!("Make maker_ata_x");
-        pinocchio_associated_token_account::instructions::Create {
-          funding_account: maker,
-          account: maker_ata_x,
-          wallet: maker,
-          mint: mint_x,
-          system_program,
-          token_program,
-        }
-        .invoke()?;
-        //Please upgrade to SPL Token 2022 for immutable owner support
-      } else {
-        log!("maker_ata_x has data");
-        check_ata(maker_ata_x, maker, mint_x)?;
-      }
-      writable(maker_ata_x)?;
-      rent_exempt_tokacct(maker_ata_x)?;
-
-      log!("Send token x to maker_ata_x");
-      pinocchio_token::instructions::TransferChecked {
-        from: escrow_ata_x,
-        mint: mint_x,
-        to: maker_ata_x,
-        authority: escrow_pda,
-        amount: unknown_amt_x,
-        decimals: decimal_x,
-      }
-      .invoke_signed(&[seed_signer.clone()])?;
-    } else {
-      log!("No token in Escrow ATA X");
-    }
-
-    log!("Close Escrow ATA Y");
-    //escrow_ata_y.check_borrow_mut()?;
-    //escrow_pda.check_borrow_mut()?;
-    pinocchio_token::instructions::CloseAccount {
-      account: escrow_ata_y,
-      authority: escrow_pda,
-      destination: maker,
-    }
-    .invoke_signed(&[seed_signer.clone()])?;
-
-    log!("Close Escrow ATA X");
-    //escrow_pda.check_borrow_mut()?;
-    //escrow_ata_x.check_borrow_mut()?;
-    pinocchio_token::instructions::CloseAccount {
-      account: escrow_ata_x,
-      authority: escrow_pda,
-      destination: maker,
-    }
-    .invoke_signed(&[seed_signer.clone()])?;
-
-    log!("Close EscrowPDA 1");
-    //set the first byte to 255
-    {
-      let mut data = escrow_pda.try_borrow_mut()?;
-      data[0] = 0xff;
-    }
-    log!("Close EscrowPDA 2");
-    let sum_lam = maker
-      .lamports()
-      .checked_add(escrow_pda.lamports())
-      .ok_or_else(|| ProgramError::ArithmeticOverflow)?;
-    maker.set_lamports(sum_lam);
-    escrow_pda.set_lamports(0);
-    //https://learn.blueshift.gg/en/courses/pinocchio-for-dummies/pinocchio-accounts
-    //*maker.try_borrow_mut_lamports()? += *escrow_pda.try_borrow_lamports()?;
-
-    log!("Close EscrowPDA 3");
-    //resize the account to only the 1st byte
-    escrow_pda.resize(1)?;
-    escrow_pda.close()?;
+    .invoke_signed(&[seed_signer])?;
+    escrow.set_received_y(0);
     Ok(())
   }
 }
@@ -215,23 +137,24 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for EscrowTokWithdraw<'a> {
     log!("accounts len: {}, data len: {}", accounts.len(), data.len());
     data_len(data, 0)?;
 
-    let [maker, maker_ata_x, maker_ata_y, escrow_ata_x, escrow_ata_y, mint_x, mint_y, escrow_pda, config_pda, token_program, system_program, atoken_program] =
+    let [maker, maker_ata_x, maker_ata_y, escrow_ata_x, escrow_ata_y, mint_x, mint_y, escrow_pda, config_pda, token_program_x, token_program_y, system_program, atoken_program] =
       accounts
     else {
       return Err(ProgramError::NotEnoughAccountKeys);
     };
     check_signer(maker)?;
-    executable(token_program)?;
+    executable(token_program_x)?;
+    executable(token_program_y)?;
     check_sysprog(system_program)?;
     check_atoken_gpvbd(atoken_program)?;
     log!("EscrowTokWithdraw try_from 1");
 
     writable(escrow_ata_x)?;
-    check_ata(escrow_ata_x, escrow_pda, mint_x)?;
+    check_ata_interface(escrow_ata_x, escrow_pda, mint_x)?;
 
     log!("EscrowTokWithdraw try_from 2");
     writable(escrow_ata_y)?;
-    check_ata(escrow_ata_y, escrow_pda, mint_y)?;
+    check_ata_interface(escrow_ata_y, escrow_pda, mint_y)?;
     log!("EscrowTokWithdraw try_from 3");
 
     writable(escrow_pda)?;
@@ -240,13 +163,12 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for EscrowTokWithdraw<'a> {
       return Err(Ee::EscrowDataEmpty.into());
     }
     log!("EscrowTokWithdraw try_from 5");
-    rent_exempt_mint(mint_x)?;
-    rent_exempt_mint(mint_y)?;
-    //TODO: fee is part of exchange amount
+    rent_exempt_mint_interface(mint_x)?;
+    rent_exempt_mint_interface(mint_y)?;
 
     log!("EscrowTokWithdraw try_from 6");
-    check_mint0a(mint_x, token_program)?;
-    check_mint0a(mint_y, token_program)?; // Not needed since CPI since deposit will fail if not owned by token program
+    check_mint_interface_prog(mint_x, token_program_x)?;
+    check_mint_interface_prog(mint_y, token_program_y)?;
 
     Ok(Self {
       maker,
@@ -258,7 +180,8 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for EscrowTokWithdraw<'a> {
       mint_y,
       escrow_pda,
       config_pda,
-      token_program,
+      token_program_x,
+      token_program_y,
       system_program,
       atoken_program,
     })
@@ -1,6 +1,6 @@
 use crate::{
   check_sysprog, data_len, derive_pda1, get_time, instructions::check_signer, not_initialized,
-  parse_u64, rent_exempt_mint22, to32bytes, u8_to_bool, Config, Ee, PROG_ADDR, VAULT_SEED,
+  parse_u64, to32bytes, u8_to_bool, Config, Ee, PROG_ADDR, VAULT_SEED,
 };
 use core::convert::TryFrom;
 use pinocchio::{
@@ -16,8 +16,6 @@ pub struct InitConfig<'a> {
   pub signer: &'a AccountView,
   pub config_pda: &'a AccountView,
   pub prog_owner: &'a Address,
-  pub prog_admin: &'a Address,
-  pub mints: [&'a Address; 4],
   pub vault: &'a Address,
   pub system_program: &'a AccountView,
   pub fee: u64,
@@ -34,8 +32,6 @@ impl<'a> InitConfig<'a> {
       signer,
       config_pda,
       prog_owner,
-      prog_admin,
-      mints,
       vault,
       system_program: _,
       fee,
@@ -79,11 +75,10 @@ impl<'a> InitConfig<'a> {
     let time = get_time()?;
 
     self.config_pda.check_borrow_mut()?;
-    let config = Config::from_account_view(&config_pda)?;
-    config.set_mints(mints);
+    let config = Config::init_account_view(config_pda)?;
     config.set_vault(vault);
     config.set_prog_owner(prog_owner);
-    config.set_admin(prog_admin);
+    config.set_seed_owner(prog_owner);
     config.set_str_u8array(str_u8array);
     config.set_fee(fee)?;
     config.set_updated_at(time);
@@ -91,6 +86,7 @@ impl<'a> InitConfig<'a> {
     config.set_status(status);
     config.set_vault_bump(vault_bump);
     config.set_bump(bump);
+    config.set_ext_policy(Config::DEFAULT_EXT_DENY, false);
     Ok(())
   }
 }
@@ -102,20 +98,13 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for InitConfig<'a> {
     let (data, accounts) = value;
     log!("accounts len: {}, data len: {}", accounts.len(), data.len());
 
-    let [signer, config_pda, mint0, mint1, mint2, mint3, vault, prog_owner, prog_admin, system_program] =
-      accounts
-    else {
+    let [signer, config_pda, vault, prog_owner, system_program] = accounts else {
       return Err(ProgramError::NotEnoughAccountKeys);
     };
     check_signer(signer)?;
     check_sysprog(system_program)?;
     //writable(config_pda)?;
     not_initialized(config_pda)?;
-    log!("try check mints");
-    rent_exempt_mint22(mint0)?;
-    rent_exempt_mint22(mint1)?;
-    rent_exempt_mint22(mint2)?;
-    rent_exempt_mint22(mint3)?;
 
     let (vault_expected, vault_bump) = derive_pda1(prog_owner.address(), VAULT_SEED)?;
     if vault.address() != &vault_expected {
@@ -134,13 +123,6 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for InitConfig<'a> {
       signer,
       config_pda,
       prog_owner: prog_owner.address(),
-      prog_admin: prog_admin.address(),
-      mints: [
-        mint0.address(),
-        mint1.address(),
-        mint2.address(),
-        mint3.address(),
-      ],
       vault: vault.address(),
       system_program,
       fee,
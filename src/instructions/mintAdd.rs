@@ -0,0 +1,75 @@
+use core::convert::TryFrom;
+use pinocchio::{error::ProgramError, AccountView, ProgramResult};
+use pinocchio_log::log;
+
+use crate::{
+  check_mint_interface, check_pda, check_sysprog, init_mint_reg, instructions::check_signer,
+  not_initialized, parse_u64, rent_exempt_mint_interface, versioned_payload, Admin, Config, Ee,
+};
+
+/// Add Mint: admins with the MINTS permission register an accepted mint with its deposit cap. Queue it as an Action while a timelock delay is set
+pub struct MintAdd<'a> {
+  pub signer: &'a AccountView, //signer
+  pub config_pda: &'a AccountView,
+  pub admin_pda: &'a AccountView,
+  pub mint: &'a AccountView,
+  pub mint_reg: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub deposit_cap: u64,
+}
+impl<'a> MintAdd<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &27;
+
+  pub fn process(self) -> ProgramResult {
+    let MintAdd {
+      signer,
+      config_pda,
+      admin_pda: _,
+      mint,
+      mint_reg,
+      system_program: _,
+      deposit_cap,
+    } = self;
+    log!("MintAdd process()");
+    init_mint_reg(signer, config_pda.address(), mint, mint_reg, deposit_cap)
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for MintAdd<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("MintAdd try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+
+    let [signer, config_pda, admin_pda, mint, mint_reg, system_program] = accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(signer)?;
+    check_sysprog(system_program)?;
+    check_pda(config_pda)?;
+    not_initialized(mint_reg)?;
+    check_mint_interface(mint)?;
+    rent_exempt_mint_interface(mint)?;
+
+    let payload = versioned_payload(data, 8)?;
+    let deposit_cap = parse_u64(payload)?;
+
+    config_pda.check_borrow_mut()?;
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    config.check_no_timelock()?;
+    let permissions = Admin::signer_permissions(config_pda, config, signer, admin_pda)?;
+    if permissions & Admin::MINTS == 0 {
+      return Err(Ee::AdminPermission.into());
+    }
+    Ok(Self {
+      signer,
+      config_pda,
+      admin_pda,
+      mint,
+      mint_reg,
+      system_program,
+      deposit_cap,
+    })
+  }
+}
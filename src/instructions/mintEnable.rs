@@ -0,0 +1,68 @@
+use core::convert::TryFrom;
+use pinocchio::{error::ProgramError, AccountView, ProgramResult};
+use pinocchio_log::log;
+
+use crate::{
+  check_pda, data_len, get_time, instructions::check_signer, load_mint_reg, writable, Admin,
+  Config, Ee, MintReg,
+};
+
+/// Enable Mint: admins with the MINTS permission allow deposit, pay and redeem of a registered mint. Not allowed while a timelock delay is set
+pub struct MintEnable<'a> {
+  pub signer: &'a AccountView, //signer
+  pub config_pda: &'a AccountView,
+  pub admin_pda: &'a AccountView,
+  pub mint: &'a AccountView,
+  pub mint_reg: &'a AccountView,
+}
+impl<'a> MintEnable<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &29;
+
+  pub fn process(self) -> ProgramResult {
+    let MintEnable {
+      signer: _,
+      config_pda,
+      admin_pda: _,
+      mint,
+      mint_reg,
+    } = self;
+    log!("MintEnable process()");
+    mint_reg.check_borrow_mut()?;
+    let reg: &mut MintReg = load_mint_reg(config_pda.address(), mint, mint_reg)?;
+    reg.set_is_enabled(true);
+    reg.set_updated_at(get_time()?);
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for MintEnable<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("MintEnable try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+    data_len(data, 0)?;
+
+    let [signer, config_pda, admin_pda, mint, mint_reg] = accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(signer)?;
+    writable(mint_reg)?;
+    check_pda(config_pda)?;
+
+    config_pda.check_borrow_mut()?;
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    let permissions = Admin::signer_permissions(config_pda, config, signer, admin_pda)?;
+    if permissions & Admin::MINTS == 0 {
+      return Err(Ee::AdminPermission.into());
+    }
+    config.check_no_timelock()?;
+    Ok(Self {
+      signer,
+      config_pda,
+      admin_pda,
+      mint,
+      mint_reg,
+    })
+  }
+}
@@ -0,0 +1,71 @@
+use core::convert::TryFrom;
+use pinocchio::{error::ProgramError, AccountView, ProgramResult};
+use pinocchio_log::log;
+
+use crate::{
+  check_pda, close_pda, data_len, instructions::check_signer, load_mint_reg, writable, Admin,
+  Config, Ee, MintReg,
+};
+
+/// Remove Mint: admins with the MINTS permission close the Mint Registry PDA of a mint with no deposits. Queue it as an Action while a timelock delay is set
+pub struct MintRemove<'a> {
+  pub signer: &'a AccountView, //signer
+  pub config_pda: &'a AccountView,
+  pub admin_pda: &'a AccountView,
+  pub mint: &'a AccountView,
+  pub mint_reg: &'a AccountView,
+}
+impl<'a> MintRemove<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &28;
+
+  pub fn process(self) -> ProgramResult {
+    let MintRemove {
+      signer,
+      config_pda: _,
+      admin_pda: _,
+      mint: _,
+      mint_reg,
+    } = self;
+    log!("MintRemove process()");
+    close_pda(mint_reg, signer)
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for MintRemove<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("MintRemove try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+    data_len(data, 0)?;
+
+    let [signer, config_pda, admin_pda, mint, mint_reg] = accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(signer)?;
+    writable(signer)?;
+    writable(mint_reg)?;
+    check_pda(config_pda)?;
+
+    config_pda.check_borrow_mut()?;
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    config.check_no_timelock()?;
+    let permissions = Admin::signer_permissions(config_pda, config, signer, admin_pda)?;
+    if permissions & Admin::MINTS == 0 {
+      return Err(Ee::AdminPermission.into());
+    }
+
+    mint_reg.check_borrow_mut()?;
+    let reg: &mut MintReg = load_mint_reg(config_pda.address(), mint, mint_reg)?;
+    if reg.deposited() > 0 {
+      return Err(Ee::MintRegHasDeposits.into());
+    }
+    Ok(Self {
+      signer,
+      config_pda,
+      admin_pda,
+      mint,
+      mint_reg,
+    })
+  }
+}
@@ -1,14 +1,38 @@
 //---------------== Module Declaration
 //declare your new function mod here to be included into this project, then export it via "pub use"
 #[allow(non_snake_case)]
+pub mod actionCancel;
+#[allow(non_snake_case)]
+pub mod actionEnqueue;
+#[allow(non_snake_case)]
+pub mod actionExecute;
+#[allow(non_snake_case)]
+pub mod adminGrant;
+#[allow(non_snake_case)]
+pub mod adminRevoke;
+#[allow(non_snake_case)]
 pub mod closeConfig;
 #[allow(non_snake_case)]
-pub mod configResize;
+pub mod configMigrate;
+#[allow(non_snake_case)]
+pub mod configReconcile;
+#[allow(non_snake_case)]
+pub mod configSetAuthorized;
+#[allow(non_snake_case)]
+pub mod configSetExtPolicy;
+#[allow(non_snake_case)]
+pub mod configSetFee;
+#[allow(non_snake_case)]
+pub mod configSetLabel;
+#[allow(non_snake_case)]
+pub mod configSetStatus;
 #[allow(non_snake_case)]
 pub mod depositSol;
 #[allow(non_snake_case)]
 pub mod escrowTokCancel;
 #[allow(non_snake_case)]
+pub mod escrowTokExpire;
+#[allow(non_snake_case)]
 pub mod escrowTokMake;
 #[allow(non_snake_case)]
 pub mod escrowTokTake;
@@ -17,12 +41,48 @@ pub mod escrowTokWithdraw;
 #[allow(non_snake_case)]
 pub mod initConfig;
 #[allow(non_snake_case)]
+pub mod mintAdd;
+#[allow(non_snake_case)]
+pub mod mintDisable;
+#[allow(non_snake_case)]
+pub mod mintEnable;
+#[allow(non_snake_case)]
+pub mod mintRemove;
+#[allow(non_snake_case)]
+pub mod multisigApprove;
+#[allow(non_snake_case)]
+pub mod multisigCancel;
+#[allow(non_snake_case)]
+pub mod multisigExecute;
+#[allow(non_snake_case)]
+pub mod multisigInit;
+#[allow(non_snake_case)]
+pub mod multisigPropose;
+#[allow(non_snake_case)]
+pub mod ownerAccept;
+#[allow(non_snake_case)]
+pub mod ownerCancel;
+#[allow(non_snake_case)]
+pub mod ownerPropose;
+#[allow(non_snake_case)]
+pub mod poolDepositSol;
+#[allow(non_snake_case)]
+pub mod poolWithdrawSol;
+#[allow(non_snake_case)]
+pub mod tok22Extensions;
+#[allow(non_snake_case)]
+pub mod tok22HarvestFees;
+#[allow(non_snake_case)]
 pub mod tok22InitATA;
 #[allow(non_snake_case)]
 pub mod tok22InitMint;
 #[allow(non_snake_case)]
 pub mod tok22MintToken;
 #[allow(non_snake_case)]
+pub mod tok22UpdateMetadata;
+#[allow(non_snake_case)]
+pub mod tok22UpdateRate;
+#[allow(non_snake_case)]
 pub mod tokLgcDeposit;
 #[allow(non_snake_case)]
 pub mod tokLgcInitATA;
@@ -35,33 +95,63 @@ pub mod tokLgcPay;
 #[allow(non_snake_case)]
 pub mod tokLgcRedeem;
 #[allow(non_snake_case)]
-pub mod tokLgcWithdraw;
+pub mod tokLgcVoucher;
 #[allow(non_snake_case)]
-pub mod updateConfig;
+pub mod tokLgcWithdraw;
 pub mod utils;
 #[allow(non_snake_case)]
 pub mod withdrawSol;
 
 //file names start with a lower case + Camel cases, but struct names start with Upper case + Camel cases!
+pub use actionCancel::*;
+pub use actionEnqueue::*;
+pub use actionExecute::*;
+pub use adminGrant::*;
+pub use adminRevoke::*;
 pub use closeConfig::*;
-pub use configResize::*;
+pub use configMigrate::*;
+pub use configReconcile::*;
+pub use configSetAuthorized::*;
+pub use configSetExtPolicy::*;
+pub use configSetFee::*;
+pub use configSetLabel::*;
+pub use configSetStatus::*;
 pub use depositSol::*;
 pub use escrowTokCancel::*;
+pub use escrowTokExpire::*;
 pub use escrowTokMake::*;
 pub use escrowTokTake::*;
 pub use escrowTokWithdraw::*;
 pub use initConfig::*;
+pub use mintAdd::*;
+pub use mintDisable::*;
+pub use mintEnable::*;
+pub use mintRemove::*;
+pub use multisigApprove::*;
+pub use multisigCancel::*;
+pub use multisigExecute::*;
+pub use multisigInit::*;
+pub use multisigPropose::*;
+pub use ownerAccept::*;
+pub use ownerCancel::*;
+pub use ownerPropose::*;
+pub use poolDepositSol::*;
+pub use poolWithdrawSol::*;
+pub use tok22Extensions::*;
+pub use tok22HarvestFees::*;
 pub use tok22InitATA::*;
 pub use tok22InitMint::*;
 pub use tok22MintToken::*;
+pub use tok22UpdateMetadata::*;
+pub use tok22UpdateRate::*;
 pub use tokLgcDeposit::*;
 pub use tokLgcInitATA::*;
 pub use tokLgcInitMint::*;
 pub use tokLgcMintToken::*;
 pub use tokLgcPay::*;
 pub use tokLgcRedeem::*;
+pub use tokLgcVoucher::*;
 pub use tokLgcWithdraw::*;
-pub use updateConfig::*;
 pub use utils::*;
 pub use withdrawSol::*;
 
@@ -77,19 +167,36 @@ use shank::ShankInstruction;
 pub enum ProgramIx {
   /// 0 Deposit lamports into the vault.
   #[account(0, signer, writable, name = "user", desc = "User")]
-  #[account(1, writable, name = "vault", desc = "VaultPDA")]
-  #[account(2, name = "system_program", desc = "System Program")]
+  #[account(
+    1,
+    writable,
+    name = "vault",
+    desc = "SOL Vault PDA of the config and user"
+  )]
+  #[account(2, writable, name = "config_pda", desc = "Config PDA")]
+  #[account(3, name = "system_program", desc = "System Program")]
   Deposit { amount: u64 },
 
   /// 1 Withdraw lamports from the vault
   #[account(0, signer, writable, name = "user", desc = "User")]
-  #[account(1, writable, name = "vault", desc = "Vault PDA")]
+  #[account(
+    1,
+    writable,
+    name = "vault",
+    desc = "SOL Vault PDA of the config and user"
+  )]
+  #[account(2, writable, name = "config_pda", desc = "Config PDA")]
   Withdraw { amount: u64 },
 
   /// 2 TokLgc Init Mint
   #[account(0, signer, writable, name = "payer", desc = "Payer")]
   #[account(1, signer, writable, name = "mint", desc = "Mint")]
-  #[account(2, name = "mint_authority", desc = "Mint Authority")]
+  #[account(
+    2,
+    signer,
+    name = "mint_authority",
+    desc = "Mint Authority and Metadata Update Authority"
+  )]
   #[account(3, name = "token_program", desc = "Token Program")]
   #[account(4, name = "freeze_authority_opt", desc = "Freeze Authority")]
   #[account(5, name = "system_program", desc = "System Program")]
@@ -122,9 +229,15 @@ pub enum ProgramIx {
   #[account(3, name = "to_wallet", desc = "To Wallet")]
   #[account(4, name = "mint", desc = "Mint")]
   #[account(5, writable, name = "config_pda", desc = "config_pda")]
-  #[account(6, name = "token_program", desc = "Token Program")]
-  #[account(7, name = "system_program", desc = "System Program")]
-  #[account(8, name = "atoken_program", desc = "Associated Token Program")]
+  #[account(6, writable, name = "mint_reg", desc = "Mint Registry PDA")]
+  #[account(7, writable, name = "user_pda", desc = "User PDA")]
+  #[account(
+    8,
+    name = "token_program",
+    desc = "Token Program: TokenLgc or Token2022"
+  )]
+  #[account(9, name = "system_program", desc = "System Program")]
+  #[account(10, name = "atoken_program", desc = "Associated Token Program")]
   TokLgcDeposit { decimals: u8, amount: u64 },
 
   /// 6 TokLgc Withdraw Token
@@ -133,9 +246,16 @@ pub enum ProgramIx {
   #[account(2, writable, name = "to", desc = "To ATA")]
   #[account(3, name = "from_wallet", desc = "From Wallet")]
   #[account(4, name = "mint", desc = "Mint")]
-  #[account(5, name = "token_program", desc = "Token Program")]
-  #[account(6, name = "system_program", desc = "System Program")]
-  #[account(7, name = "atoken_program", desc = "Associated Token Program")]
+  #[account(5, writable, name = "config_pda", desc = "Config PDA")]
+  #[account(6, writable, name = "mint_reg", desc = "Mint Registry PDA")]
+  #[account(7, writable, name = "user_pda", desc = "User PDA")]
+  #[account(
+    8,
+    name = "token_program",
+    desc = "Token Program: TokenLgc or Token2022"
+  )]
+  #[account(9, name = "system_program", desc = "System Program")]
+  #[account(10, name = "atoken_program", desc = "Associated Token Program")]
   TokLgcWithdraw { decimals: u8, amount: u64 },
 
   /// 7 TokLgc User Pays Tokens to VaultPDA
@@ -145,9 +265,15 @@ pub enum ProgramIx {
   #[account(3, name = "vault", desc = "Vault as To Wallet")]
   #[account(4, name = "mint", desc = "Mint")]
   #[account(5, writable, name = "config_pda", desc = "config_pda")]
-  #[account(6, name = "token_program", desc = "Token Program")]
-  #[account(7, name = "system_program", desc = "System Program")]
-  #[account(8, name = "atoken_program", desc = "Associated Token Program")]
+  #[account(6, name = "mint_reg", desc = "Mint Registry PDA")]
+  #[account(7, writable, name = "user_pda", desc = "User PDA")]
+  #[account(
+    8,
+    name = "token_program",
+    desc = "Token Program: TokenLgc or Token2022"
+  )]
+  #[account(9, name = "system_program", desc = "System Program")]
+  #[account(10, name = "atoken_program", desc = "Associated Token Program")]
   TokLgcPay { decimals: u8, amount: u64 },
 
   /// 8 TokLgc Redeem Tokens
@@ -155,21 +281,39 @@ pub enum ProgramIx {
   #[account(1, writable, name = "from", desc = "From ATA")]
   #[account(2, writable, name = "to", desc = "To ATA")]
   #[account(3, name = "vault", desc = "Vault as From PDA")]
-  #[account(4, name = "config_pda", desc = "Config PDA")]
+  #[account(4, writable, name = "config_pda", desc = "Config PDA")]
   #[account(5, name = "mint", desc = "Mint")]
-  #[account(6, name = "token_program", desc = "Token Program")]
-  #[account(7, name = "system_program", desc = "System Program")]
-  #[account(8, name = "atoken_program", desc = "Associated Token Program")]
+  #[account(6, name = "mint_reg", desc = "Mint Registry PDA")]
+  #[account(7, writable, name = "user_pda", desc = "User PDA")]
+  #[account(
+    8,
+    name = "token_program",
+    desc = "Token Program: TokenLgc or Token2022"
+  )]
+  #[account(9, name = "system_program", desc = "System Program")]
+  #[account(10, name = "atoken_program", desc = "Associated Token Program")]
   TokLgcRedeem { decimals: u8, amount: u64 },
 
   //---------== Token2022
-  /// 9 Token2022 Init Mint
+  /// 9 Token2022 Init Mint. Optional trailing data: extension flags u8, then for flag 1 (TransferFeeConfig): basis_points u16, maximum_fee u64, withdraw_withheld_authority [u8; 32]. Flag 2 (NonTransferable) takes no arguments. Flag 4 (InterestBearingConfig): rate i16 in basis points, with the config PDA as an extra account and rate authority, and the mint authority needing the FEE permission on it
   #[account(0, signer, writable, name = "payer", desc = "Payer")]
   #[account(1, signer, writable, name = "mint", desc = "Mint")]
-  #[account(2, name = "mint_authority", desc = "Mint Authority")]
+  #[account(2, signer, name = "mint_authority", desc = "Mint Authority")]
   #[account(3, name = "token_program", desc = "Token Program")]
   #[account(4, name = "freeze_authority_opt", desc = "Freeze Authority")]
   #[account(5, name = "system_program", desc = "System Program")]
+  #[account(
+    6,
+    optional,
+    name = "config_pda",
+    desc = "Config PDA as the interest rate authority"
+  )]
+  #[account(
+    7,
+    optional,
+    name = "admin_pda",
+    desc = "Admin PDA of the mint authority, or the config PDA for the prog_owner"
+  )]
   Token2022InitMint {
     decimals: u8,
     token_name: [u8; 10],
@@ -177,7 +321,7 @@ pub enum ProgramIx {
     token_uri: [u8; 32],
   },
 
-  /// 10 Token2022 Init ATA(Associated Token Acct)
+  /// 10 Token2022 Init ATA(Associated Token Acct). Optional data: immutable_owner bool to require the ImmutableOwner extension
   #[account(0, signer, writable, name = "payer", desc = "Payer")]
   #[account(1, name = "to_wallet", desc = "To Wallet")]
   #[account(2, name = "mint", desc = "Mint")]
@@ -201,34 +345,26 @@ pub enum ProgramIx {
   /// 12 Init Config PDA
   #[account(0, signer, writable, name = "signer", desc = "Signer")]
   #[account(1, writable, name = "config_pda", desc = "Config PDA")]
-  #[account(2, name = "mint0", desc = "Mint 0")]
-  #[account(3, name = "mint1", desc = "Mint 1")]
-  #[account(4, name = "mint2", desc = "Mint 2")]
-  #[account(5, name = "mint3", desc = "Mint 3")]
-  #[account(6, name = "vault", desc = "VaultO")]
-  #[account(7, name = "prog_owner", desc = "Program Owner")]
-  #[account(8, name = "prog_admin", desc = "Program Admin")]
-  #[account(9, name = "system_program", desc = "System Program")]
+  #[account(2, name = "vault", desc = "VaultO")]
+  #[account(3, name = "prog_owner", desc = "Program Owner")]
+  #[account(4, name = "system_program", desc = "System Program")]
   InitConfig { fee: u64, is_authorized: bool },
 
-  /// 13 Update Config PDA
-  #[account(0, signer, writable, name = "authority", desc = "Authority")]
+  /// 13 Set Config Fee
+  #[account(0, signer, name = "signer", desc = "Program Owner or Admin")]
   #[account(1, writable, name = "config_pda", desc = "Config PDA")]
-  #[account(2, name = "account1", desc = "Account1")]
-  #[account(3, name = "account2", desc = "Account2")]
-  UpdateConfig {
-    bools: [u8; 4],
-    u8s: [u8; 4],
-    u32s: [u32; 4],
-    u64s: [u64; 4],
-    str_u8: [u8; 32],
+  #[account(2, name = "admin_pda", desc = "Admin PDA")]
+  ConfigSetFee {
+    version: u8,
+    fee: u64,
+    fee_on_x: bool,
   },
 
   /// 14 Close Config PDA
-  #[account(0, signer, writable, name = "authority", desc = "Authority")]
-  #[account(1, writable, name = "config_pda", desc = "PDA")]
-  #[account(2, name = "dest", desc = "Destination")]
-  CloseConfigPda {},
+  #[account(0, signer, name = "prog_owner", desc = "Program Owner")]
+  #[account(1, writable, name = "config_pda", desc = "Config PDA")]
+  #[account(2, writable, name = "dest", desc = "System-owned Destination")]
+  CloseConfigPda { force: bool },
 
   //---------------== Escrow PDA
   /// 15 Escrow Token Make Offer
@@ -239,7 +375,7 @@ pub enum ProgramIx {
   #[account(4, name = "mint_y", desc = "Mint Y")]
   #[account(5, writable, name = "escrow_pda", desc = "Escrow PDA")]
   #[account(6, writable, name = "config_pda", desc = "Config PDA")]
-  #[account(7, name = "token_program", desc = "Token Program")]
+  #[account(7, name = "token_program", desc = "Token Program of Mint X")]
   #[account(8, name = "system_program", desc = "System Program")]
   #[account(9, name = "atoken_program", desc = "Associated Token Program")]
   EscrowTokMake {
@@ -248,8 +384,10 @@ pub enum ProgramIx {
     decimal_y: u8,
     amount_y: u64,
     id: u64,
+    expires_at: u32,
+    taker: [u8; 32],
   },
-  /// 16 Escrow Token Take Offer
+  /// 16 Escrow Token Take Offer, fully or partially
   #[account(0, signer, writable, name = "taker", desc = "Taker")]
   #[account(1, writable, name = "taker_ata_x", desc = "Taker ATA X")]
   #[account(2, writable, name = "taker_ata_y", desc = "Taker ATA Y")]
@@ -259,9 +397,14 @@ pub enum ProgramIx {
   #[account(6, name = "mint_y", desc = "Mint Y")]
   #[account(7, writable, name = "escrow_pda", desc = "Escrow PDA")]
   #[account(8, writable, name = "config_pda", desc = "Config PDA")]
-  #[account(9, name = "token_program", desc = "Token Program")]
-  #[account(10, name = "system_program", desc = "System Program")]
-  #[account(11, name = "atoken_program", desc = "Associated Token Program")]
+  #[account(9, writable, name = "maker", desc = "Maker")]
+  #[account(10, writable, name = "maker_ata_y", desc = "Maker ATA Y")]
+  #[account(11, name = "vault", desc = "Config Vault")]
+  #[account(12, writable, name = "vault_ata", desc = "Vault ATA of the fee token")]
+  #[account(13, name = "token_program_x", desc = "Token Program of Mint X")]
+  #[account(14, name = "token_program_y", desc = "Token Program of Mint Y")]
+  #[account(15, name = "system_program", desc = "System Program")]
+  #[account(16, name = "atoken_program", desc = "Associated Token Program")]
   EscrowTokTake {
     decimal_x: u8,
     amount_x: u64,
@@ -279,9 +422,10 @@ pub enum ProgramIx {
   #[account(6, name = "mint_y", desc = "Mint Y")]
   #[account(7, writable, name = "escrow_pda", desc = "Escrow PDA")]
   #[account(8, writable, name = "config_pda", desc = "Config PDA")]
-  #[account(9, name = "token_program", desc = "Token Program")]
-  #[account(10, name = "system_program", desc = "System Program")]
-  #[account(11, name = "atoken_program", desc = "Associated Token Program")]
+  #[account(9, name = "token_program_x", desc = "Token Program of Mint X")]
+  #[account(10, name = "token_program_y", desc = "Token Program of Mint Y")]
+  #[account(11, name = "system_program", desc = "System Program")]
+  #[account(12, name = "atoken_program", desc = "Associated Token Program")]
   EscrowTokWithdraw {},
 
   /// 18 Escrow Token Cancel Offer
@@ -294,18 +438,282 @@ pub enum ProgramIx {
   #[account(6, name = "mint_y", desc = "Mint Y")]
   #[account(7, writable, name = "escrow_pda", desc = "Escrow PDA")]
   #[account(8, writable, name = "config_pda", desc = "Config PDA")]
-  #[account(9, name = "token_program", desc = "Token Program")]
-  #[account(10, name = "system_program", desc = "System Program")]
-  #[account(11, name = "atoken_program", desc = "Associated Token Program")]
+  #[account(9, name = "token_program_x", desc = "Token Program of Mint X")]
+  #[account(10, name = "token_program_y", desc = "Token Program of Mint Y")]
+  #[account(11, name = "system_program", desc = "System Program")]
+  #[account(12, name = "atoken_program", desc = "Associated Token Program")]
   EscrowTokCancel {},
 
-  /// 19 Resize Config PDA
-  #[account(0, signer, writable, name = "authority", desc = "Authority")]
-  #[account(1, writable, name = "config_pda", desc = "PDA")]
-  #[account(2, name = "first_prog_owner", desc = "First Program Owner")]
-  #[account(3, name = "system_program", desc = "System Program")]
-  ConfigResize { new_size: u64 },
+  /// 19 Migrate Config PDA to the current layout
+  #[account(0, signer, writable, name = "prog_owner", desc = "Program Owner")]
+  #[account(1, writable, name = "config_pda", desc = "Config PDA")]
+  #[account(2, name = "system_program", desc = "System Program")]
+  #[account(
+    3,
+    optional,
+    name = "seed_owner",
+    desc = "Seed Owner that derives the config, if not the prog_owner"
+  )]
+  ConfigMigrate {},
+
+  /// 20 Escrow Token Expire: anyone can close an expired escrow
+  #[account(0, signer, writable, name = "cranker", desc = "Anyone")]
+  #[account(1, writable, name = "maker", desc = "Maker")]
+  #[account(2, writable, name = "maker_ata_x", desc = "Maker ATA X")]
+  #[account(3, writable, name = "maker_ata_y", desc = "Maker ATA Y")]
+  #[account(4, writable, name = "escrow_ata_x", desc = "Escrow ATA X")]
+  #[account(5, writable, name = "escrow_ata_y", desc = "Escrow ATA Y")]
+  #[account(6, name = "mint_x", desc = "Mint X")]
+  #[account(7, name = "mint_y", desc = "Mint Y")]
+  #[account(8, writable, name = "escrow_pda", desc = "Escrow PDA")]
+  #[account(9, writable, name = "config_pda", desc = "Config PDA")]
+  #[account(10, name = "token_program_x", desc = "Token Program of Mint X")]
+  #[account(11, name = "token_program_y", desc = "Token Program of Mint Y")]
+  #[account(12, name = "system_program", desc = "System Program")]
+  #[account(13, name = "atoken_program", desc = "Associated Token Program")]
+  EscrowTokExpire {},
   //---------------== Admin PDA
-  //---------------== User PDA
+  /// 21 Grant Admin Permissions
+  #[account(0, signer, writable, name = "prog_owner", desc = "Program Owner")]
+  #[account(1, name = "config_pda", desc = "Config PDA")]
+  #[account(2, writable, name = "admin_pda", desc = "Admin PDA")]
+  #[account(3, name = "admin", desc = "Admin")]
+  #[account(4, name = "system_program", desc = "System Program")]
+  AdminGrant { version: u8, permissions: u32 },
+
+  /// 22 Revoke Admin Permissions
+  #[account(0, signer, writable, name = "prog_owner", desc = "Program Owner")]
+  #[account(1, name = "config_pda", desc = "Config PDA")]
+  #[account(2, writable, name = "admin_pda", desc = "Admin PDA")]
+  AdminRevoke { version: u8, permissions: u32 },
+
+  /// 23 TokLgc Admin Grants a Redeem Voucher
+  #[account(0, signer, writable, name = "signer", desc = "Program Owner or Admin")]
+  #[account(1, writable, name = "config_pda", desc = "Config PDA")]
+  #[account(2, name = "admin_pda", desc = "Admin PDA")]
+  #[account(3, name = "user", desc = "User")]
+  #[account(4, name = "mint", desc = "Mint")]
+  #[account(5, name = "mint_reg", desc = "Mint Registry PDA")]
+  #[account(6, writable, name = "user_pda", desc = "User PDA")]
+  #[account(7, name = "system_program", desc = "System Program")]
+  TokLgcVoucher { version: u8, amount: u64 },
+
+  /// 24 Propose a New Program Owner
+  #[account(0, signer, name = "prog_owner", desc = "Program Owner")]
+  #[account(1, writable, name = "config_pda", desc = "Config PDA")]
+  #[account(2, name = "new_owner", desc = "New Program Owner")]
+  OwnerPropose {},
+
+  /// 25 Accept the Program Ownership
+  #[account(0, signer, name = "pending_owner", desc = "Pending Program Owner")]
+  #[account(1, writable, name = "config_pda", desc = "Config PDA")]
+  OwnerAccept {},
+
+  /// 26 Cancel the Program Owner Proposal
+  #[account(0, signer, name = "prog_owner", desc = "Program Owner")]
+  #[account(1, writable, name = "config_pda", desc = "Config PDA")]
+  OwnerCancel {},
+
+  //---------------== Mint Registry PDA
+  /// 27 Add an Accepted Mint
+  #[account(0, signer, writable, name = "signer", desc = "Program Owner or Admin")]
+  #[account(1, name = "config_pda", desc = "Config PDA")]
+  #[account(2, name = "admin_pda", desc = "Admin PDA")]
+  #[account(3, name = "mint", desc = "Mint")]
+  #[account(4, writable, name = "mint_reg", desc = "Mint Registry PDA")]
+  #[account(5, name = "system_program", desc = "System Program")]
+  MintAdd { version: u8, deposit_cap: u64 },
+
+  /// 28 Remove an Accepted Mint
+  #[account(0, signer, writable, name = "signer", desc = "Program Owner or Admin")]
+  #[account(1, name = "config_pda", desc = "Config PDA")]
+  #[account(2, name = "admin_pda", desc = "Admin PDA")]
+  #[account(3, name = "mint", desc = "Mint")]
+  #[account(4, writable, name = "mint_reg", desc = "Mint Registry PDA")]
+  MintRemove {},
+
+  /// 29 Enable an Accepted Mint
+  #[account(0, signer, name = "signer", desc = "Program Owner or Admin")]
+  #[account(1, name = "config_pda", desc = "Config PDA")]
+  #[account(2, name = "admin_pda", desc = "Admin PDA")]
+  #[account(3, name = "mint", desc = "Mint")]
+  #[account(4, writable, name = "mint_reg", desc = "Mint Registry PDA")]
+  MintEnable {},
+
+  /// 30 Disable an Accepted Mint
+  #[account(0, signer, name = "signer", desc = "Program Owner or Admin")]
+  #[account(1, name = "config_pda", desc = "Config PDA")]
+  #[account(2, name = "admin_pda", desc = "Admin PDA")]
+  #[account(3, name = "mint", desc = "Mint")]
+  #[account(4, writable, name = "mint_reg", desc = "Mint Registry PDA")]
+  MintDisable {},
+
+  /// 31 Reconcile Config Balances against SOL Vaults and Config Vault ATAs
+  #[account(0, signer, name = "signer", desc = "Program Owner or Admin")]
+  #[account(1, name = "config_pda", desc = "Config PDA")]
+  #[account(2, name = "admin_pda", desc = "Admin PDA")]
+  ConfigReconcile {},
+
+  /// 32 Set Config Status
+  #[account(0, signer, name = "signer", desc = "Program Owner or Admin")]
+  #[account(1, writable, name = "config_pda", desc = "Config PDA")]
+  #[account(2, name = "admin_pda", desc = "Admin PDA")]
+  ConfigSetStatus { version: u8, status: u8 },
+
+  /// 33 Retired Set Config Admin, kept so later discriminators do not move. Admins are Admin PDAs made by AdminGrant
+  RetiredConfigSetAdmin {},
+
+  /// 34 Set Config Label
+  #[account(0, signer, name = "prog_owner", desc = "Program Owner")]
+  #[account(1, writable, name = "config_pda", desc = "Config PDA")]
+  ConfigSetLabel { version: u8, str_u8: [u8; 32] },
+
+  /// 35 Set Config Authorization Flag
+  #[account(0, signer, name = "prog_owner", desc = "Program Owner")]
+  #[account(1, writable, name = "config_pda", desc = "Config PDA")]
+  ConfigSetAuthorized { version: u8, is_authorized: bool },
   //---------------== Action PDA
+  /// 36 Enqueue a Timelocked Config Change
+  #[account(
+    0,
+    signer,
+    writable,
+    name = "proposer",
+    desc = "Program Owner or Admin"
+  )]
+  #[account(1, name = "config_pda", desc = "Config PDA")]
+  #[account(2, name = "admin_pda", desc = "Admin PDA")]
+  #[account(3, writable, name = "action_pda", desc = "Action PDA")]
+  #[account(4, name = "system_program", desc = "System Program")]
+  ActionEnqueue {
+    version: u8,
+    id: u64,
+    kind: u8,
+    payload_hash: [u8; 32],
+  },
+
+  /// 37 Execute a Queued Config Change after its eta. Data is the payload: kind + arguments
+  #[account(0, signer, writable, name = "executor", desc = "Anyone")]
+  #[account(1, writable, name = "config_pda", desc = "Config PDA")]
+  #[account(2, writable, name = "action_pda", desc = "Action PDA")]
+  #[account(3, writable, name = "proposer", desc = "Proposer to get the rent back")]
+  #[account(
+    4,
+    name = "admin_pda",
+    desc = "Admin PDA of the proposer, or any account for owner-only kinds"
+  )]
+  ActionExecute { kind: u8 },
+
+  /// 38 Cancel a Queued Config Change
+  #[account(0, signer, name = "prog_owner", desc = "Program Owner")]
+  #[account(1, name = "config_pda", desc = "Config PDA")]
+  #[account(2, writable, name = "action_pda", desc = "Action PDA")]
+  #[account(3, writable, name = "proposer", desc = "Proposer to get the rent back")]
+  ActionCancel {},
+  //---------------== Multisig PDA
+  /// 39 Init an M-of-N Multisig. Data: id, threshold, then the signer addresses
+  #[account(0, signer, writable, name = "creator", desc = "Creator")]
+  #[account(1, writable, name = "multisig_pda", desc = "Multisig PDA")]
+  #[account(2, name = "system_program", desc = "System Program")]
+  MultisigInit { id: u64, threshold: u8 },
+
+  /// 40 Propose a Config Change to a Multisig
+  #[account(0, signer, writable, name = "proposer", desc = "Multisig Signer")]
+  #[account(1, name = "config_pda", desc = "Config PDA")]
+  #[account(2, name = "multisig_pda", desc = "Multisig PDA")]
+  #[account(3, writable, name = "tx_pda", desc = "MultisigTx PDA")]
+  #[account(4, name = "system_program", desc = "System Program")]
+  MultisigPropose {
+    version: u8,
+    id: u64,
+    kind: u8,
+    payload_hash: [u8; 32],
+  },
+
+  /// 41 Approve a Multisig Tx
+  #[account(0, signer, name = "signer", desc = "Multisig Signer")]
+  #[account(1, name = "config_pda", desc = "Config PDA")]
+  #[account(2, name = "multisig_pda", desc = "Multisig PDA")]
+  #[account(3, writable, name = "tx_pda", desc = "MultisigTx PDA")]
+  MultisigApprove {},
+
+  /// 42 Execute an Approved Multisig Tx after its eta. Data is the payload: kind + arguments. Remaining accounts follow the kind, e.g. [dest] for CLOSE_CONFIG and [system_program] for MIGRATE_CONFIG
+  #[account(0, signer, writable, name = "executor", desc = "Anyone")]
+  #[account(1, writable, name = "config_pda", desc = "Config PDA")]
+  #[account(2, name = "multisig_pda", desc = "Multisig PDA")]
+  #[account(3, name = "admin_pda", desc = "Admin PDA of the Multisig")]
+  #[account(4, writable, name = "tx_pda", desc = "MultisigTx PDA")]
+  #[account(5, writable, name = "proposer", desc = "Proposer to get the rent back")]
+  MultisigExecute { kind: u8 },
+
+  /// 43 Cancel a Multisig Tx
+  #[account(0, signer, writable, name = "proposer", desc = "Proposer")]
+  #[account(1, writable, name = "tx_pda", desc = "MultisigTx PDA")]
+  MultisigCancel {},
+  //---------------== Token2022 Extensions
+  /// 44 Token2022 Update Metadata. field: 0 name, 1 symbol, 2 uri, 3 key/value pair
+  #[account(
+    0,
+    signer,
+    writable,
+    name = "update_authority",
+    desc = "Metadata Update Authority"
+  )]
+  #[account(1, writable, name = "mint", desc = "Mint")]
+  #[account(2, name = "token_program", desc = "Token2022 Program")]
+  #[account(3, name = "system_program", desc = "System Program")]
+  Token2022UpdateMetadata {
+    field: u8,
+    key: [u8; 32],
+    value: [u8; 32],
+  },
+
+  /// 45 Token2022 Harvest withheld transfer fees into the config vault ATA. Remaining accounts: token accounts to harvest from
+  #[account(0, signer, writable, name = "payer", desc = "Payer")]
+  #[account(1, writable, name = "config_pda", desc = "Config PDA")]
+  #[account(2, writable, name = "mint", desc = "Mint")]
+  #[account(3, name = "vault", desc = "Config Vault")]
+  #[account(4, writable, name = "vault_ata", desc = "Vault ATA")]
+  #[account(5, name = "token_program", desc = "Token2022 Program")]
+  #[account(6, name = "system_program", desc = "System Program")]
+  #[account(7, name = "atoken_program", desc = "Associated Token Program")]
+  Token2022HarvestFees {},
+
+  /// 46 Set Config Token2022 mint extension policy: ext_mask bits are ExtensionType ids
+  #[account(0, signer, name = "signer", desc = "Program Owner or Admin")]
+  #[account(1, writable, name = "config_pda", desc = "Config PDA")]
+  #[account(2, name = "admin_pda", desc = "Admin PDA")]
+  ConfigSetExtPolicy {
+    version: u8,
+    ext_mask: u64,
+    allowlist: bool,
+  },
+
+  /// 47 Token2022 Update the interest rate of a mint whose rate authority is the config PDA
+  #[account(0, signer, name = "signer", desc = "Program Owner or Admin")]
+  #[account(1, writable, name = "config_pda", desc = "Config PDA")]
+  #[account(2, name = "admin_pda", desc = "Admin PDA")]
+  #[account(3, writable, name = "mint", desc = "Mint")]
+  #[account(4, name = "token_program", desc = "Token2022 Program")]
+  Token2022UpdateRate { version: u8, rate: i16 },
+
+  /// 48 Deposit lamports into the shared pool vault for share tokens. The pool vault and share mint are PDAs made on the first deposit
+  #[account(0, signer, writable, name = "user", desc = "User")]
+  #[account(1, writable, name = "config_pda", desc = "Config PDA")]
+  #[account(2, writable, name = "pool", desc = "Pool Vault PDA")]
+  #[account(3, writable, name = "share_mint", desc = "Share Mint PDA")]
+  #[account(4, writable, name = "user_ata", desc = "User Share ATA")]
+  #[account(5, name = "token_program", desc = "Token Program")]
+  #[account(6, name = "system_program", desc = "System Program")]
+  #[account(7, name = "atoken_program", desc = "Associated Token Program")]
+  PoolDepositSol { amount: u64, min_shares: u64 },
+
+  /// 49 Burn share tokens for lamports from the shared pool vault
+  #[account(0, signer, writable, name = "user", desc = "User")]
+  #[account(1, writable, name = "config_pda", desc = "Config PDA")]
+  #[account(2, writable, name = "pool", desc = "Pool Vault PDA")]
+  #[account(3, writable, name = "share_mint", desc = "Share Mint PDA")]
+  #[account(4, writable, name = "user_ata", desc = "User Share ATA")]
+  #[account(5, name = "token_program", desc = "Token Program")]
+  PoolWithdrawSol { shares: u64, min_lamports: u64 },
 } //update here and lib.rs for new functions
@@ -0,0 +1,77 @@
+use core::convert::TryFrom;
+use pinocchio::{error::ProgramError, AccountView, ProgramResult};
+use pinocchio_log::log;
+
+use crate::{
+  check_pda, data_len, get_time, instructions::check_signer, writable, Config, Ee, Multisig,
+  MultisigTx,
+};
+
+/// Approve Multisig Tx: a multisig signer approves a proposed config change. The approval meeting the threshold starts the config timelock delay
+pub struct MultisigApprove<'a> {
+  pub signer: &'a AccountView, //signer
+  pub config_pda: &'a AccountView,
+  pub multisig_pda: &'a AccountView,
+  pub tx_pda: &'a AccountView,
+  pub tx: &'a mut MultisigTx,
+  pub index: u8,
+  pub threshold: u8,
+  pub delay: u32,
+}
+impl<'a> MultisigApprove<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &41;
+
+  pub fn process(self) -> ProgramResult {
+    let MultisigApprove {
+      signer: _,
+      config_pda: _,
+      multisig_pda: _,
+      tx_pda: _,
+      tx,
+      index,
+      threshold,
+      delay,
+    } = self;
+    log!("MultisigApprove process()");
+    tx.approve(index, threshold, get_time()?, delay)?;
+    log!("approvals: {}", tx.approval_count());
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for MultisigApprove<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("MultisigApprove try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+    data_len(data, 0)?;
+
+    let [signer, config_pda, multisig_pda, tx_pda] = accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(signer)?;
+    writable(tx_pda)?;
+    check_pda(config_pda)?;
+
+    let multisig: &mut Multisig = Multisig::from_account_view(multisig_pda)?;
+    let index = multisig.signer_index(signer.address())?;
+    tx_pda.check_borrow_mut()?;
+    let tx: &mut MultisigTx = MultisigTx::from_account_view(tx_pda)?;
+    if tx.multisig().ne(multisig_pda.address()) {
+      return Err(Ee::MultisigPDA.into());
+    }
+    tx.check_config(config_pda)?;
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    Ok(Self {
+      signer,
+      config_pda,
+      multisig_pda,
+      tx_pda,
+      tx,
+      index,
+      threshold: multisig.threshold(),
+      delay: config.timelock_delay(),
+    })
+  }
+}
@@ -0,0 +1,43 @@
+use core::convert::TryFrom;
+use pinocchio::{error::ProgramError, AccountView, ProgramResult};
+use pinocchio_log::log;
+
+use crate::{close_pda, data_len, instructions::check_signer, writable, Ee, MultisigTx};
+
+/// Cancel Multisig Tx: the proposer drops a proposed config change and gets the rent back
+pub struct MultisigCancel<'a> {
+  pub proposer: &'a AccountView, //signer
+  pub tx_pda: &'a AccountView,
+}
+impl<'a> MultisigCancel<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &43;
+
+  pub fn process(self) -> ProgramResult {
+    let MultisigCancel { proposer, tx_pda } = self;
+    log!("MultisigCancel process()");
+    close_pda(tx_pda, proposer)
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for MultisigCancel<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("MultisigCancel try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+    data_len(data, 0)?;
+
+    let [proposer, tx_pda] = accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(proposer)?;
+    writable(proposer)?;
+    writable(tx_pda)?;
+
+    let tx: &mut MultisigTx = MultisigTx::from_account_view(tx_pda)?;
+    if tx.proposer().ne(proposer.address()) {
+      return Err(Ee::MultisigSigner.into());
+    }
+    Ok(Self { proposer, tx_pda })
+  }
+}
@@ -0,0 +1,178 @@
+use core::convert::TryFrom;
+use pinocchio::{error::ProgramError, AccountView, Address, ProgramResult};
+use pinocchio_log::log;
+
+use crate::{
+  apply_action, check_closable, check_pda, check_sysprog, close_pda, get_time,
+  instructions::check_signer, migrate_config, sha256, to32bytes, u8_to_bool, writable, Action,
+  Admin, Config, Ee, Multisig, MultisigTx,
+};
+
+/// Execute Multisig Tx: anyone applies an approved config change once its eta has passed, by giving the payload matching the proposed hash. The multisig acts as the prog_owner or as an admin with its Admin PDA. As the prog_owner it can also close or migrate the config: remaining is [dest] for CLOSE_CONFIG and [system_program] for MIGRATE_CONFIG
+pub struct MultisigExecute<'a> {
+  pub executor: &'a AccountView, //signer
+  pub config_pda: &'a AccountView,
+  pub multisig_pda: &'a AccountView,
+  pub admin_pda: &'a AccountView,
+  pub tx_pda: &'a AccountView,
+  pub proposer: &'a AccountView,
+  pub remaining: &'a [AccountView],
+  pub payload: &'a [u8],
+  pub version: u8, //config layout version, only read for MIGRATE_CONFIG
+}
+impl<'a> MultisigExecute<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &42;
+
+  pub fn process(self) -> ProgramResult {
+    let MultisigExecute {
+      executor,
+      config_pda,
+      multisig_pda,
+      admin_pda: _,
+      tx_pda,
+      proposer,
+      remaining,
+      payload,
+      version,
+    } = self;
+    log!("MultisigExecute process()");
+    match payload[0] {
+      Action::MIGRATE_CONFIG => {
+        let [system_program] = remaining else {
+          return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        check_sysprog(system_program)?;
+        let seed_owner = Address::new_from_array(*to32bytes(&payload[1..33])?);
+        migrate_config(executor, proposer, config_pda, version, &seed_owner)?;
+      }
+      Action::CLOSE_CONFIG => {
+        let [dest] = remaining else {
+          return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        if dest.address().as_ref().ne(&payload[2..34]) {
+          return Ee::DestNotWallet.e();
+        }
+        let config: &mut Config = Config::from_account_view(config_pda)?;
+        check_closable(config_pda, config, dest, u8_to_bool(payload[1])?)?;
+        log!("Close Config PDA");
+        close_pda(config_pda, dest)?;
+      }
+      kind => {
+        let config: &mut Config = Config::from_account_view(config_pda)?;
+        if kind == Action::ACCEPT_OWNER {
+          config.set_prog_owner(multisig_pda.address());
+          config.set_pending_owner(&Address::default());
+        } else {
+          apply_action(config_pda, config, payload, executor, proposer, remaining)?;
+        }
+        config.set_updated_at(get_time()?);
+      }
+    }
+
+    log!("Close MultisigTx PDA");
+    close_pda(tx_pda, proposer)
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for MultisigExecute<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("MultisigExecute try_from");
+    let (payload, accounts) = value;
+    log!(
+      "accounts len: {}, data len: {}",
+      accounts.len(),
+      payload.len()
+    );
+
+    let [executor, config_pda, multisig_pda, admin_pda, tx_pda, proposer, remaining @ ..] =
+      accounts
+    else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(executor)?;
+    writable(executor)?;
+    writable(config_pda)?;
+    writable(tx_pda)?;
+    writable(proposer)?;
+    check_pda(config_pda)?;
+    Multisig::check(multisig_pda)?;
+
+    tx_pda.check_borrow_mut()?;
+    let tx: &mut MultisigTx = MultisigTx::from_account_view(tx_pda)?;
+    if tx.multisig().ne(multisig_pda.address()) {
+      return Err(Ee::MultisigPDA.into());
+    }
+    if tx.proposer().ne(proposer.address()) {
+      return Err(Ee::MultisigPDA.into());
+    }
+    tx.check_config(config_pda)?;
+    let Some(kind) = payload.first() else {
+      return Err(Ee::InputDataLen.into());
+    };
+    if *kind != tx.kind() {
+      return Err(Ee::ActionKind.into());
+    }
+    if payload.len() != Action::payload_len(*kind)? {
+      return Err(Ee::InputDataLen.into());
+    }
+    if sha256(payload).ne(tx.payload_hash()) {
+      return Err(Ee::ActionPayloadHash.into());
+    }
+    tx.check_ready(get_time()?)?;
+
+    config_pda.check_borrow_mut()?;
+    let mut version = 0;
+    if *kind == Action::MIGRATE_CONFIG {
+      //a config of an older layout cannot be loaded, so only its owner is read
+      version = Config::check_migratable(config_pda, multisig_pda.address())?;
+    } else {
+      check_executable(*kind, config_pda, multisig_pda, admin_pda)?;
+    }
+    Ok(Self {
+      executor,
+      config_pda,
+      multisig_pda,
+      admin_pda,
+      tx_pda,
+      proposer,
+      remaining,
+      payload,
+      version,
+    })
+  }
+}
+
+/// check the multisig may execute this kind on a loadable config
+fn check_executable(
+  kind: u8,
+  config_pda: &AccountView,
+  multisig_pda: &AccountView,
+  admin_pda: &AccountView,
+) -> ProgramResult {
+  let config: &mut Config = Config::from_account_view(config_pda)?;
+  if kind == Action::ACCEPT_OWNER {
+    if config.pending_owner().ne(multisig_pda.address()) {
+      return Err(Ee::OnlyPendingOwner.into());
+    }
+  } else if kind == Action::CLOSE_CONFIG {
+    if config.prog_owner().ne(multisig_pda.address()) {
+      return Err(Ee::OnlyProgOwner.into());
+    }
+  } else {
+    match Action::permission(kind)? {
+      None => {
+        if config.prog_owner().ne(multisig_pda.address()) {
+          return Err(Ee::OnlyProgOwner.into());
+        }
+      }
+      Some(permission) => {
+        let permissions = Admin::signer_permissions(config_pda, config, multisig_pda, admin_pda)?;
+        if permissions & permission == 0 {
+          return Err(Ee::AdminPermission.into());
+        }
+      }
+    }
+  }
+  Ok(())
+}
@@ -0,0 +1,112 @@
+use core::convert::TryFrom;
+use pinocchio::{
+  cpi::{Seed, Signer},
+  error::ProgramError,
+  sysvars::rent::Rent,
+  AccountView, Address, ProgramResult,
+};
+use pinocchio_log::log;
+
+use crate::{
+  check_sysprog, instructions::check_signer, not_initialized, parse_u64, to32bytes, writable, Ee,
+  Multisig, PROG_ADDR,
+};
+
+/// Init Multisig: anyone creates an M-of-N signer set. It gets authority once the prog_owner hands ownership to it, or grants it an Admin PDA
+pub struct MultisigInit<'a> {
+  pub creator: &'a AccountView, //signer
+  pub multisig_pda: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub id: u64,
+  pub threshold: u8,
+  pub signers: &'a [u8],
+}
+impl<'a> MultisigInit<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &39;
+
+  pub fn process(self) -> ProgramResult {
+    let MultisigInit {
+      creator,
+      multisig_pda,
+      system_program: _,
+      id,
+      threshold,
+      signers,
+    } = self;
+    log!("MultisigInit process()");
+    let id_bytes = &id.to_le_bytes();
+    let seed = [Multisig::SEED, creator.address().as_array(), id_bytes];
+    let (expected_multisig, bump) = Address::find_program_address(&seed[..], &PROG_ADDR);
+    if expected_multisig.ne(multisig_pda.address()) {
+      return Ee::MultisigPDA.e();
+    }
+    let mut signer_set = [Address::default(); Multisig::MAX_SIGNERS];
+    for (i, signer) in signers.chunks_exact(32).enumerate() {
+      signer_set[i] = Address::from(*to32bytes(signer)?);
+    }
+    let signer_count = signers.len() / 32;
+
+    let rent = Rent::from_account_view(multisig_pda)?;
+    let lamports = Rent::try_minimum_balance(&rent, Multisig::LEN)?;
+    let seeds = [
+      Seed::from(Multisig::SEED),
+      Seed::from(creator.address().as_ref()),
+      Seed::from(id_bytes),
+      Seed::from(core::slice::from_ref(&bump)),
+    ];
+    let seed_signer = Signer::from(&seeds);
+
+    pinocchio_system::instructions::CreateAccount {
+      from: creator,
+      to: multisig_pda,
+      lamports,
+      space: Multisig::LEN as u64,
+      owner: &PROG_ADDR,
+    }
+    .invoke_signed(&[seed_signer])?;
+
+    let multisig: &mut Multisig = Multisig::init_account_view(multisig_pda)?;
+    multisig.set_signers(&signer_set[..signer_count], threshold)?;
+    multisig.set_creator(creator.address());
+    multisig.set_id(id);
+    multisig.set_bump(bump);
+    log!("{} of {} signers", threshold, signer_count);
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for MultisigInit<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("MultisigInit try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+    //id: 8, threshold: 1, then 32 bytes per signer
+    if data.len() < 9 + 32 || (data.len() - 9) % 32 != 0 {
+      return Err(Ee::InputDataLen.into());
+    }
+    let id = parse_u64(&data[0..8])?;
+    let threshold = data[8];
+    let signers = &data[9..];
+    if signers.len() / 32 > Multisig::MAX_SIGNERS {
+      return Err(Ee::MultisigSigner.into());
+    }
+
+    let [creator, multisig_pda, system_program] = accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(creator)?;
+    writable(creator)?;
+    writable(multisig_pda)?;
+    not_initialized(multisig_pda)?;
+    check_sysprog(system_program)?;
+    Ok(Self {
+      creator,
+      multisig_pda,
+      system_program,
+      id,
+      threshold,
+      signers,
+    })
+  }
+}
@@ -0,0 +1,129 @@
+use core::convert::TryFrom;
+use pinocchio::{
+  cpi::{Seed, Signer},
+  error::ProgramError,
+  sysvars::rent::Rent,
+  AccountView, Address, ProgramResult,
+};
+use pinocchio_log::log;
+
+use crate::{
+  check_pda, check_sysprog, get_time, instructions::check_signer, not_initialized, parse_u64,
+  to32bytes, versioned_payload, writable, Action, Config, Ee, Multisig, MultisigTx, PROG_ADDR,
+};
+
+/// Propose Multisig Tx: a multisig signer proposes a change to config_pda by its payload hash, which counts as their approval
+pub struct MultisigPropose<'a> {
+  pub proposer: &'a AccountView, //signer
+  pub config_pda: &'a AccountView,
+  pub multisig_pda: &'a AccountView,
+  pub tx_pda: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub id: u64,
+  pub kind: u8,
+  pub payload_hash: [u8; 32],
+  pub index: u8,
+  pub threshold: u8,
+  pub delay: u32,
+}
+impl<'a> MultisigPropose<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &40;
+
+  pub fn process(self) -> ProgramResult {
+    let MultisigPropose {
+      proposer,
+      config_pda,
+      multisig_pda,
+      tx_pda,
+      system_program: _,
+      id,
+      kind,
+      payload_hash,
+      index,
+      threshold,
+      delay,
+    } = self;
+    log!("MultisigPropose process()");
+    let id_bytes = &id.to_le_bytes();
+    let seed = [
+      MultisigTx::SEED,
+      multisig_pda.address().as_array(),
+      id_bytes,
+    ];
+    let (expected_tx, bump) = Address::find_program_address(&seed[..], &PROG_ADDR);
+    if expected_tx.ne(tx_pda.address()) {
+      return Ee::MultisigPDA.e();
+    }
+    let rent = Rent::from_account_view(tx_pda)?;
+    let lamports = Rent::try_minimum_balance(&rent, MultisigTx::LEN)?;
+    let seeds = [
+      Seed::from(MultisigTx::SEED),
+      Seed::from(multisig_pda.address().as_ref()),
+      Seed::from(id_bytes),
+      Seed::from(core::slice::from_ref(&bump)),
+    ];
+    let seed_signer = Signer::from(&seeds);
+
+    pinocchio_system::instructions::CreateAccount {
+      from: proposer,
+      to: tx_pda,
+      lamports,
+      space: MultisigTx::LEN as u64,
+      owner: &PROG_ADDR,
+    }
+    .invoke_signed(&[seed_signer])?;
+
+    let tx: &mut MultisigTx = MultisigTx::init_account_view(tx_pda)?;
+    tx.set_config(config_pda.address());
+    tx.set_multisig(multisig_pda.address());
+    tx.set_proposer(proposer.address());
+    tx.set_payload_hash(payload_hash);
+    tx.set_id(id);
+    tx.set_kind(kind);
+    tx.set_bump(bump);
+    tx.approve(index, threshold, get_time()?, delay)?;
+    log!("kind: {}, approvals: {}", kind, tx.approval_count());
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for MultisigPropose<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("MultisigPropose try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+    let payload = versioned_payload(data, 41)?;
+    let id = parse_u64(&payload[0..8])?;
+    let kind = payload[8];
+    let payload_hash = *to32bytes(&payload[9..41])?;
+    Action::payload_len(kind)?;
+
+    let [proposer, config_pda, multisig_pda, tx_pda, system_program] = accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(proposer)?;
+    writable(proposer)?;
+    writable(tx_pda)?;
+    not_initialized(tx_pda)?;
+    check_pda(config_pda)?;
+    check_sysprog(system_program)?;
+
+    let multisig: &mut Multisig = Multisig::from_account_view(multisig_pda)?;
+    let index = multisig.signer_index(proposer.address())?;
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    Ok(Self {
+      proposer,
+      config_pda,
+      multisig_pda,
+      tx_pda,
+      system_program,
+      id,
+      kind,
+      payload_hash,
+      index,
+      threshold: multisig.threshold(),
+      delay: config.timelock_delay(),
+    })
+  }
+}
@@ -0,0 +1,57 @@
+use core::convert::TryFrom;
+use pinocchio::{error::ProgramError, AccountView, Address, ProgramResult};
+use pinocchio_log::log;
+
+use crate::{check_pda, data_len, get_time, instructions::check_signer, writable, Config, Ee};
+
+/// Accept Program Owner: the pending owner accepts the ownership proposed by the prog_owner. Not allowed while a timelock delay is set
+pub struct OwnerAccept<'a> {
+  pub pending_owner: &'a AccountView, //signer
+  pub config_pda: &'a AccountView,
+  pub config: &'a mut Config,
+}
+impl<'a> OwnerAccept<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &25;
+
+  pub fn process(self) -> ProgramResult {
+    let OwnerAccept {
+      pending_owner,
+      config_pda: _,
+      config,
+    } = self;
+    log!("OwnerAccept process()");
+    config.set_prog_owner(pending_owner.address());
+    config.set_pending_owner(&Address::default());
+    config.set_updated_at(get_time()?);
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for OwnerAccept<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("OwnerAccept try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+    data_len(data, 0)?;
+
+    let [pending_owner, config_pda] = accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(pending_owner)?;
+    writable(config_pda)?;
+    check_pda(config_pda)?;
+
+    config_pda.check_borrow_mut()?;
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    if config.pending_owner().ne(pending_owner.address()) {
+      return Err(Ee::OnlyPendingOwner.into());
+    }
+    config.check_no_timelock()?;
+    Ok(Self {
+      pending_owner,
+      config_pda,
+      config,
+    })
+  }
+}
@@ -0,0 +1,55 @@
+use core::convert::TryFrom;
+use pinocchio::{error::ProgramError, AccountView, Address, ProgramResult};
+use pinocchio_log::log;
+
+use crate::{check_pda, data_len, get_time, instructions::check_signer, writable, Config, Ee};
+
+/// Cancel Program Owner Proposal: the prog_owner clears the pending owner
+pub struct OwnerCancel<'a> {
+  pub prog_owner: &'a AccountView, //signer
+  pub config_pda: &'a AccountView,
+  pub config: &'a mut Config,
+}
+impl<'a> OwnerCancel<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &26;
+
+  pub fn process(self) -> ProgramResult {
+    let OwnerCancel {
+      prog_owner: _,
+      config_pda: _,
+      config,
+    } = self;
+    log!("OwnerCancel process()");
+    config.set_pending_owner(&Address::default());
+    config.set_updated_at(get_time()?);
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for OwnerCancel<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("OwnerCancel try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+    data_len(data, 0)?;
+
+    let [prog_owner, config_pda] = accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(prog_owner)?;
+    writable(config_pda)?;
+    check_pda(config_pda)?;
+
+    config_pda.check_borrow_mut()?;
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    if config.prog_owner().ne(prog_owner.address()) {
+      return Err(Ee::OnlyProgOwner.into());
+    }
+    Ok(Self {
+      prog_owner,
+      config_pda,
+      config,
+    })
+  }
+}
@@ -0,0 +1,59 @@
+use core::convert::TryFrom;
+use pinocchio::{error::ProgramError, AccountView, ProgramResult};
+use pinocchio_log::log;
+
+use crate::{check_pda, data_len, get_time, instructions::check_signer, writable, Config, Ee};
+
+/// Propose Program Owner: the prog_owner proposes a new owner, who must accept it with OwnerAccept. Not allowed while a timelock delay is set
+pub struct OwnerPropose<'a> {
+  pub prog_owner: &'a AccountView, //signer
+  pub config_pda: &'a AccountView,
+  pub new_owner: &'a AccountView,
+  pub config: &'a mut Config,
+}
+impl<'a> OwnerPropose<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &24;
+
+  pub fn process(self) -> ProgramResult {
+    let OwnerPropose {
+      prog_owner: _,
+      config_pda: _,
+      new_owner,
+      config,
+    } = self;
+    log!("OwnerPropose process()");
+    config.set_pending_owner(new_owner.address());
+    config.set_updated_at(get_time()?);
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for OwnerPropose<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("OwnerPropose try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+    data_len(data, 0)?;
+
+    let [prog_owner, config_pda, new_owner] = accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(prog_owner)?;
+    writable(config_pda)?;
+    check_pda(config_pda)?;
+
+    config_pda.check_borrow_mut()?;
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    if config.prog_owner().ne(prog_owner.address()) {
+      return Err(Ee::OnlyProgOwner.into());
+    }
+    config.check_no_timelock()?;
+    Ok(Self {
+      prog_owner,
+      config_pda,
+      new_owner,
+      config,
+    })
+  }
+}
@@ -0,0 +1,153 @@
+use core::convert::TryFrom;
+use pinocchio::{
+  cpi::{Seed, Signer},
+  error::ProgramError,
+  AccountView, ProgramResult,
+};
+use pinocchio_log::log;
+use pinocchio_system::instructions::Transfer as SystemTransfer;
+use pinocchio_token::state::Mint;
+
+use crate::{
+  check_ata, check_atoken_gpvbd, check_sysprog, data_len, ensure_pool_accounts, executable,
+  instructions::{check_pda, check_signer, parse_u64},
+  none_zero_u64, pool_assets, pool_shares_for, rent_exempt_tokacct, sol_balc, writable, Config, Ee,
+  POOL_SEED,
+};
+
+/// Pool Deposit SOL: deposit lamports into the shared pool vault and receive share tokens proportional to the pool assets. The pool vault and its share mint are made on the first deposit
+pub struct PoolDepositSol<'a> {
+  pub user: &'a AccountView, //signer
+  pub config_pda: &'a AccountView,
+  pub pool: &'a AccountView,
+  pub share_mint: &'a AccountView,
+  pub user_ata: &'a AccountView,
+  pub token_program: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub atoken_program: &'a AccountView,
+  pub amount: u64,
+  pub min_shares: u64,
+}
+impl<'a> PoolDepositSol<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &48;
+
+  pub fn process(self) -> ProgramResult {
+    let PoolDepositSol {
+      user,
+      config_pda,
+      pool,
+      share_mint,
+      user_ata,
+      token_program,
+      system_program,
+      atoken_program: _,
+      amount,
+      min_shares,
+    } = self;
+    log!("PoolDepositSol process()");
+    let pool_bump = ensure_pool_accounts(user, config_pda, pool, share_mint, token_program)?;
+
+    let assets = pool_assets(pool)?;
+    let supply = Mint::from_account_view(share_mint)?.supply();
+    let shares = pool_shares_for(amount, assets, supply)?;
+    log!("assets: {}, supply: {}, shares: {}", assets, supply, shares);
+    if shares == 0 {
+      return Ee::PoolZeroOut.e();
+    }
+    if shares < min_shares {
+      return Ee::PoolSlippage.e();
+    }
+
+    SystemTransfer {
+      from: user,
+      to: pool,
+      lamports: amount,
+    }
+    .invoke()?;
+
+    if user_ata.is_data_empty() {
+      log!("Make user_ata");
+      pinocchio_associated_token_account::instructions::Create {
+        funding_account: user,
+        account: user_ata,
+        wallet: user,
+        mint: share_mint,
+        system_program,
+        token_program,
+      }
+      .invoke()?;
+    } else {
+      log!("user_ata has data");
+      check_ata(user_ata, user, share_mint)?;
+    }
+    writable(user_ata)?;
+    rent_exempt_tokacct(user_ata)?;
+
+    let signer_seeds = [
+      Seed::from(POOL_SEED),
+      Seed::from(config_pda.address().as_ref()),
+      Seed::from(core::slice::from_ref(&pool_bump)),
+    ];
+    log!("Mint shares");
+    pinocchio_token::instructions::MintTo {
+      mint: share_mint,
+      account: user_ata,
+      mint_authority: pool,
+      amount: shares,
+    }
+    .invoke_signed(&[Signer::from(&signer_seeds)])?;
+
+    log!("{} lamports deposited for {} shares", amount, shares);
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for PoolDepositSol<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("PoolDepositSol try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+
+    let [user, config_pda, pool, share_mint, user_ata, token_program, system_program, atoken_program] =
+      accounts
+    else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(user)?;
+    writable(config_pda)?;
+    check_pda(config_pda)?;
+    writable(pool)?;
+    writable(share_mint)?;
+    executable(token_program)?;
+    if token_program.address().ne(&pinocchio_token::ID) {
+      return Err(Ee::TokenProgram.into());
+    }
+    check_sysprog(system_program)?;
+    check_atoken_gpvbd(atoken_program)?;
+
+    config_pda.check_borrow_mut()?;
+    Config::from_account_view(config_pda)?.check_not_paused()?;
+
+    //8 amount + 8 min_shares
+    data_len(data, 16)?;
+    let amount = parse_u64(&data[0..8])?;
+    let min_shares = parse_u64(&data[8..16])?;
+    log!("amount: {}, min_shares: {}", amount, min_shares);
+    none_zero_u64(amount)?;
+    sol_balc(user, amount)?;
+
+    Ok(Self {
+      user,
+      config_pda,
+      pool,
+      share_mint,
+      user_ata,
+      token_program,
+      system_program,
+      atoken_program,
+      amount,
+      min_shares,
+    })
+  }
+}
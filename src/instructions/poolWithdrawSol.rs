@@ -0,0 +1,133 @@
+use core::convert::TryFrom;
+use pinocchio::{error::ProgramError, AccountView, ProgramResult};
+use pinocchio_log::log;
+use pinocchio_token::state::Mint;
+
+use crate::{
+  ata_balc, check_ata, check_mint0a, check_pool, data_len, derive_pda1, executable,
+  instructions::{check_pda, check_signer, parse_u64},
+  none_zero_u64, pool_assets, pool_lamports_for, writable, Config, Ee, POOL_SEED, SHARE_MINT_SEED,
+};
+
+/// Pool Withdraw SOL: burn share tokens for their part of the shared pool vault lamports
+pub struct PoolWithdrawSol<'a> {
+  pub user: &'a AccountView, //signer
+  pub config_pda: &'a AccountView,
+  pub pool: &'a AccountView,
+  pub share_mint: &'a AccountView,
+  pub user_ata: &'a AccountView,
+  pub token_program: &'a AccountView,
+  pub shares: u64,
+  pub min_lamports: u64,
+}
+impl<'a> PoolWithdrawSol<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &49;
+
+  pub fn process(self) -> ProgramResult {
+    let PoolWithdrawSol {
+      user,
+      config_pda: _,
+      pool,
+      share_mint,
+      user_ata,
+      token_program: _,
+      shares,
+      min_lamports,
+    } = self;
+    log!("PoolWithdrawSol process()");
+    let assets = pool_assets(pool)?;
+    let supply = Mint::from_account_view(share_mint)?.supply();
+    let amount = pool_lamports_for(shares, assets, supply)?;
+    log!(
+      "assets: {}, supply: {}, lamports: {}",
+      assets,
+      supply,
+      amount
+    );
+    if amount == 0 {
+      return Ee::PoolZeroOut.e();
+    }
+    if amount < min_lamports {
+      return Ee::PoolSlippage.e();
+    }
+
+    log!("Burn shares");
+    pinocchio_token::instructions::Burn {
+      account: user_ata,
+      mint: share_mint,
+      authority: user,
+      amount: shares,
+    }
+    .invoke()?;
+
+    // Transfer SOL from the pool vault to user
+    {
+      let from_lam = pool
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(ProgramError::InsufficientFunds)?;
+      pool.set_lamports(from_lam);
+
+      let sum_lam = user
+        .lamports()
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+      user.set_lamports(sum_lam);
+    }
+    log!("{} shares burnt for {} lamports", shares, amount);
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for PoolWithdrawSol<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("PoolWithdrawSol try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+
+    let [user, config_pda, pool, share_mint, user_ata, token_program] = accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(user)?;
+    writable(config_pda)?;
+    check_pda(config_pda)?;
+    config_pda.check_borrow_mut()?;
+    Config::check(config_pda)?;
+    writable(pool)?;
+    check_pool(pool)?;
+    writable(share_mint)?;
+    writable(user_ata)?;
+    executable(token_program)?;
+    check_mint0a(share_mint, token_program)?;
+
+    let (expected_pool, _bump) = derive_pda1(config_pda.address(), POOL_SEED)?;
+    if pool.address().ne(&expected_pool) {
+      return Err(Ee::PoolPDA.into());
+    }
+    let (expected_share_mint, _bump) = derive_pda1(config_pda.address(), SHARE_MINT_SEED)?;
+    if share_mint.address().ne(&expected_share_mint) {
+      return Err(Ee::ShareMintPDA.into());
+    }
+    check_ata(user_ata, user, share_mint)?;
+
+    //8 shares + 8 min_lamports
+    data_len(data, 16)?;
+    let shares = parse_u64(&data[0..8])?;
+    let min_lamports = parse_u64(&data[8..16])?;
+    log!("shares: {}, min_lamports: {}", shares, min_lamports);
+    none_zero_u64(shares)?;
+    ata_balc(user_ata, shares)?;
+
+    Ok(Self {
+      user,
+      config_pda,
+      pool,
+      share_mint,
+      user_ata,
+      token_program,
+      shares,
+      min_lamports,
+    })
+  }
+}
@@ -0,0 +1,575 @@
+//! Token2022 extension CPIs that pinocchio_token_2022 does not provide yet
+use pinocchio::{
+  cpi::{invoke, invoke_signed, invoke_with_bounds, Signer},
+  error::ProgramError,
+  instruction::{InstructionAccount, InstructionView},
+  sysvars::{clock::Clock, Sysvar},
+  AccountView, Address, ProgramResult,
+};
+
+use crate::{tlv_extensions, Ee, EXT_INTEREST_BEARING_CONFIG, EXT_TRANSFER_FEE_CONFIG};
+
+/// TokenInstruction::TransferFeeExtension
+const TRANSFER_FEE_EXTENSION: u8 = 26;
+/// TokenInstruction::InitializeNonTransferableMint
+const INITIALIZE_NON_TRANSFERABLE_MINT: u8 = 32;
+/// TokenInstruction::InterestBearingMintExtension
+const INTEREST_BEARING_MINT_EXTENSION: u8 = 33;
+/// TokenInstruction::MetadataPointerExtension
+const METADATA_POINTER_EXTENSION: u8 = 39;
+/// spl_token_metadata_interface discriminators: the first 8 bytes of sha256("spl_token_metadata_interface:<name>")
+const TOKEN_METADATA_INITIALIZE: [u8; 8] = [210, 225, 30, 162, 88, 184, 77, 141];
+const TOKEN_METADATA_UPDATE_FIELD: [u8; 8] = [221, 233, 49, 45, 181, 202, 220, 200];
+
+/// Mint base 82 bytes + padding to the 165 bytes of a token account + 1 byte of account type
+pub const EXTENSIONS_PADDING_AND_OFFSET: usize = 84;
+/// [2 (extension type) + 2 (length)]
+pub const TLV_HEADER_SIZE: usize = 4;
+/// [32 (config authority) + 32 (withdraw_withheld_authority) + 8 (withheld_amount) + 2 * (8 (epoch) + 8 (maximum_fee) + 2 (basis_points))]
+pub const TRANSFER_FEE_CONFIG_SIZE: usize = TLV_HEADER_SIZE + 32 + 32 + 8 + 18 + 18;
+/// NonTransferable has no value, only the TLV header
+pub const NON_TRANSFERABLE_SIZE: usize = TLV_HEADER_SIZE;
+/// [32 (rate_authority) + 8 (initialization_timestamp) + 2 (pre_update_average_rate) + 8 (last_update_timestamp) + 2 (current_rate)]
+pub const INTEREST_BEARING_CONFIG_SIZE: usize = TLV_HEADER_SIZE + 32 + 8 + 2 + 8 + 2;
+/// transfer fees are in basis points of the transferred amount
+pub const MAX_FEE_BASIS_POINTS: u16 = 10_000;
+/// Token2022InitMint extension flags
+pub const MINT_EXT_TRANSFER_FEE: u8 = 1;
+pub const MINT_EXT_NON_TRANSFERABLE: u8 = 1 << 1;
+pub const MINT_EXT_INTEREST_BEARING: u8 = 1 << 2;
+/// the most source accounts in one HarvestWithheldTokensToMint
+pub const MAX_HARVEST_SOURCES: usize = 10;
+/// [32 (authority) + 32 (metadata_address)]
+pub const METADATA_POINTER_SIZE: usize = TLV_HEADER_SIZE + 32 + 32;
+/// [32 (update_authority) + 32 (mint) + 4 (name len) + 4 (symbol len) + 4 (uri len) + 4 (additional_metadata len)], without the string bytes
+pub const TOKEN_METADATA_BASE_SIZE: usize = TLV_HEADER_SIZE + 32 + 32 + 4 + 4 + 4 + 4;
+
+/// Metadata fields as in spl_token_metadata_interface::state::Field
+pub const METADATA_FIELD_NAME: u8 = 0;
+pub const METADATA_FIELD_SYMBOL: u8 = 1;
+pub const METADATA_FIELD_URI: u8 = 2;
+pub const METADATA_FIELD_KEY: u8 = 3;
+
+/// fixed size string inputs are padded with zeros
+pub fn trim_padding(bytes: &[u8]) -> &[u8] {
+  let len = bytes.iter().rposition(|b| *b != 0).map_or(0, |i| i + 1);
+  &bytes[..len]
+}
+/// find the value of a TLV extension in a Token2022 mint's data
+pub fn mint_extension(data: &[u8], extension_type: u16) -> Option<&[u8]> {
+  tlv_extensions(data)
+    .find(|(ext_type, _)| *ext_type == extension_type)
+    .map(|(_, value)| value)
+}
+/// the withdraw_withheld_authority of a mint with the TransferFeeConfig extension
+pub fn withdraw_withheld_authority(mint: &AccountView) -> Result<Address, ProgramError> {
+  let data = mint.try_borrow()?;
+  let config = mint_extension(&data, EXT_TRANSFER_FEE_CONFIG).ok_or(Ee::TransferFeeConfig)?;
+  Ok(Address::new_from_array(
+    config[32..64]
+      .try_into()
+      .map_err(|_| Ee::TransferFeeConfig)?,
+  ))
+}
+/// the rate_authority of a mint with the InterestBearingConfig extension
+pub fn rate_authority(mint: &AccountView) -> Result<Address, ProgramError> {
+  let data = mint.try_borrow()?;
+  let config =
+    mint_extension(&data, EXT_INTEREST_BEARING_CONFIG).ok_or(Ee::InterestBearingConfig)?;
+  Ok(Address::new_from_array(
+    config[0..32]
+      .try_into()
+      .map_err(|_| Ee::InterestBearingConfig)?,
+  ))
+}
+/// the fee withheld by the token program when transferring `amount` in the current epoch; zero for mints without the TransferFeeConfig extension
+pub fn transfer_fee(mint: &AccountView, amount: u64) -> Result<u64, ProgramError> {
+  if !mint.owned_by(&pinocchio_token_2022::ID) {
+    return Ok(0);
+  }
+  let data = mint.try_borrow()?;
+  let Some(config) = mint_extension(&data, EXT_TRANSFER_FEE_CONFIG) else {
+    return Ok(0);
+  };
+  if config.len() + TLV_HEADER_SIZE != TRANSFER_FEE_CONFIG_SIZE {
+    return Err(Ee::TransferFeeConfig.into());
+  }
+  let u64_at = |i: usize| u64::from_le_bytes(config[i..i + 8].try_into().unwrap_or_default());
+  let u16_at = |i: usize| u16::from_le_bytes([config[i], config[i + 1]]);
+
+  //older fee at 72, newer fee at 90: [8 (epoch) + 8 (maximum_fee) + 2 (basis_points)]
+  let epoch = Clock::get().map_err(|_| Ee::ClockGet)?.epoch;
+  let fee_at = if epoch >= u64_at(90) { 90 } else { 72 };
+  let maximum_fee = u64_at(fee_at + 8);
+  let basis_points = u16_at(fee_at + 16);
+  if basis_points == 0 || amount == 0 {
+    return Ok(0);
+  }
+  //round up like the token program does
+  let fee = (amount as u128)
+    .checked_mul(basis_points as u128)
+    .and_then(|n| n.checked_add(MAX_FEE_BASIS_POINTS as u128 - 1))
+    .map(|n| n / MAX_FEE_BASIS_POINTS as u128)
+    .ok_or(ProgramError::ArithmeticOverflow)?;
+  Ok(fee.min(maximum_fee as u128) as u64)
+}
+/// write a borsh string: u32 length + bytes. Returns the offset after it
+fn write_str(buf: &mut [u8], offset: usize, s: &[u8]) -> usize {
+  buf[offset..offset + 4].copy_from_slice(&(s.len() as u32).to_le_bytes());
+  buf[offset + 4..offset + 4 + s.len()].copy_from_slice(s);
+  offset + 4 + s.len()
+}
+
+/// Initialize the MetadataPointer extension. Must come before InitializeMint2
+///
+/// ### Accounts:
+///   0. `[WRITE]` The mint to initialize.
+pub struct InitializeMetadataPointer<'a, 'b> {
+  pub mint: &'a AccountView,
+  /// can update the metadata address
+  pub authority: Option<&'a Address>,
+  /// the account holding the metadata, usually the mint itself
+  pub metadata_address: Option<&'a Address>,
+  pub token_program: &'b Address,
+}
+impl InitializeMetadataPointer<'_, '_> {
+  pub fn invoke(&self) -> ProgramResult {
+    let instruction_accounts = [InstructionAccount::writable(self.mint.address())];
+
+    // -  [0]: extension instruction, [1]: Initialize
+    // -  [2..34]: authority, all zeros for None
+    // -  [34..66]: metadata_address, all zeros for None
+    let mut instruction_data = [0u8; 66];
+    instruction_data[0] = METADATA_POINTER_EXTENSION;
+    if let Some(authority) = self.authority {
+      instruction_data[2..34].copy_from_slice(authority.as_ref());
+    }
+    if let Some(metadata_address) = self.metadata_address {
+      instruction_data[34..66].copy_from_slice(metadata_address.as_ref());
+    }
+    let instruction = InstructionView {
+      program_id: self.token_program,
+      accounts: &instruction_accounts,
+      data: &instruction_data,
+    };
+    invoke(&instruction, &[self.mint])
+  }
+}
+
+/// Initialize the TokenMetadata extension inside the mint. Must come after InitializeMint2, and the mint must already hold the lamports for its grown size
+///
+/// ### Accounts:
+///   0. `[WRITE]` The metadata account, which is the mint.
+///   1. `[]` The update authority.
+///   2. `[]` The mint.
+///   3. `[SIGNER]` The mint authority.
+pub struct InitializeTokenMetadata<'a, 'b> {
+  pub metadata: &'a AccountView,
+  pub update_authority: &'a AccountView,
+  pub mint: &'a AccountView,
+  pub mint_authority: &'a AccountView,
+  pub name: &'b [u8],
+  pub symbol: &'b [u8],
+  pub uri: &'b [u8],
+  pub token_program: &'b Address,
+}
+impl InitializeTokenMetadata<'_, '_> {
+  /// the longest name + symbol + uri this CPI can carry
+  pub const MAX_STR_LEN: usize = 128;
+
+  pub fn invoke(&self) -> ProgramResult {
+    self.invoke_signed(&[])
+  }
+  pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+    let instruction_accounts = [
+      InstructionAccount::writable(self.metadata.address()),
+      InstructionAccount::readonly(self.update_authority.address()),
+      InstructionAccount::readonly(self.mint.address()),
+      InstructionAccount::readonly_signer(self.mint_authority.address()),
+    ];
+    // -  [0..8]: discriminator
+    // -  then name, symbol and uri as borsh strings
+    let mut instruction_data = [0u8; 8 + 12 + Self::MAX_STR_LEN];
+    instruction_data[..8].copy_from_slice(&TOKEN_METADATA_INITIALIZE);
+    let offset = write_str(&mut instruction_data, 8, self.name);
+    let offset = write_str(&mut instruction_data, offset, self.symbol);
+    let length = write_str(&mut instruction_data, offset, self.uri);
+
+    let instruction = InstructionView {
+      program_id: self.token_program,
+      accounts: &instruction_accounts,
+      data: &instruction_data[..length],
+    };
+    invoke_signed(
+      &instruction,
+      &[
+        self.metadata,
+        self.update_authority,
+        self.mint,
+        self.mint_authority,
+      ],
+      signers,
+    )
+  }
+}
+
+/// Update a TokenMetadata field, or add/overwrite a key/value pair. The token program reallocs the metadata account, which must already hold the lamports for its grown size
+///
+/// ### Accounts:
+///   0. `[WRITE]` The metadata account, which is the mint.
+///   1. `[SIGNER]` The update authority.
+pub struct UpdateTokenMetadataField<'a, 'b> {
+  pub metadata: &'a AccountView,
+  pub update_authority: &'a AccountView,
+  /// METADATA_FIELD_NAME, METADATA_FIELD_SYMBOL, METADATA_FIELD_URI or METADATA_FIELD_KEY
+  pub field: u8,
+  /// only used with METADATA_FIELD_KEY
+  pub key: &'b [u8],
+  pub value: &'b [u8],
+  pub token_program: &'b Address,
+}
+impl UpdateTokenMetadataField<'_, '_> {
+  /// the longest key + value this CPI can carry
+  pub const MAX_STR_LEN: usize = 64;
+
+  pub fn invoke(&self) -> ProgramResult {
+    self.invoke_signed(&[])
+  }
+  pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+    let instruction_accounts = [
+      InstructionAccount::writable(self.metadata.address()),
+      InstructionAccount::readonly_signer(self.update_authority.address()),
+    ];
+    // -  [0..8]: discriminator
+    // -  [8]: field, followed by the key as a borsh string for METADATA_FIELD_KEY
+    // -  then the value as a borsh string
+    let mut instruction_data = [0u8; 8 + 1 + 8 + Self::MAX_STR_LEN];
+    instruction_data[..8].copy_from_slice(&TOKEN_METADATA_UPDATE_FIELD);
+    instruction_data[8] = self.field;
+    let mut offset = 9;
+    if self.field == METADATA_FIELD_KEY {
+      offset = write_str(&mut instruction_data, offset, self.key);
+    }
+    let length = write_str(&mut instruction_data, offset, self.value);
+
+    let instruction = InstructionView {
+      program_id: self.token_program,
+      accounts: &instruction_accounts,
+      data: &instruction_data[..length],
+    };
+    invoke_signed(
+      &instruction,
+      &[self.metadata, self.update_authority],
+      signers,
+    )
+  }
+}
+
+/// Initialize the TransferFeeConfig extension. Must come before InitializeMint2
+///
+/// ### Accounts:
+///   0. `[WRITE]` The mint to initialize.
+pub struct InitializeTransferFeeConfig<'a, 'b> {
+  pub mint: &'a AccountView,
+  /// can update the fee
+  pub transfer_fee_config_authority: Option<&'b Address>,
+  /// can withdraw the withheld fees
+  pub withdraw_withheld_authority: Option<&'b Address>,
+  pub transfer_fee_basis_points: u16,
+  pub maximum_fee: u64,
+  pub token_program: &'b Address,
+}
+impl InitializeTransferFeeConfig<'_, '_> {
+  pub fn invoke(&self) -> ProgramResult {
+    let instruction_accounts = [InstructionAccount::writable(self.mint.address())];
+
+    // -  [0]: extension instruction, [1]: InitializeTransferFeeConfig
+    // -  then both authorities as COption: 1 byte tag + 32 bytes when Some
+    // -  then basis points u16 and maximum fee u64
+    let mut instruction_data = [0u8; 2 + 33 + 33 + 2 + 8];
+    instruction_data[0] = TRANSFER_FEE_EXTENSION;
+    let mut offset = 2;
+    for authority in [
+      self.transfer_fee_config_authority,
+      self.withdraw_withheld_authority,
+    ] {
+      if let Some(authority) = authority {
+        instruction_data[offset] = 1;
+        instruction_data[offset + 1..offset + 33].copy_from_slice(authority.as_ref());
+        offset += 33;
+      } else {
+        offset += 1;
+      }
+    }
+    instruction_data[offset..offset + 2]
+      .copy_from_slice(&self.transfer_fee_basis_points.to_le_bytes());
+    instruction_data[offset + 2..offset + 10].copy_from_slice(&self.maximum_fee.to_le_bytes());
+
+    let instruction = InstructionView {
+      program_id: self.token_program,
+      accounts: &instruction_accounts,
+      data: &instruction_data[..offset + 10],
+    };
+    invoke(&instruction, &[self.mint])
+  }
+}
+
+/// Initialize the NonTransferable extension: tokens can be minted and burned, but never transferred. Must come before InitializeMint2
+///
+/// ### Accounts:
+///   0. `[WRITE]` The mint to initialize.
+pub struct InitializeNonTransferableMint<'a, 'b> {
+  pub mint: &'a AccountView,
+  pub token_program: &'b Address,
+}
+impl InitializeNonTransferableMint<'_, '_> {
+  pub fn invoke(&self) -> ProgramResult {
+    let instruction_accounts = [InstructionAccount::writable(self.mint.address())];
+    let instruction_data = [INITIALIZE_NON_TRANSFERABLE_MINT];
+
+    let instruction = InstructionView {
+      program_id: self.token_program,
+      accounts: &instruction_accounts,
+      data: &instruction_data,
+    };
+    invoke(&instruction, &[self.mint])
+  }
+}
+
+/// Initialize the InterestBearingConfig extension. The rate is in basis points per year and only changes the UI amount. Must come before InitializeMint2
+///
+/// ### Accounts:
+///   0. `[WRITE]` The mint to initialize.
+pub struct InitializeInterestBearingMint<'a, 'b> {
+  pub mint: &'a AccountView,
+  /// can update the rate
+  pub rate_authority: Option<&'b Address>,
+  pub rate: i16,
+  pub token_program: &'b Address,
+}
+impl InitializeInterestBearingMint<'_, '_> {
+  pub fn invoke(&self) -> ProgramResult {
+    let instruction_accounts = [InstructionAccount::writable(self.mint.address())];
+
+    // -  [0]: extension instruction, [1]: Initialize
+    // -  [2..34]: rate authority as OptionalNonZeroPubkey, all zeros when None
+    // -  [34..36]: rate i16
+    let mut instruction_data = [0u8; 2 + 32 + 2];
+    instruction_data[0] = INTEREST_BEARING_MINT_EXTENSION;
+    if let Some(rate_authority) = self.rate_authority {
+      instruction_data[2..34].copy_from_slice(rate_authority.as_ref());
+    }
+    instruction_data[34..36].copy_from_slice(&self.rate.to_le_bytes());
+
+    let instruction = InstructionView {
+      program_id: self.token_program,
+      accounts: &instruction_accounts,
+      data: &instruction_data,
+    };
+    invoke(&instruction, &[self.mint])
+  }
+}
+
+/// Update the interest rate of a mint with the InterestBearingConfig extension
+///
+/// ### Accounts:
+///   0. `[WRITE]` The mint.
+///   1. `[SIGNER]` The mint's rate authority.
+pub struct UpdateInterestRate<'a, 'b> {
+  pub mint: &'a AccountView,
+  pub rate_authority: &'a AccountView,
+  pub rate: i16,
+  pub token_program: &'b Address,
+}
+impl UpdateInterestRate<'_, '_> {
+  pub fn invoke(&self) -> ProgramResult {
+    self.invoke_signed(&[])
+  }
+  pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+    let instruction_accounts = [
+      InstructionAccount::writable(self.mint.address()),
+      InstructionAccount::readonly_signer(self.rate_authority.address()),
+    ];
+    // -  [0]: extension instruction, [1]: UpdateRate, [2..4]: rate i16
+    let mut instruction_data = [INTEREST_BEARING_MINT_EXTENSION, 1, 0, 0];
+    instruction_data[2..4].copy_from_slice(&self.rate.to_le_bytes());
+
+    let instruction = InstructionView {
+      program_id: self.token_program,
+      accounts: &instruction_accounts,
+      data: &instruction_data,
+    };
+    invoke_signed(&instruction, &[self.mint, self.rate_authority], signers)
+  }
+}
+
+/// TransferChecked with the expected fee. The fee must match the mint's current TransferFeeConfig, or be zero for mints without it
+///
+/// ### Accounts:
+///   0. `[WRITE]` The source account.
+///   1. `[]` The token mint.
+///   2. `[WRITE]` The destination account.
+///   3. `[SIGNER]` The source account's owner/delegate.
+pub struct TransferCheckedWithFee<'a, 'b> {
+  pub from: &'a AccountView,
+  pub mint: &'a AccountView,
+  pub to: &'a AccountView,
+  pub authority: &'a AccountView,
+  pub amount: u64,
+  pub decimals: u8,
+  pub fee: u64,
+  pub token_program: &'b Address,
+}
+impl TransferCheckedWithFee<'_, '_> {
+  pub fn invoke(&self) -> ProgramResult {
+    self.invoke_signed(&[])
+  }
+  pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+    let instruction_accounts = [
+      InstructionAccount::writable(self.from.address()),
+      InstructionAccount::readonly(self.mint.address()),
+      InstructionAccount::writable(self.to.address()),
+      InstructionAccount::readonly_signer(self.authority.address()),
+    ];
+    // -  [0]: extension instruction, [1]: TransferCheckedWithFee
+    // -  [2..10]: amount, [10]: decimals, [11..19]: fee
+    let mut instruction_data = [0u8; 19];
+    instruction_data[0] = TRANSFER_FEE_EXTENSION;
+    instruction_data[1] = 1;
+    instruction_data[2..10].copy_from_slice(&self.amount.to_le_bytes());
+    instruction_data[10] = self.decimals;
+    instruction_data[11..19].copy_from_slice(&self.fee.to_le_bytes());
+
+    let instruction = InstructionView {
+      program_id: self.token_program,
+      accounts: &instruction_accounts,
+      data: &instruction_data,
+    };
+    invoke_signed(
+      &instruction,
+      &[self.from, self.mint, self.to, self.authority],
+      signers,
+    )
+  }
+}
+
+/// Program transfers of any mint: TransferCheckedWithFee when the mint has the TransferFeeConfig extension, else TransferChecked. Returns the withheld fee, so callers can book the amount actually received
+pub struct TransferCheckedInterface<'a, 'b> {
+  pub from: &'a AccountView,
+  pub mint: &'a AccountView,
+  pub to: &'a AccountView,
+  pub authority: &'a AccountView,
+  pub amount: u64,
+  pub decimals: u8,
+  pub token_program: &'b Address,
+}
+impl TransferCheckedInterface<'_, '_> {
+  pub fn invoke(&self) -> Result<u64, ProgramError> {
+    self.invoke_signed(&[])
+  }
+  pub fn invoke_signed(&self, signers: &[Signer]) -> Result<u64, ProgramError> {
+    let fee = transfer_fee(self.mint, self.amount)?;
+    if fee == 0 {
+      pinocchio_token_2022::instructions::TransferChecked {
+        from: self.from,
+        mint: self.mint,
+        to: self.to,
+        authority: self.authority,
+        amount: self.amount,
+        decimals: self.decimals,
+        token_program: self.token_program,
+      }
+      .invoke_signed(signers)?;
+    } else {
+      TransferCheckedWithFee {
+        from: self.from,
+        mint: self.mint,
+        to: self.to,
+        authority: self.authority,
+        amount: self.amount,
+        decimals: self.decimals,
+        fee,
+        token_program: self.token_program,
+      }
+      .invoke_signed(signers)?;
+    }
+    Ok(fee)
+  }
+}
+
+/// Permissionless: move the withheld fees of token accounts into their mint
+///
+/// ### Accounts:
+///   0. `[WRITE]` The mint.
+///   1. `..1+N` `[WRITE]` The source accounts to harvest from.
+pub struct HarvestWithheldTokensToMint<'a, 'b> {
+  pub mint: &'a AccountView,
+  pub sources: &'a [AccountView],
+  pub token_program: &'b Address,
+}
+impl HarvestWithheldTokensToMint<'_, '_> {
+  pub fn invoke(&self) -> ProgramResult {
+    let n = self.sources.len();
+    if n > MAX_HARVEST_SOURCES {
+      return Err(ProgramError::InvalidArgument);
+    }
+    let instruction_accounts: [InstructionAccount; 1 + MAX_HARVEST_SOURCES] =
+      core::array::from_fn(|i| match i {
+        0 => InstructionAccount::writable(self.mint.address()),
+        i if i <= n => InstructionAccount::writable(self.sources[i - 1].address()),
+        _ => InstructionAccount::readonly(self.mint.address()),
+      });
+    let account_views: [&AccountView; 1 + MAX_HARVEST_SOURCES] =
+      core::array::from_fn(|i| match i {
+        i if i > 0 && i <= n => &self.sources[i - 1],
+        _ => self.mint,
+      });
+    // -  [0]: extension instruction, [1]: HarvestWithheldTokensToMint
+    let instruction_data = [TRANSFER_FEE_EXTENSION, 4];
+
+    let instruction = InstructionView {
+      program_id: self.token_program,
+      accounts: &instruction_accounts[..1 + n],
+      data: &instruction_data,
+    };
+    invoke_with_bounds::<{ 1 + MAX_HARVEST_SOURCES }>(&instruction, &account_views[..1 + n])
+  }
+}
+
+/// Move all withheld fees in the mint to a token account
+///
+/// ### Accounts:
+///   0. `[WRITE]` The mint.
+///   1. `[WRITE]` The fee receiver account.
+///   2. `[SIGNER]` The mint's withdraw_withheld_authority.
+pub struct WithdrawWithheldTokensFromMint<'a, 'b> {
+  pub mint: &'a AccountView,
+  pub to: &'a AccountView,
+  pub withdraw_withheld_authority: &'a AccountView,
+  pub token_program: &'b Address,
+}
+impl WithdrawWithheldTokensFromMint<'_, '_> {
+  pub fn invoke(&self) -> ProgramResult {
+    self.invoke_signed(&[])
+  }
+  pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+    let instruction_accounts = [
+      InstructionAccount::writable(self.mint.address()),
+      InstructionAccount::writable(self.to.address()),
+      InstructionAccount::readonly_signer(self.withdraw_withheld_authority.address()),
+    ];
+    // -  [0]: extension instruction, [1]: WithdrawWithheldTokensFromMint
+    let instruction_data = [TRANSFER_FEE_EXTENSION, 2];
+
+    let instruction = InstructionView {
+      program_id: self.token_program,
+      accounts: &instruction_accounts,
+      data: &instruction_data,
+    };
+    invoke_signed(
+      &instruction,
+      &[self.mint, self.to, self.withdraw_withheld_authority],
+      signers,
+    )
+  }
+}
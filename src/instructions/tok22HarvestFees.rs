@@ -0,0 +1,148 @@
+use core::convert::TryFrom;
+use pinocchio::{
+  cpi::{Seed, Signer},
+  error::ProgramError,
+  AccountView, Address, ProgramResult,
+};
+use pinocchio_log::log;
+
+use crate::{
+  ata_amount_interface, check_ata_interface, check_atoken_gpvbd, check_mint22a, check_sysprog,
+  check_vault, data_len, executable, instructions::check_signer, rent_exempt_tokacct_interface,
+  withdraw_withheld_authority, writable, Config, Ee, HarvestWithheldTokensToMint,
+  WithdrawWithheldTokensFromMint, MAX_HARVEST_SOURCES, VAULT_SEED,
+};
+
+/// Token2022 Harvest Fees: harvest the withheld transfer fees of the given token accounts into the mint, then withdraw all withheld fees of the mint into the config vault ATA. The mint's withdraw_withheld_authority must be the config vault
+pub struct Token2022HarvestFees<'a> {
+  pub payer: &'a AccountView, //signer
+  pub config_pda: &'a AccountView,
+  pub mint: &'a AccountView,
+  pub vault: &'a AccountView,
+  pub vault_ata: &'a AccountView,
+  pub token_program: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub atoken_program: &'a AccountView,
+  pub sources: &'a [AccountView],
+  pub seed_owner: &'a Address,
+  pub vault_bump: u8,
+}
+impl<'a> Token2022HarvestFees<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &45;
+
+  pub fn process(self) -> ProgramResult {
+    let Token2022HarvestFees {
+      payer,
+      config_pda,
+      mint,
+      vault,
+      vault_ata,
+      token_program,
+      system_program,
+      atoken_program: _,
+      sources,
+      seed_owner,
+      vault_bump,
+    } = self;
+    log!("Token2022HarvestFees process()");
+    if !sources.is_empty() {
+      log!("Harvest {} token accounts to the mint", sources.len());
+      HarvestWithheldTokensToMint {
+        mint,
+        sources,
+        token_program: token_program.address(),
+      }
+      .invoke()?;
+    }
+
+    if vault_ata.is_data_empty() {
+      log!("Make vault_ata");
+      pinocchio_associated_token_account::instructions::Create {
+        funding_account: payer,
+        account: vault_ata,
+        wallet: vault,
+        mint,
+        system_program,
+        token_program,
+      }
+      .invoke()?;
+    } else {
+      log!("vault_ata has data");
+      check_ata_interface(vault_ata, vault, mint)?;
+    }
+    writable(vault_ata)?;
+    rent_exempt_tokacct_interface(vault_ata)?;
+    let balance_before = ata_amount_interface(vault_ata)?;
+
+    let signer_seeds = [
+      Seed::from(VAULT_SEED),
+      Seed::from(seed_owner.as_ref()),
+      Seed::from(core::slice::from_ref(&vault_bump)),
+    ];
+    let seed_signer = Signer::from(&signer_seeds);
+
+    log!("Withdraw withheld fees to Vault ATA");
+    WithdrawWithheldTokensFromMint {
+      mint,
+      to: vault_ata,
+      withdraw_withheld_authority: vault,
+      token_program: token_program.address(),
+    }
+    .invoke_signed(&[seed_signer])?;
+
+    let harvested = ata_amount_interface(vault_ata)?.saturating_sub(balance_before);
+    log!("harvested: {}", harvested);
+    Config::from_account_view(config_pda)?.add_token_balance(harvested)?;
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for Token2022HarvestFees<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("Token2022HarvestFees try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+
+    let [payer, config_pda, mint, vault, vault_ata, token_program, system_program, atoken_program, sources @ ..] =
+      accounts
+    else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(payer)?;
+    executable(token_program)?;
+    check_sysprog(system_program)?;
+    check_atoken_gpvbd(atoken_program)?;
+    writable(config_pda)?;
+    writable(mint)?;
+    check_mint22a(mint, token_program)?;
+    if sources.len() > MAX_HARVEST_SOURCES {
+      return Err(ProgramError::InvalidArgument);
+    }
+    for source in sources {
+      writable(source)?;
+    }
+    data_len(data, 0)?;
+
+    config_pda.check_borrow_mut()?;
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    check_vault(vault, config.vault())?;
+    if withdraw_withheld_authority(mint)?.ne(vault.address()) {
+      return Err(Ee::WithdrawWithheldAuthority.into());
+    }
+
+    Ok(Self {
+      payer,
+      config_pda,
+      mint,
+      vault,
+      vault_ata,
+      token_program,
+      system_program,
+      atoken_program,
+      sources,
+      seed_owner: config.seed_owner(),
+      vault_bump: config.vault_bump(),
+    })
+  }
+}
@@ -4,10 +4,11 @@ use pinocchio_log::log;
 
 use crate::{
   check_mint22a, check_sysprog, executable, initialized, instructions::check_signer,
-  not_initialized, rent_exempt_mint22, writable,
+  not_initialized, rent_exempt_mint22, tlv_extensions, u8_to_bool, writable, Ee,
+  EXT_IMMUTABLE_OWNER,
 };
 
-/// Token2022 Init ATA(Associated Token Account)
+/// Token2022 Init ATA(Associated Token Account). Optionally require the ImmutableOwner extension, so the owner of a soulbound badge account can never be reassigned
 pub struct Token2022InitAta<'a> {
   pub payer: &'a AccountView,
   pub to_wallet: &'a AccountView,
@@ -16,6 +17,7 @@ pub struct Token2022InitAta<'a> {
   pub token_program: &'a AccountView,
   pub system_program: &'a AccountView,
   pub atoken_program: &'a AccountView,
+  pub immutable_owner: bool,
 }
 impl<'a> Token2022InitAta<'a> {
   pub const DISCRIMINATOR: &'a u8 = &10;
@@ -29,15 +31,16 @@ impl<'a> Token2022InitAta<'a> {
       token_program,
       system_program,
       atoken_program: _,
+      immutable_owner,
     } = self;
     log!("Token2022InitAta process()");
     pinocchio_associated_token_account::instructions::Create {
       funding_account: payer, // Keypair
       account: ata,
       wallet: to_wallet,
-      mint: mint,
-      system_program: system_program,
-      token_program: token_program,
+      mint,
+      system_program,
+      token_program,
     }
     .invoke()?;
     /*pinocchio_token_2022::instructions::InitializeAccount3 {
@@ -47,6 +50,13 @@ impl<'a> Token2022InitAta<'a> {
         token_program: token_program.key(),
     }
     .invoke()?;//invalid account data for instruction*/
+
+    //the associated token program initializes ImmutableOwner on Token2022 accounts. Refuse any that lack it
+    if immutable_owner
+      && !tlv_extensions(&ata.try_borrow()?).any(|(ext_type, _)| ext_type == EXT_IMMUTABLE_OWNER)
+    {
+      return Ee::ImmutableOwner.e();
+    }
     Ok(())
   }
   pub fn init_if_needed(self) -> ProgramResult {
@@ -79,6 +89,13 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for Token2022InitAta<'a> {
     rent_exempt_mint22(mint)?;
     check_mint22a(mint, token_program)?;
 
+    //optional: 1 to require ImmutableOwner
+    let immutable_owner = match data {
+      [] => false,
+      [flag] => u8_to_bool(*flag)?,
+      _ => return Err(Ee::InputDataLen.into()),
+    };
+
     Ok(Self {
       payer,
       to_wallet,
@@ -87,6 +104,7 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for Token2022InitAta<'a> {
       token_program,
       system_program,
       atoken_program,
+      immutable_owner,
     })
   }
 }
@@ -4,22 +4,34 @@ use pinocchio_log::log;
 use pinocchio_system::instructions::CreateAccount;
 
 use crate::{
-  check_decimals_max, check_sysprog, data_len, executable, initialized, instructions::check_signer,
-  not_initialized, to10bytes, to32bytes, to6bytes, writable,
+  check_decimals_max, check_pda, check_sysprog, data_len, executable, initialized,
+  instructions::check_signer, not_initialized, parse_u64, to10bytes, to32bytes, to6bytes,
+  trim_padding, writable, Admin, Config, Ee, InitializeInterestBearingMint,
+  InitializeMetadataPointer, InitializeNonTransferableMint, InitializeTokenMetadata,
+  InitializeTransferFeeConfig, EXTENSIONS_PADDING_AND_OFFSET, INTEREST_BEARING_CONFIG_SIZE,
+  MAX_FEE_BASIS_POINTS, METADATA_POINTER_SIZE, MINT_EXT_INTEREST_BEARING,
+  MINT_EXT_NON_TRANSFERABLE, MINT_EXT_TRANSFER_FEE, NON_TRANSFERABLE_SIZE,
+  TOKEN_METADATA_BASE_SIZE, TRANSFER_FEE_CONFIG_SIZE,
 };
 use pinocchio_token_2022::{instructions::InitializeMint2, state::Mint};
 
-//Initiate Token2022 Mint Account
+//Initiate Token2022 Mint Account with MetadataPointer + TokenMetadata extensions holding the name, symbol and uri, and optionally TransferFeeConfig, NonTransferable for soulbound tokens, or InterestBearingConfig
 pub struct Token2022InitMint<'a> {
   pub payer: &'a AccountView, //signer
   pub mint: &'a AccountView,
-  pub mint_authority: &'a AccountView,
+  pub mint_authority: &'a AccountView, //signer, also the metadata update authority
   pub token_program: &'a AccountView,
   pub freeze_authority_opt: Option<&'a Address>, // or Pubkey
   pub decimals: u8,
   pub token_name: [u8; 10],
   pub token_symbol: [u8; 6],
   pub token_uri: [u8; 32],
+  /// transfer_fee_basis_points, maximum_fee, withdraw_withheld_authority. The mint authority can update the fee
+  pub transfer_fee_opt: Option<(u16, u64, Address)>,
+  /// soulbound: holders cannot transfer the tokens
+  pub non_transferable: bool,
+  /// interest rate in basis points per year, and the rate authority: the config PDA, on which the mint authority needs the FEE permission. Only config admins can then update the rate via Token2022UpdateRate
+  pub interest_bearing_opt: Option<(i16, &'a Address)>,
 }
 impl<'a> Token2022InitMint<'a> {
   pub const DISCRIMINATOR: &'a u8 = &9;
@@ -35,28 +47,31 @@ impl<'a> Token2022InitMint<'a> {
       token_name,
       token_symbol,
       token_uri,
+      transfer_fee_opt,
+      non_transferable,
+      interest_bearing_opt,
     } = self;
     log!("Token2022InitMint process()");
+    let name = trim_padding(&token_name);
+    let symbol = trim_padding(&token_symbol);
+    let uri = trim_padding(&token_uri);
 
-    /// [4 (extension discriminator) + 32 (update_authority) + 32 (metadata)]
-    pub const METADATA_POINTER_SIZE: usize = 4 + 32 + 32;
-    /// [4 (extension discriminator) + 32 (update_authority) + 32 (mint) + 10 (size of name ) + 6 (size of symbol) + 32 (size of uri) + 4 (size of additional_metadata)]
-    pub const METADATA_EXTENSION_BASE_SIZE: usize = 4 + 32 + 32 + 10 + 6 + 32 + 4;
-    /// Padding used so that Mint and Account extensions start at the same index
-    pub const EXTENSIONS_PADDING_AND_OFFSET: usize = 84;
-
-    let extension_size = METADATA_POINTER_SIZE
-      + METADATA_EXTENSION_BASE_SIZE
-      + token_name.len()
-      + token_symbol.len()
-      + token_uri.len();
-    let _total_mint_size = Mint::BASE_LEN + EXTENSIONS_PADDING_AND_OFFSET + extension_size;
+    //the token program grows the mint for TokenMetadata, so only the rent is paid for it up front
+    let mut space = Mint::BASE_LEN + EXTENSIONS_PADDING_AND_OFFSET + METADATA_POINTER_SIZE;
+    if transfer_fee_opt.is_some() {
+      space += TRANSFER_FEE_CONFIG_SIZE;
+    }
+    if non_transferable {
+      space += NON_TRANSFERABLE_SIZE;
+    }
+    if interest_bearing_opt.is_some() {
+      space += INTEREST_BEARING_CONFIG_SIZE;
+    }
+    let total_mint_size = space + TOKEN_METADATA_BASE_SIZE + name.len() + symbol.len() + uri.len();
 
     let rent = Rent::from_account_view(mint)?;
-    let lamports = Rent::try_minimum_balance(&rent, Mint::BASE_LEN)?;
-    let space = Mint::BASE_LEN as u64;
+    let lamports = Rent::try_minimum_balance(&rent, total_mint_size)?;
     log!("lamports: {}, space: {}", lamports, space);
-    //let mint = Keypair::new();
 
     log!("Make Mint Account"); //payer and mint are both keypairs!
     CreateAccount {
@@ -64,51 +79,76 @@ impl<'a> Token2022InitMint<'a> {
       to: mint,
       owner: token_program.address(), //address("TokenXYZ");
       lamports,
-      space,
+      space: space as u64,
     }
     .invoke()?;
     log!("Token2022InitMint 7");
     writable(mint)?;
 
+    log!("Init MetadataPointer to the Mint itself");
+    InitializeMetadataPointer {
+      mint,
+      authority: Some(mint_authority.address()),
+      metadata_address: Some(mint.address()),
+      token_program: token_program.address(),
+    }
+    .invoke()?;
+
+    if let Some((transfer_fee_basis_points, maximum_fee, withdraw_withheld_authority)) =
+      transfer_fee_opt
+    {
+      log!("Init TransferFeeConfig");
+      InitializeTransferFeeConfig {
+        mint,
+        transfer_fee_config_authority: Some(mint_authority.address()),
+        withdraw_withheld_authority: Some(&withdraw_withheld_authority),
+        transfer_fee_basis_points,
+        maximum_fee,
+        token_program: token_program.address(),
+      }
+      .invoke()?;
+    }
+    if non_transferable {
+      log!("Init NonTransferable");
+      InitializeNonTransferableMint {
+        mint,
+        token_program: token_program.address(),
+      }
+      .invoke()?;
+    }
+    if let Some((rate, rate_authority)) = interest_bearing_opt {
+      log!("Init InterestBearingConfig");
+      InitializeInterestBearingMint {
+        mint,
+        rate_authority: Some(rate_authority),
+        rate,
+        token_program: token_program.address(),
+      }
+      .invoke()?;
+    }
+
     log!("Init Mint");
     InitializeMint2 {
-      mint: mint, //Keypair
-      decimals: decimals,
+      mint, //Keypair
+      decimals,
       mint_authority: mint_authority.address(),
       freeze_authority: freeze_authority_opt,
       token_program: token_program.address(),
     }
     .invoke()?;
 
-    //TODO: search "InitializeMetadataPointer solana token 2022" to add metadata: https://solana.com/docs/tokens/extensions/metadata
-    // Initialize MetadataPointer extension pointing to the Mint account
-    /*InitializeMetadataPointer {
-      mint: mint_account,
-      authority: Some(*payer.address()),
-      metadata_address: Some(*mint_account.address()),
-    }.invoke()?;
-
-        // Now initialize that account as a Token2022 Mint
-    InitializeMint2 {
-        mint: mint_account,
-        decimals: args.decimals,
-        mint_authority: mint_authority.address(),
-        freeze_authority: None,
-    }
-    .invoke(TokenProgramVariant::Token2022)?;
-
-     //https://www.helius.dev/blog/pinocchio#how-is-pinocchio-more-performant-than-solana-program
-
-     // Set the metadata within the Mint account
-     InitializeTokenMetadata {
-         metadata: mint,
-         update_authority: payer,
-         mint: mint,
-         mint_authority: payer,
-         name: &name,
-         symbol: &symbol,
-         uri: &uri,
-     }.invoke()?;*/
+    log!("Init TokenMetadata");
+    InitializeTokenMetadata {
+      metadata: mint,
+      update_authority: mint_authority,
+      mint,
+      mint_authority,
+      name,
+      symbol,
+      uri,
+      token_program: token_program.address(),
+    }
+    .invoke()?;
     Ok(())
   }
   pub fn init_if_needed(self) -> ProgramResult {
@@ -127,13 +167,17 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for Token2022InitMint<'a> {
     log!("accounts len: {}, data len: {}", accounts.len(), data.len());
     //accounts len: 5, data len: 1
 
-    let [payer, mint, mint_authority, token_program, freeze_authority_opt1, system_program] =
+    let [payer, mint, mint_authority, token_program, freeze_authority_opt1, system_program, rest @ ..] =
       accounts
     else {
       return Err(ProgramError::NotEnoughAccountKeys);
     };
     check_signer(payer)?;
+    check_signer(mint_authority)?;
     executable(token_program)?;
+    if token_program.address().ne(&pinocchio_token_2022::ID) {
+      return Err(Ee::TokenProgram.into());
+    }
     check_sysprog(system_program)?;
     //check_pda(config_pda)?;
     not_initialized(mint)?;
@@ -146,7 +190,25 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for Token2022InitMint<'a> {
       None
     };
 
-    data_len(data, 49)?; //1+16+32=49
+    //1+16+32=49, then optional extension flags and their arguments
+    if data.len() < 49 {
+      return Err(Ee::InputDataLen.into());
+    }
+    let flags = data.get(49).copied().unwrap_or(0);
+    log!("extension flags: {}", flags);
+    if flags & !(MINT_EXT_TRANSFER_FEE | MINT_EXT_NON_TRANSFERABLE | MINT_EXT_INTEREST_BEARING) != 0
+    {
+      return Err(Ee::MintExtensionFlags.into());
+    }
+    let mut expected_len = if data.len() > 49 { 50 } else { 49 };
+    if flags & MINT_EXT_TRANSFER_FEE != 0 {
+      expected_len += 42; //2 basis points + 8 maximum fee + 32 withdraw withheld authority
+    }
+    if flags & MINT_EXT_INTEREST_BEARING != 0 {
+      expected_len += 2; //rate i16
+    }
+    data_len(data, expected_len)?;
+
     let decimals = data[0];
     log!("decimals: {}", decimals);
     check_decimals_max(decimals, 18)?;
@@ -161,6 +223,45 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for Token2022InitMint<'a> {
     let token_uri = *to32bytes(&data[17..49])?;
     log!("token_uri: {}", &token_uri);
 
+    let mut offset = 50;
+    let mut transfer_fee_opt = None;
+    if flags & MINT_EXT_TRANSFER_FEE != 0 {
+      let basis_points = u16::from_le_bytes([data[offset], data[offset + 1]]);
+      if basis_points > MAX_FEE_BASIS_POINTS {
+        return Err(Ee::TransferFeeBasisPoints.into());
+      }
+      let maximum_fee = parse_u64(&data[offset + 2..offset + 10])?;
+      let withdraw_withheld_authority =
+        Address::new_from_array(*to32bytes(&data[offset + 10..offset + 42])?);
+      log!(
+        "basis_points: {}, maximum_fee: {}",
+        basis_points,
+        maximum_fee
+      );
+      transfer_fee_opt = Some((basis_points, maximum_fee, withdraw_withheld_authority));
+      offset += 42;
+    }
+    let mut interest_bearing_opt = None;
+    if flags & MINT_EXT_INTEREST_BEARING != 0 {
+      let rate = i16::from_le_bytes([data[offset], data[offset + 1]]);
+      log!("interest rate: {}", rate);
+      let [config_pda, admin_pda, extra @ ..] = rest else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+      };
+      if !extra.is_empty() {
+        return Err(Ee::UnexpectedAccounts.into());
+      }
+      check_pda(config_pda)?;
+      let config: &mut Config = Config::from_account_view(config_pda)?;
+      let permissions = Admin::signer_permissions(config_pda, config, mint_authority, admin_pda)?;
+      if permissions & Admin::FEE == 0 {
+        return Err(Ee::AdminPermission.into());
+      }
+      interest_bearing_opt = Some((rate, config_pda.address()));
+    } else if !rest.is_empty() {
+      return Err(Ee::UnexpectedAccounts.into());
+    }
+
     Ok(Self {
       payer,
       mint,
@@ -171,6 +272,9 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for Token2022InitMint<'a> {
       token_name,
       token_symbol,
       token_uri,
+      transfer_fee_opt,
+      non_transferable: flags & MINT_EXT_NON_TRANSFERABLE != 0,
+      interest_bearing_opt,
     })
   }
 }
@@ -0,0 +1,101 @@
+use core::convert::TryFrom;
+use pinocchio::{
+  error::ProgramError,
+  sysvars::{rent::Rent, Sysvar},
+  AccountView, ProgramResult,
+};
+use pinocchio_log::log;
+use pinocchio_system::instructions::Transfer as SystemTransfer;
+
+use crate::{
+  check_mint22a, check_sysprog, data_len, executable, instructions::check_signer, to32bytes,
+  trim_padding, writable, Ee, UpdateTokenMetadataField, METADATA_FIELD_KEY,
+};
+
+/// Token2022 Update Metadata: the update authority sets the name, symbol or uri, or adds/overwrites a key/value pair in the mint TokenMetadata
+pub struct Token2022UpdateMetadata<'a> {
+  pub update_authority: &'a AccountView, //signer
+  pub mint: &'a AccountView,
+  pub token_program: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub field: u8,
+  pub key: &'a [u8],
+  pub value: &'a [u8],
+}
+impl<'a> Token2022UpdateMetadata<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &44;
+
+  pub fn process(self) -> ProgramResult {
+    let Token2022UpdateMetadata {
+      update_authority,
+      mint,
+      token_program,
+      system_program: _,
+      field,
+      key,
+      value,
+    } = self;
+    log!("Token2022UpdateMetadata process()");
+    //the token program reallocs the mint. Cover the largest growth: a new key/value pair
+    let max_len = mint.data_len() + 8 + key.len() + value.len();
+    let min_lamports = Rent::get()?.try_minimum_balance(max_len)?;
+    let lamports = mint.lamports();
+    if lamports < min_lamports {
+      log!("top up rent: {}", min_lamports - lamports);
+      SystemTransfer {
+        from: update_authority,
+        to: mint,
+        lamports: min_lamports - lamports,
+      }
+      .invoke()?;
+    }
+
+    UpdateTokenMetadataField {
+      metadata: mint,
+      update_authority,
+      field,
+      key,
+      value,
+      token_program: token_program.address(),
+    }
+    .invoke()?;
+    log!("field: {}, mint len: {}", field, mint.data_len());
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for Token2022UpdateMetadata<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("Token2022UpdateMetadata try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+
+    let [update_authority, mint, token_program, system_program] = accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(update_authority)?;
+    writable(update_authority)?;
+    writable(mint)?;
+    executable(token_program)?;
+    check_sysprog(system_program)?;
+    check_mint22a(mint, token_program)?;
+
+    data_len(data, 65)?; //1+32+32=65
+    let field = data[0];
+    let key = trim_padding(to32bytes(&data[1..33])?);
+    let value = trim_padding(to32bytes(&data[33..65])?);
+    if field > METADATA_FIELD_KEY || (field == METADATA_FIELD_KEY && key.is_empty()) {
+      return Err(Ee::MetadataField.into());
+    }
+    Ok(Self {
+      update_authority,
+      mint,
+      token_program,
+      system_program,
+      field,
+      key,
+      value,
+    })
+  }
+}
@@ -0,0 +1,110 @@
+use core::convert::TryFrom;
+use pinocchio::{
+  cpi::{Seed, Signer},
+  error::ProgramError,
+  AccountView, ProgramResult,
+};
+use pinocchio_log::log;
+
+use crate::{
+  check_mint22a, check_pda, executable, get_time, instructions::check_signer, rate_authority,
+  versioned_payload, writable, Admin, Config, Ee, UpdateInterestRate,
+};
+
+/// Token2022 Update Rate: admins with the FEE permission set the interest rate of an InterestBearingConfig mint whose rate authority is the config PDA
+pub struct Token2022UpdateRate<'a> {
+  pub signer: &'a AccountView,
+  pub config_pda: &'a AccountView,
+  pub admin_pda: &'a AccountView,
+  pub mint: &'a AccountView,
+  pub token_program: &'a AccountView,
+  pub rate: i16,
+  pub config: &'a mut Config,
+}
+impl<'a> Token2022UpdateRate<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &47;
+
+  pub fn process(self) -> ProgramResult {
+    let Token2022UpdateRate {
+      signer: _,
+      config_pda,
+      admin_pda: _,
+      mint,
+      token_program,
+      rate,
+      config,
+    } = self;
+    log!("Token2022UpdateRate process()");
+    update_rate(config_pda, config, mint, token_program, rate)?;
+    config.set_updated_at(get_time()?);
+    Ok(())
+  }
+}
+/// set the interest rate of an InterestBearingConfig mint whose rate authority is the config PDA, signing as the config PDA. Also applied by the SET_RATE Action
+pub fn update_rate(
+  config_pda: &AccountView,
+  config: &Config,
+  mint: &AccountView,
+  token_program: &AccountView,
+  rate: i16,
+) -> ProgramResult {
+  writable(mint)?;
+  executable(token_program)?;
+  check_mint22a(mint, token_program)?;
+  if rate_authority(mint)?.ne(config_pda.address()) {
+    return Ee::RateAuthority.e();
+  }
+  let seed_owner = *config.seed_owner();
+  let config_bump = config.bump();
+  let signer_seeds = [
+    Seed::from(Config::SEED),
+    Seed::from(seed_owner.as_ref()),
+    Seed::from(core::slice::from_ref(&config_bump)),
+  ];
+  let seed_signer = Signer::from(&signer_seeds);
+
+  UpdateInterestRate {
+    mint,
+    rate_authority: config_pda,
+    rate,
+    token_program: token_program.address(),
+  }
+  .invoke_signed(&[seed_signer])
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for Token2022UpdateRate<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("Token2022UpdateRate try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+    let payload = versioned_payload(data, 2)?;
+    let rate = i16::from_le_bytes([payload[0], payload[1]]);
+    log!("rate: {}", rate);
+
+    let [signer, config_pda, admin_pda, mint, token_program] = accounts else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(signer)?;
+    writable(config_pda)?;
+    check_pda(config_pda)?;
+
+    config_pda.check_borrow_mut()?;
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    //under a timelock, rates change through the SET_RATE Action
+    config.check_no_timelock()?;
+    let permissions = Admin::signer_permissions(config_pda, config, signer, admin_pda)?;
+    if permissions & Admin::FEE == 0 {
+      return Err(Ee::AdminPermission.into());
+    }
+    Ok(Self {
+      signer,
+      config_pda,
+      admin_pda,
+      mint,
+      token_program,
+      rate,
+      config,
+    })
+  }
+}
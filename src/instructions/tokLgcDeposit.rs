@@ -9,18 +9,23 @@ use pinocchio_log::log;
 use pinocchio_system::instructions::CreateAccount;
 
 use crate::{
-  ata_balc, check_ata, check_atoken_gpvbd, check_decimals, check_mint0a, check_pda, check_sysprog,
-  data_len, derive_pda1, executable, instructions::check_signer, none_zero_u64, parse_u64,
-  rent_exempt_mint, rent_exempt_tokacct, writable, Config, Ee, PROG_ADDR, VAULT_SEED, VAULT_SIZE,
+  ata_balc_interface, check_ata_interface, check_atoken_gpvbd, check_decimals_interface,
+  check_mint_interface_prog, check_pda, check_sysprog, data_len, derive_pda1, executable, get_time,
+  init_user_pda, instructions::check_signer, load_mint_reg, none_zero_u64, parse_u64,
+  rent_exempt_mint_interface, rent_exempt_tokacct_interface, writable, Config, Ee, MintReg,
+  TransferCheckedInterface, PROG_ADDR, VAULT_SEED, VAULT_SIZE,
 };
 
-/// TokLgc: Users to Deposit Tokens
+/// TokLgc: Users to Deposit Tokens. Works with mints of either TokenLgc or Token2022
 pub struct TokLgcDeposit<'a> {
   pub user: &'a AccountView, //signer
   pub from_ata: &'a AccountView,
   pub to_ata: &'a AccountView,
   pub to_wallet: &'a AccountView,
   pub mint: &'a AccountView,
+  pub config_pda: &'a AccountView,
+  pub mint_reg: &'a AccountView,
+  pub user_pda: &'a AccountView,
   pub token_program: &'a AccountView,
   pub system_program: &'a AccountView,
   pub atoken_program: &'a AccountView,
@@ -37,6 +42,9 @@ impl<'a> TokLgcDeposit<'a> {
       to_ata,
       to_wallet,
       mint,
+      config_pda,
+      mint_reg,
+      user_pda,
       token_program,
       system_program,
       atoken_program: _,
@@ -44,7 +52,6 @@ impl<'a> TokLgcDeposit<'a> {
       amount,
     } = self;
     log!("TokLgcDeposit process()");
-
     if to_wallet.lamports() == 0 {
       log!("TokLgcDeposit 6: make to_wallet");
       let (expected_vault_pda, bump) = derive_pda1(user.address(), VAULT_SEED)?;
@@ -88,21 +95,34 @@ impl<'a> TokLgcDeposit<'a> {
       //Please upgrade to SPL Token 2022 for immutable owner support
     } else {
       log!("to_ata has data");
-      check_ata(to_ata, to_wallet, mint)?;
+      check_ata_interface(to_ata, to_wallet, mint)?;
     }
     writable(to_ata)?;
-    rent_exempt_tokacct(to_ata)?;
+    rent_exempt_tokacct_interface(to_ata)?;
     log!("ToATA is found/verified");
 
-    pinocchio_token::instructions::TransferChecked {
+    let fee = TransferCheckedInterface {
       from: from_ata,
       mint,
       to: to_ata,
       authority: user,
       amount,
       decimals,
+      token_program: token_program.address(),
     }
     .invoke()?;
+    //book what the vault received after any Token2022 transfer fee
+    let amount = amount - fee;
+
+    log!("Update Mint Registry PDA");
+    load_mint_reg(config_pda.address(), mint, mint_reg)?.add_deposit(amount)?;
+
+    log!("Update User PDA");
+    let user_acct = init_user_pda(user, user, mint, config_pda.address(), user_pda)?;
+    let was_open = user_acct.is_open();
+    user_acct.add_deposit(amount)?;
+    user_acct.set_updated_at(get_time()?);
+    Config::from_account_view(config_pda)?.update_open_positions(was_open, user_acct.is_open())?;
     Ok(())
   }
 }
@@ -114,7 +134,7 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for TokLgcDeposit<'a> {
     let (data, accounts) = value;
     log!("accounts len: {}, data len: {}", accounts.len(), data.len());
 
-    let [user, from_ata, to_ata, to_wallet, mint, config_pda, token_program, system_program, atoken_program] =
+    let [user, from_ata, to_ata, to_wallet, mint, config_pda, mint_reg, user_pda, token_program, system_program, atoken_program] =
       accounts
     else {
       return Err(ProgramError::NotEnoughAccountKeys);
@@ -125,7 +145,10 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for TokLgcDeposit<'a> {
     check_atoken_gpvbd(atoken_program)?;
 
     writable(from_ata)?;
-    check_ata(from_ata, user, mint)?;
+    check_ata_interface(from_ata, user, mint)?;
+    writable(mint_reg)?;
+    writable(user_pda)?;
+    writable(config_pda)?;
 
     //1+8: u8 takes 1, u64 takes 8 bytes
     data_len(data, 9)?;
@@ -134,19 +157,24 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for TokLgcDeposit<'a> {
     log!("decimals: {}, amount: {}", decimals, amount);
 
     none_zero_u64(amount)?;
-    ata_balc(from_ata, amount)?;
+    ata_balc_interface(from_ata, amount)?;
 
     log!("TokLgcDeposit try_from 9");
     config_pda.check_borrow_mut()?;
-    let config: &mut Config = Config::from_account_view(&config_pda)?;
-
-    if !config.mints().contains(&mint.address()) {
-      return Err(Ee::MintNotAccepted.into());
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    config.check_not_paused()?;
+    config.check_mint_extensions(mint)?;
+
+    mint_reg.check_borrow_mut()?;
+    let reg: &mut MintReg = load_mint_reg(config_pda.address(), mint, mint_reg)?;
+    reg.check_enabled()?;
+    if reg.decimals() != decimals {
+      return Err(Ee::DecimalsValue.into());
     }
     log!("TokLgcDeposit try_from 10");
-    rent_exempt_mint(mint)?;
-    check_decimals(mint, decimals)?;
-    check_mint0a(mint, token_program)?;
+    rent_exempt_mint_interface(mint)?;
+    check_decimals_interface(mint, decimals)?;
+    check_mint_interface_prog(mint, token_program)?;
 
     Ok(Self {
       user,
@@ -154,6 +182,9 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for TokLgcDeposit<'a> {
       to_ata,
       to_wallet,
       mint,
+      config_pda,
+      mint_reg,
+      user_pda,
       token_program,
       system_program,
       atoken_program,
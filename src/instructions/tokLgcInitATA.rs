@@ -34,9 +34,9 @@ impl<'a> TokenLgcInitAta<'a> {
       funding_account: payer, // Keypair
       account: ata,
       wallet: to_wallet,
-      mint: mint,
-      system_program: system_program,
-      token_program: token_program,
+      mint,
+      system_program,
+      token_program,
     }
     .invoke()?;
     Ok(())
@@ -3,12 +3,14 @@ use pinocchio::{error::ProgramError, AccountView, ProgramResult};
 use pinocchio_log::log;
 
 use crate::{
-  ata_balc, check_ata, check_atoken_gpvbd, check_decimals, check_mint0a, check_sysprog,
-  check_vault, data_len, executable, instructions::check_signer, none_zero_u64, parse_u64,
-  rent_exempt_mint, rent_exempt_tokacct, writable, Config, Ee,
+  ata_balc_interface, check_ata_interface, check_atoken_gpvbd, check_decimals_interface,
+  check_mint_interface_prog, check_sysprog, check_vault, data_len, executable, get_time,
+  init_user_pda, instructions::check_signer, load_mint_reg, none_zero_u64, parse_u64,
+  rent_exempt_mint_interface, rent_exempt_tokacct_interface, writable, Config, Ee, MintReg,
+  TransferCheckedInterface,
 };
 
-/// TokLgc: Users to Pay Tokens to VaultAdmin
+/// TokLgc: Users to Pay Tokens to VaultAdmin. Works with mints of either TokenLgc or Token2022
 pub struct TokLgcPay<'a> {
   pub user: &'a AccountView, //signer
   pub user_ata: &'a AccountView,
@@ -16,6 +18,8 @@ pub struct TokLgcPay<'a> {
   pub vault: &'a AccountView,
   pub mint: &'a AccountView,
   pub config_pda: &'a AccountView,
+  pub mint_reg: &'a AccountView,
+  pub user_pda: &'a AccountView,
   pub token_program: &'a AccountView,
   pub system_program: &'a AccountView,
   pub atoken_program: &'a AccountView,
@@ -32,7 +36,9 @@ impl<'a> TokLgcPay<'a> {
       vault_ata,
       vault,
       mint,
-      config_pda: _,
+      config_pda,
+      mint_reg: _,
+      user_pda,
       token_program,
       system_program,
       atoken_program: _,
@@ -55,21 +61,35 @@ impl<'a> TokLgcPay<'a> {
       //Please upgrade to SPL Token 2022 for immutable owner support
     } else {
       log!("vault_ata has data");
-      check_ata(vault_ata, vault, mint)?;
+      check_ata_interface(vault_ata, vault, mint)?;
     }
     writable(vault_ata)?;
-    rent_exempt_tokacct(vault_ata)?;
+    rent_exempt_tokacct_interface(vault_ata)?;
     log!("Vault ATA is found/verified");
 
-    pinocchio_token::instructions::TransferChecked {
+    let fee = TransferCheckedInterface {
       from: user_ata,
       mint,
       to: vault_ata,
       authority: user,
       amount,
       decimals,
+      token_program: token_program.address(),
     }
     .invoke()?;
+    //book what the vault received after any Token2022 transfer fee
+    let amount = amount - fee;
+
+    log!("Update Config token_balance");
+    let config = Config::from_account_view(config_pda)?;
+    config.add_token_balance(amount)?;
+
+    log!("Update User PDA");
+    let user_acct = init_user_pda(user, user, mint, config_pda.address(), user_pda)?;
+    let was_open = user_acct.is_open();
+    user_acct.add_paid(amount)?;
+    user_acct.set_updated_at(get_time()?);
+    config.update_open_positions(was_open, user_acct.is_open())?;
     Ok(())
   }
 }
@@ -81,7 +101,7 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for TokLgcPay<'a> {
     let (data, accounts) = value;
     log!("accounts len: {}, data len: {}", accounts.len(), data.len());
 
-    let [user, user_ata, vault_ata, vault, mint, config_pda, token_program, system_program, atoken_program] =
+    let [user, user_ata, vault_ata, vault, mint, config_pda, mint_reg, user_pda, token_program, system_program, atoken_program] =
       accounts
     else {
       return Err(ProgramError::NotEnoughAccountKeys);
@@ -92,7 +112,9 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for TokLgcPay<'a> {
     check_atoken_gpvbd(atoken_program)?;
 
     writable(user_ata)?;
-    check_ata(user_ata, user, mint)?;
+    check_ata_interface(user_ata, user, mint)?;
+    writable(user_pda)?;
+    writable(config_pda)?;
     log!("TokLgcPay try_from 5");
 
     //1+8: u8 takes 1, u64 takes 8 bytes
@@ -102,21 +124,25 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for TokLgcPay<'a> {
     log!("decimals: {}, amount: {}", decimals, amount);
 
     none_zero_u64(amount)?;
-    ata_balc(user_ata, amount)?;
+    ata_balc_interface(user_ata, amount)?;
 
     log!("TokLgcPay try_from 9");
     config_pda.check_borrow_mut()?;
-    let config: &mut Config = Config::from_account_view(&config_pda)?;
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    config.check_not_paused()?;
 
-    if !config.mints().contains(&mint.address()) {
-      return Err(Ee::MintNotAccepted.into());
+    mint_reg.check_borrow_mut()?;
+    let reg: &mut MintReg = load_mint_reg(config_pda.address(), mint, mint_reg)?;
+    reg.check_enabled()?;
+    if reg.decimals() != decimals {
+      return Err(Ee::DecimalsValue.into());
     }
     check_vault(vault, config.vault())?;
 
     log!("LgcPay try_from 10");
-    rent_exempt_mint(mint)?;
-    check_decimals(mint, decimals)?;
-    check_mint0a(mint, token_program)?;
+    rent_exempt_mint_interface(mint)?;
+    check_decimals_interface(mint, decimals)?;
+    check_mint_interface_prog(mint, token_program)?;
 
     Ok(Self {
       user,
@@ -125,6 +151,8 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for TokLgcPay<'a> {
       vault,
       mint,
       config_pda,
+      mint_reg,
+      user_pda,
       token_program,
       system_program,
       atoken_program,
@@ -7,19 +7,24 @@ use pinocchio::{
 use pinocchio_log::log;
 
 use crate::{
-  ata_balc, check_ata, check_decimals, check_mint0a, check_sysprog, check_vault, data_len,
-  executable, instructions::check_signer, none_zero_u64, parse_u64, rent_exempt_mint,
-  rent_exempt_tokacct, writable, Config, Ee, VAULT_SEED,
+  ata_balc_interface, check_ata_interface, check_decimals_interface, check_mint_interface_prog,
+  check_sysprog, check_vault, data_len, executable, get_time, instructions::check_signer,
+  load_mint_reg, load_user_pda, none_zero_u64, parse_u64, rent_exempt_mint_interface,
+  rent_exempt_tokacct_interface, writable, Config, Ee, MintReg, TransferCheckedInterface,
+  VAULT_SEED,
 };
 
-/// TokLgc: Users to Redeem Tokens from VaultPDA
+/// TokLgc: Users to Redeem Tokens from VaultPDA, bounded by the redeemable amount in their User PDA. Works with mints of either TokenLgc or Token2022
 pub struct TokLgcRedeem<'a> {
   pub user: &'a AccountView, //signer
   pub from_ata: &'a AccountView,
   pub to_ata: &'a AccountView,
   pub vault: &'a AccountView,
-  pub prog_owner: &'a Address,
+  pub config_pda: &'a AccountView,
+  pub seed_owner: &'a Address,
   pub mint: &'a AccountView,
+  pub mint_reg: &'a AccountView,
+  pub user_pda: &'a AccountView,
   pub token_program: &'a AccountView,
   pub system_program: &'a AccountView,
   pub atoken_program: &'a AccountView,
@@ -36,8 +41,11 @@ impl<'a> TokLgcRedeem<'a> {
       from_ata,
       to_ata,
       vault,
-      prog_owner,
+      config_pda,
+      seed_owner,
       mint,
+      mint_reg: _,
+      user_pda,
       token_program,
       system_program,
       atoken_program: _,
@@ -46,6 +54,21 @@ impl<'a> TokLgcRedeem<'a> {
       amount,
     } = self;
     log!("TokLgcRedeem process()");
+    log!("Check redeemable in User PDA");
+    if user_pda.is_data_empty() {
+      return Ee::UserInsuffRedeemable.e();
+    }
+    let user_acct = load_user_pda(user, mint, user_pda)?;
+    user_acct.check_config(config_pda.address())?;
+    log!("redeemable: {}", user_acct.redeemable());
+    let was_open = user_acct.is_open();
+    user_acct.sub_redeemed(amount)?;
+    user_acct.set_updated_at(get_time()?);
+
+    log!("Update Config token_balance");
+    let config = Config::from_account_view(config_pda)?;
+    config.sub_token_balance(amount);
+    config.update_open_positions(was_open, user_acct.is_open())?;
 
     if to_ata.is_data_empty() {
       log!("Make to_ata");
@@ -61,28 +84,29 @@ impl<'a> TokLgcRedeem<'a> {
       //Please upgrade to SPL Token 2022 for immutable owner support
     } else {
       log!("to_ata has data");
-      check_ata(to_ata, user, mint)?;
+      check_ata_interface(to_ata, user, mint)?;
     }
     writable(to_ata)?;
-    rent_exempt_tokacct(to_ata)?;
+    rent_exempt_tokacct_interface(to_ata)?;
     log!("ToATA is found/verified");
 
     let signer_seeds = [
       Seed::from(VAULT_SEED),
-      Seed::from(prog_owner.as_ref()),
+      Seed::from(seed_owner.as_ref()),
       Seed::from(core::slice::from_ref(&vault_bump)),
     ];
     log!("TokLgcRedeem 7c");
     let seed_signer = Signer::from(&signer_seeds);
 
     log!("TokLgcRedeem 8 Transfer Tokens");
-    pinocchio_token::instructions::TransferChecked {
+    TransferCheckedInterface {
       from: from_ata,
       mint,
       to: to_ata,
       authority: vault,
       amount,
       decimals,
+      token_program: token_program.address(),
     }
     .invoke_signed(&[seed_signer])?;
     Ok(())
@@ -96,7 +120,7 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for TokLgcRedeem<'a> {
     let (data, accounts) = value;
     log!("accounts len: {}, data len: {}", accounts.len(), data.len());
 
-    let [user, from_ata, to_ata, vault, config_pda, mint, token_program, system_program, atoken_program] =
+    let [user, from_ata, to_ata, vault, config_pda, mint, mint_reg, user_pda, token_program, system_program, atoken_program] =
       accounts
     else {
       return Err(ProgramError::NotEnoughAccountKeys);
@@ -106,7 +130,9 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for TokLgcRedeem<'a> {
     check_sysprog(system_program)?;
 
     writable(from_ata)?;
-    check_ata(from_ata, vault, mint)?;
+    check_ata_interface(from_ata, vault, mint)?;
+    writable(config_pda)?;
+    writable(user_pda)?;
 
     //1+8: u8 takes 1, u64 takes 8 bytes
     data_len(data, 9)?;
@@ -115,34 +141,36 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for TokLgcRedeem<'a> {
     log!("decimals: {}, amount: {}", decimals, amount);
 
     none_zero_u64(amount)?;
-    ata_balc(from_ata, amount)?;
+    ata_balc_interface(from_ata, amount)?;
 
-    log!("TokLgcPay try_from 9");
+    log!("TokLgcRedeem try_from 9");
     config_pda.check_borrow_mut()?;
-    let config: &mut Config = Config::from_account_view(&config_pda)?;
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    config.check_not_paused()?;
 
-    if !config.mints().contains(&mint.address()) {
-      return Err(Ee::MintNotAccepted.into());
+    mint_reg.check_borrow_mut()?;
+    let reg: &mut MintReg = load_mint_reg(config_pda.address(), mint, mint_reg)?;
+    reg.check_enabled()?;
+    if reg.decimals() != decimals {
+      return Err(Ee::DecimalsValue.into());
     }
     check_vault(vault, config.vault())?;
-    /*let (expected_vault, vault_bump) = derive_pda1(config.prog_owner(), VAULT_SEED)?;
-    log!("TokLgcPay try_from 9");
-    if vault.address() != &expected_vault {
-      return Err(Ee::VaultPDA.into());
-    }*/
 
     log!("TokLgcRedeem try_from 12");
-    rent_exempt_mint(mint)?;
-    check_decimals(mint, decimals)?;
-    check_mint0a(mint, token_program)?;
+    rent_exempt_mint_interface(mint)?;
+    check_decimals_interface(mint, decimals)?;
+    check_mint_interface_prog(mint, token_program)?;
 
     Ok(Self {
       user,
       from_ata,
       to_ata,
       vault,
-      prog_owner: config.prog_owner(),
+      config_pda,
+      seed_owner: config.seed_owner(),
       mint,
+      mint_reg,
+      user_pda,
       token_program,
       system_program,
       atoken_program,
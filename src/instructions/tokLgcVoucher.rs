@@ -0,0 +1,94 @@
+use core::convert::TryFrom;
+use pinocchio::{error::ProgramError, AccountView, ProgramResult};
+use pinocchio_log::log;
+
+use crate::{
+  check_pda, check_sysprog, get_time, init_user_pda, instructions::check_signer, load_mint_reg,
+  none_zero_u64, parse_u64, versioned_payload, writable, Admin, Config, Ee,
+};
+
+/// TokLgc: Admins to grant a user a voucher to redeem tokens of a mint from VaultPDA. The User PDA must already exist unless the signer grants itself, so nobody can make a User PDA for someone else
+pub struct TokLgcVoucher<'a> {
+  pub signer: &'a AccountView, //signer
+  pub config_pda: &'a AccountView,
+  pub admin_pda: &'a AccountView,
+  pub user: &'a AccountView,
+  pub mint: &'a AccountView,
+  pub mint_reg: &'a AccountView,
+  pub user_pda: &'a AccountView,
+  pub system_program: &'a AccountView,
+  pub amount: u64,
+}
+impl<'a> TokLgcVoucher<'a> {
+  pub const DISCRIMINATOR: &'a u8 = &23;
+
+  pub fn process(self) -> ProgramResult {
+    let TokLgcVoucher {
+      signer,
+      config_pda,
+      admin_pda: _,
+      user,
+      mint,
+      mint_reg: _,
+      user_pda,
+      system_program: _,
+      amount,
+    } = self;
+    log!("TokLgcVoucher process()");
+    if user_pda.is_data_empty() && user.address().ne(signer.address()) {
+      return Ee::OnlyUser.e();
+    }
+    let user_acct = init_user_pda(signer, user, mint, config_pda.address(), user_pda)?;
+    let was_open = user_acct.is_open();
+    user_acct.add_redeemable(amount)?;
+    user_acct.set_updated_at(get_time()?);
+    log!("redeemable: {}", user_acct.redeemable());
+    Config::from_account_view(config_pda)?.update_open_positions(was_open, user_acct.is_open())?;
+    Ok(())
+  }
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for TokLgcVoucher<'a> {
+  type Error = ProgramError;
+
+  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
+    log!("TokLgcVoucher try_from");
+    let (data, accounts) = value;
+    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
+
+    let [signer, config_pda, admin_pda, user, mint, mint_reg, user_pda, system_program] = accounts
+    else {
+      return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_signer(signer)?;
+    check_sysprog(system_program)?;
+    writable(user_pda)?;
+    writable(config_pda)?;
+    check_pda(config_pda)?;
+
+    let payload = versioned_payload(data, 8)?;
+    let amount = parse_u64(payload)?;
+    log!("amount: {}", amount);
+    none_zero_u64(amount)?;
+
+    config_pda.check_borrow_mut()?;
+    let config: &mut Config = Config::from_account_view(config_pda)?;
+    config.check_not_paused()?;
+
+    let permissions = Admin::signer_permissions(config_pda, config, signer, admin_pda)?;
+    if permissions & Admin::TREASURY == 0 {
+      return Err(Ee::AdminPermission.into());
+    }
+    load_mint_reg(config_pda.address(), mint, mint_reg)?;
+    Ok(Self {
+      signer,
+      config_pda,
+      admin_pda,
+      user,
+      mint,
+      mint_reg,
+      user_pda,
+      system_program,
+      amount,
+    })
+  }
+}
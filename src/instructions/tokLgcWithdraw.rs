@@ -7,18 +7,24 @@ use pinocchio::{
 use pinocchio_log::log;
 
 use crate::{
-  ata_balc, check_ata, check_decimals, check_mint0a, check_sysprog, data_len, derive_pda1,
-  executable, instructions::check_signer, none_zero_u64, parse_u64, rent_exempt_mint,
-  rent_exempt_tokacct, writable, Ee, VAULT_SEED,
+  ata_balc_interface, check_ata_interface, check_decimals_interface, check_mint_interface_prog,
+  check_pda, check_sysprog, data_len, derive_pda1, derive_pda3, executable, get_time,
+  instructions::check_signer, load_mint_reg, load_user_pda, none_zero_u64, parse_u64,
+  rent_exempt_mint_interface, rent_exempt_tokacct_interface, writable, Config, Ee,
+  TransferCheckedInterface, User, VAULT_SEED,
 };
 
-/// TokLgc: Users to Withdraw Tokens
+/// TokLgc: Users to Withdraw Tokens booked on a config. Works with mints of either TokenLgc or Token2022
+/// Deposits made before the ledger have no User PDA: pass the empty User PDA of that config and the withdrawal skips the ledger
 pub struct TokLgcWithdraw<'a> {
   pub user: &'a AccountView, //signer
   pub from_ata: &'a AccountView,
   pub to_ata: &'a AccountView,
   pub vault: &'a AccountView,
   pub mint: &'a AccountView,
+  pub config_pda: &'a AccountView,
+  pub mint_reg: &'a AccountView,
+  pub user_pda: &'a AccountView,
   pub token_program: &'a AccountView,
   pub system_program: &'a AccountView,
   pub atoken_program: &'a AccountView,
@@ -36,6 +42,9 @@ impl<'a> TokLgcWithdraw<'a> {
       to_ata,
       vault,
       mint,
+      config_pda,
+      mint_reg,
+      user_pda,
       token_program,
       system_program,
       atoken_program: _,
@@ -44,6 +53,30 @@ impl<'a> TokLgcWithdraw<'a> {
       amount,
     } = self;
     log!("TokLgcWithdraw process()");
+    if user_pda.is_data_empty() {
+      //deposits made before the ledger existed have no User PDA to book against
+      let (expected_user_pda, _bump) = derive_pda3(
+        config_pda.address(),
+        user.address(),
+        mint.address(),
+        User::SEED,
+      )?;
+      if expected_user_pda.ne(user_pda.address()) {
+        return Ee::UserPDA.e();
+      }
+      log!("Legacy withdrawal without a ledger entry");
+    } else {
+      log!("Update User PDA");
+      let user_acct = load_user_pda(user, mint, user_pda)?;
+      user_acct.check_config(config_pda.address())?;
+      let was_open = user_acct.is_open();
+      user_acct.sub_withdrawal(amount)?;
+      user_acct.set_updated_at(get_time()?);
+      log!("Update the Mint Registry PDA of the config booking the deposits");
+      load_mint_reg(config_pda.address(), mint, mint_reg)?.sub_withdrawal(amount)?;
+      Config::from_account_view(config_pda)?
+        .update_open_positions(was_open, user_acct.is_open())?;
+    }
 
     if to_ata.is_data_empty() {
       log!("Make to_ata");
@@ -59,10 +92,10 @@ impl<'a> TokLgcWithdraw<'a> {
       //Please upgrade to SPL Token 2022 for immutable owner support
     } else {
       log!("to_ata has data");
-      check_ata(to_ata, user, mint)?;
+      check_ata_interface(to_ata, user, mint)?;
     }
     writable(to_ata)?;
-    rent_exempt_tokacct(to_ata)?;
+    rent_exempt_tokacct_interface(to_ata)?;
     log!("ToATA is found/verified");
 
     let signer_seeds = [
@@ -73,13 +106,14 @@ impl<'a> TokLgcWithdraw<'a> {
     let seed_signer = Signer::from(&signer_seeds);
 
     log!("Transfer Tokens");
-    pinocchio_token::instructions::TransferChecked {
+    TransferCheckedInterface {
       from: from_ata,
       mint,
       to: to_ata,
       authority: vault,
       amount,
       decimals,
+      token_program: token_program.address(),
     }
     .invoke_signed(&[seed_signer])?;
     Ok(())
@@ -93,7 +127,7 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for TokLgcWithdraw<'a> {
     let (data, accounts) = value;
     log!("accounts len: {}, data len: {}", accounts.len(), data.len());
 
-    let [user, from_ata, to_ata, vault, mint, token_program, system_program, atoken_program] =
+    let [user, from_ata, to_ata, vault, mint, config_pda, mint_reg, user_pda, token_program, system_program, atoken_program] =
       accounts
     else {
       return Err(ProgramError::NotEnoughAccountKeys);
@@ -101,9 +135,12 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for TokLgcWithdraw<'a> {
     check_signer(user)?;
     executable(token_program)?;
     check_sysprog(system_program)?;
-    //check_pda(config_pda)?;
+    writable(config_pda)?;
+    check_pda(config_pda)?;
     writable(from_ata)?;
-    check_ata(from_ata, vault, mint)?;
+    check_ata_interface(from_ata, vault, mint)?;
+    writable(mint_reg)?;
+    writable(user_pda)?;
 
     //1+8: u8 takes 1, u64 takes 8 bytes
     data_len(data, 9)?;
@@ -113,7 +150,7 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for TokLgcWithdraw<'a> {
     log!("decimals: {}, amount: {}", decimals, amount);
 
     none_zero_u64(amount)?;
-    ata_balc(from_ata, amount)?;
+    ata_balc_interface(from_ata, amount)?;
 
     let (expected_vault, vault_bump) = derive_pda1(user.address(), VAULT_SEED)?;
     if vault.address() != &expected_vault {
@@ -121,9 +158,9 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for TokLgcWithdraw<'a> {
     }
 
     log!("TokLgcWithdraw try_from 12");
-    rent_exempt_mint(mint)?;
-    check_decimals(mint, decimals)?;
-    check_mint0a(mint, token_program)?;
+    rent_exempt_mint_interface(mint)?;
+    check_decimals_interface(mint, decimals)?;
+    check_mint_interface_prog(mint, token_program)?;
 
     Ok(Self {
       user,
@@ -131,6 +168,9 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for TokLgcWithdraw<'a> {
       to_ata,
       vault,
       mint,
+      config_pda,
+      mint_reg,
+      user_pda,
       token_program,
       system_program,
       atoken_program,
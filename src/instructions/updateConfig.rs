@@ -1,171 +0,0 @@
-use core::convert::TryFrom;
-use pinocchio::{error::ProgramError, AccountView, Address, ProgramResult};
-use pinocchio_log::log;
-
-use crate::{
-  check_pda, data_len, get_time, instructions::check_signer, parse_u32, parse_u64, to32bytes,
-  u8_to_bool, u8_to_status, writable, Config, Ee,
-};
-
-/// Update Config PDA
-pub struct UpdateConfig<'a> {
-  pub signer: &'a AccountView,
-  pub config_pda: &'a AccountView,
-  pub account1: &'a Address,
-  pub account2: &'a Address,
-  pub bools: [bool; 4],
-  pub u8s: [u8; 4],
-  pub u32s: [u32; 4],
-  pub u64s: [u64; 4],
-  pub str_u8array: [u8; 32],
-  pub config: &'a mut Config,
-}
-impl<'a> UpdateConfig<'a> {
-  pub const DISCRIMINATOR: &'a u8 = &13;
-
-  pub fn process(self) -> ProgramResult {
-    log!("UpdateConfig process()");
-    match self.u8s[0] {
-      0 => self.update_status(),
-      1 => self.update_fee(),
-      2 => self.update_admin(),
-      _ => Ee::FunctionSelector.e(),
-    }
-  }
-
-  pub fn add_tokens(self) -> ProgramResult {
-    log!("UpdateConfig add_tokens()");
-    let mutated_state = (self.config.token_balance())
-      .checked_add(self.u64s[1])
-      .ok_or_else(|| ProgramError::ArithmeticOverflow)?;
-    self.config.set_token_balance(mutated_state);
-    Ok(())
-  }
-
-  pub fn update_status(self) -> ProgramResult {
-    log!("UpdateConfig update_status()");
-    self.config.set_status(self.u8s[1]);
-    Ok(())
-  }
-
-  pub fn update_fee(self) -> ProgramResult {
-    log!("UpdateConfig update_fee()");
-    let fee = self.u64s[0];
-    self.config.set_fee(fee)?;
-    let time = get_time()?;
-    self.config.set_updated_at(time);
-
-    self.config.set_status(self.u8s[1]);
-    self.config.set_str_u8array(self.str_u8array);
-
-    self.config.set_admin(&self.account1);
-    //self.add_tokens()?;
-    Ok(())
-  }
-  pub fn only_owner(&self) -> ProgramResult {
-    if self.config.prog_owner() != self.signer.address() {
-      return Ee::OnlyProgOwner.e();
-    }
-    Ok(())
-  }
-  pub fn update_admin(self) -> ProgramResult {
-    self.only_owner()?;
-    self.config.set_admin(self.account1);
-    Ok(())
-  }
-  pub fn update_prog_owner(self) -> ProgramResult {
-    self.only_owner()?;
-    self.config.set_prog_owner(self.account1);
-    Ok(())
-  }
-}
-impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for UpdateConfig<'a> {
-  type Error = ProgramError;
-
-  fn try_from(value: (&'a [u8], &'a [AccountView])) -> Result<Self, Self::Error> {
-    log!("UpdateConfig try_from");
-    let (data, accounts) = value;
-    log!("accounts len: {}, data len: {}", accounts.len(), data.len());
-
-    let [signer, config_pda, account1, account2] = accounts else {
-      return Err(ProgramError::NotEnoughAccountKeys);
-    };
-    log!("check accounts");
-    check_signer(signer)?;
-    writable(config_pda)?;
-    check_pda(config_pda)?;
-
-    /* check minimum data size in try_from!
-    u32size = core::mem::size_of::<u32>();//4
-    u64size = core::mem::size_of::<u64>();//8
-    let expected_size = 8 + u32size * 4 + u64size * 4; // 8 + 4*4+ 8*4 = 56
-    let expected_size: usize = 56;
-    log!("expected_size: {}", expected_size);
-    if data.len() != expected_size {
-      return Ee::InputDataLen.e();
-    }*/
-    let min_data_size1 = 88;
-    data_len(data, min_data_size1)?; //56+32
-
-    log!("parse booleans");
-    let b0 = u8_to_bool(data[0])?;
-    let b1 = u8_to_bool(data[1])?;
-    let b2 = u8_to_bool(data[2])?;
-    let b3 = u8_to_bool(data[3])?;
-    let bools = [b0, b1, b2, b3];
-
-    log!("parse u8 array");
-    let u8s = [data[4], data[5], data[6], data[7]];
-    log!("u8a: {}, u8b: {}", data[4], data[5]);
-    log!("parse u32 array");
-    let u32a = parse_u32(&data[8..12])?;
-    let u32b = parse_u32(&data[12..16])?;
-    let u32c = parse_u32(&data[16..20])?;
-    let u32d = parse_u32(&data[20..24])?;
-    let u32s = [u32a, u32b, u32c, u32d];
-    log!("u32a: {}", u32a);
-
-    log!("parse u64 array");
-    let u64a = parse_u64(&data[24..32])?;
-    let u64b = parse_u64(&data[32..40])?;
-    let u64c = parse_u64(&data[40..48])?;
-    let u64d = parse_u64(&data[48..56])?;
-    let u64s = [u64a, u64b, u64c, u64d];
-    log!("u64a: {}", u64a);
-    //log!("u8s: {}", &u8s);
-    //log!("u32s: {}", &u32s);
-    //log!("u64s: {}", &u64s);
-
-    let str_u8array = *to32bytes(&data[56..min_data_size1])?;
-    log!("str_u8array: {}", &str_u8array);
-
-    //check Status input range
-    let _status = u8_to_status(u8s[1])?;
-
-    config_pda.check_borrow_mut()?;
-    let config: &mut Config = Config::from_account_view(&config_pda)?;
-
-    if config.admin().ne(signer.address()) && config.prog_owner().ne(signer.address()) {
-      return Err(ProgramError::IncorrectAuthority);
-    }
-    // cannot use self in "0 => Self.process(),
-    Ok(Self {
-      signer,
-      config_pda,
-      account1: account1.address(),
-      account2: account2.address(),
-      u8s,
-      bools,
-      u32s,
-      u64s,
-      str_u8array,
-      config,
-    })
-  }
-}
-/*match self.datalen as usize {
-  len if len == size_of::<UpdateConfigStatus>() => self.update_status()?,
-  len if len == size_of::<UpdateConfigFee>() => self.update_fee()?,
-  len if len == size_of::<UpdateConfigAdmin>() => self.update_admin()?,
-  _ => return Err(..),
-} */
@@ -1,5 +1,6 @@
 //use num_derive::FromPrimitive;
 use pinocchio::{
+  cpi::{Seed, Signer},
   error::{ProgramError, ToStr},
   sysvars::{clock::Clock, rent::Rent, Sysvar},
   AccountView, Address, ProgramResult,
@@ -9,15 +10,19 @@ use pinocchio_token::state::{Mint, TokenAccount};
 use pinocchio_token_2022::state::{Mint as Mint22, TokenAccount as TokenAccount22};
 use thiserror::Error;
 
-use crate::{Status, PROG_ADDR};
+use crate::{
+  check_header, write_header, Admin, MintReg, Status, User, ACCOUNT_HEADER_SIZE,
+  POOL_DISCRIMINATOR, POOL_SEED, POOL_VERSION, PROG_ADDR, SHARE_DECIMALS, SHARE_MINT_SEED,
+  SOL_VAULT_DISCRIMINATOR, SOL_VAULT_SIZE, SOL_VAULT_VERSION, VAULT_SEED, VAULT_SIZE,
+};
 
 //TODO: put errors in error.rs ... https://learn.blueshift.gg/en/courses/pinocchio-for-dummies/pinocchio-errors
 #[derive(Clone, Debug, Eq, Error, PartialEq)] //FromPrimitive
 pub enum Ee {
   #[error("MethodDiscriminator")]
   MethodDiscriminator,
-  #[error("Xyz001")]
-  Xyz001,
+  #[error("AdminPermission")]
+  AdminPermission,
   #[error("OnlyProgOwner")]
   OnlyProgOwner,
   #[error("OnlyAdmin")]
@@ -26,8 +31,8 @@ pub enum Ee {
   OnlyUser,
   #[error("OnlyMaker")]
   OnlyMaker,
-  #[error("Xyz006")]
-  Xyz006,
+  #[error("ProgramPaused")]
+  ProgramPaused,
   #[error("NotWritable")]
   NotWritable,
   #[error("NotExecutable")]
@@ -48,12 +53,12 @@ pub enum Ee {
   EscrowMintY,
   #[error("EscrowId")]
   EscrowId,
-  #[error("Xyz017")]
-  Xyz017,
-  #[error("Xyz018")]
-  Xyz018,
-  #[error("Xyz019")]
-  Xyz019,
+  #[error("EscrowNotExpired")]
+  EscrowNotExpired,
+  #[error("EscrowTaker")]
+  EscrowTaker,
+  #[error("OnlyPendingOwner")]
+  OnlyPendingOwner,
   //Bytes for Numerical
   #[error("ZeroU128")]
   ZeroU128,
@@ -87,16 +92,16 @@ pub enum Ee {
   ByteSliceSize10,
   #[error("ByteSliceSize6")]
   ByteSliceSize6,
-  #[error("Xyz035")]
-  Xyz035,
-  #[error("Xyz036")]
-  Xyz036,
-  #[error("Xyz037")]
-  Xyz037,
-  #[error("Xyz038")]
-  Xyz038,
-  #[error("Xyz039")]
-  Xyz039,
+  #[error("PayloadVersion")]
+  PayloadVersion,
+  #[error("AccountDiscriminator")]
+  AccountDiscriminator,
+  #[error("AccountVersion")]
+  AccountVersion,
+  #[error("DestNotWallet")]
+  DestNotWallet,
+  #[error("ConfigHasBalances")]
+  ConfigHasBalances,
   //Inputs
   #[error("InputDataLen")]
   InputDataLen,
@@ -135,10 +140,10 @@ pub enum Ee {
   EscrowPDA,
   #[error("ActionPDA")]
   ActionPDA,
-  #[error("Xyz058")]
-  Xyz058,
-  #[error("Xyz059")]
-  Xyz059,
+  #[error("UserInsuffDeposit")]
+  UserInsuffDeposit,
+  #[error("UserInsuffRedeemable")]
+  UserInsuffRedeemable,
   //PDA Data Length
   #[error("ConfigDataLengh")]
   ConfigDataLengh,
@@ -152,14 +157,14 @@ pub enum Ee {
   EscrowDataLengh,
   #[error("ActionDataLengh")]
   ActionDataLengh,
-  #[error("Xyz066")]
-  Xyz066,
-  #[error("Xyz067")]
-  Xyz067,
-  #[error("Xyz068")]
-  Xyz068,
-  #[error("Xyz069")]
-  Xyz069,
+  #[error("MintRegDataLengh")]
+  MintRegDataLengh,
+  #[error("MintRegPDA")]
+  MintRegPDA,
+  #[error("MintDisabled")]
+  MintDisabled,
+  #[error("MintDepositCap")]
+  MintDepositCap,
   //Mint Account
   #[error("DecimalsValue")]
   DecimalsValue,
@@ -169,10 +174,10 @@ pub enum Ee {
   MintOrMintAuthority,
   #[error("MintOrTokenProgram")]
   MintOrTokenProgram,
-  #[error("Xyz074")]
-  Xyz074,
-  #[error("Xyz075")]
-  Xyz075,
+  #[error("EscrowMaker")]
+  EscrowMaker,
+  #[error("EscrowExpired")]
+  EscrowExpired,
   #[error("EscrowInsuffTokenY")]
   EscrowInsuffTokenY,
   #[error("EscrowInsuffTokenX")]
@@ -196,12 +201,12 @@ pub enum Ee {
   ForeignAta,
   #[error("AtaHasNoData")]
   AtaHasNoData,
-  #[error("Xyz087")]
-  Xyz087,
-  #[error("Xyz088")]
-  Xyz088,
-  #[error("Xyz089")]
-  Xyz089,
+  #[error("MintRegHasDeposits")]
+  MintRegHasDeposits,
+  #[error("DuplicateAccount")]
+  DuplicateAccount,
+  #[error("MultisigThreshold")]
+  MultisigThreshold,
   //Token 2022
   #[error("NoRentExemptMint")]
   NoRentExemptMint,
@@ -222,14 +227,14 @@ pub enum Ee {
   ToWalletNoLamport,
   #[error("ToWalletForeignPDA")]
   ToWalletForeignPDA,
-  #[error("Xyz099")]
-  Xyz099,
+  #[error("MultisigSigner")]
+  MultisigSigner,
   //Math
   //ArithmeticOverflow exists
-  #[error("Xyz100")]
-  Xyz100,
-  #[error("Xyz101")]
-  Xyz101,
+  #[error("FeeBps")]
+  FeeBps,
+  #[error("MultisigApproved")]
+  MultisigApproved,
   #[error("MultiplyOverflow")]
   MultiplyOverflow,
   #[error("DividedByZero")]
@@ -243,6 +248,69 @@ pub enum Ee {
   FunctionSelector,
   #[error("ClockGet")]
   ClockGet,
+  //Timelock
+  #[error("TimelockRequired")]
+  TimelockRequired,
+  #[error("TimelockDelay")]
+  TimelockDelay,
+  #[error("ActionKind")]
+  ActionKind,
+  #[error("ActionNotReady")]
+  ActionNotReady,
+  #[error("ActionPayloadHash")]
+  ActionPayloadHash,
+  //Multisig
+  #[error("MultisigPDA")]
+  MultisigPDA,
+  #[error("MultisigDataLen")]
+  MultisigDataLen,
+  #[error("MultisigNotApproved")]
+  MultisigNotApproved,
+  //Token2022 extensions
+  #[error("MetadataField")]
+  MetadataField,
+  #[error("TransferFeeBasisPoints")]
+  TransferFeeBasisPoints,
+  #[error("TransferFeeConfig")]
+  TransferFeeConfig,
+  #[error("WithdrawWithheldAuthority")]
+  WithdrawWithheldAuthority,
+  #[error("MintExtensionFlags")]
+  MintExtensionFlags,
+  #[error("MintExtensionDenied")]
+  MintExtensionDenied,
+  #[error("ImmutableOwner")]
+  ImmutableOwner,
+  #[error("InterestBearingConfig")]
+  InterestBearingConfig,
+  #[error("RateAuthority")]
+  RateAuthority,
+  #[error("PoolPDA")]
+  PoolPDA,
+  #[error("ShareMintPDA")]
+  ShareMintPDA,
+  #[error("PoolZeroOut")]
+  PoolZeroOut,
+  #[error("PoolSlippage")]
+  PoolSlippage,
+  #[error("UnexpectedAccounts")]
+  UnexpectedAccounts,
+  #[error("AdminConfig")]
+  AdminConfig,
+  #[error("ActionConfig")]
+  ActionConfig,
+  #[error("MintRegConfig")]
+  MintRegConfig,
+  #[error("UserConfig")]
+  UserConfig,
+  #[error("EscrowConfig")]
+  EscrowConfig,
+  #[error("MultisigTxConfig")]
+  MultisigTxConfig,
+  #[error("ConfigHasOpenAccounts")]
+  ConfigHasOpenAccounts,
+  #[error("VaultConfig")]
+  VaultConfig,
   #[error("NotMapped")]
   NotMapped,
   //ProgramResult: AccountBorrowFailed
@@ -263,12 +331,12 @@ impl TryFrom<u32> for Ee {
   fn try_from(error: u32) -> Result<Self, Self::Error> {
     match error {
       0 => Ok(Ee::MethodDiscriminator),
-      1 => Ok(Ee::Xyz001),
+      1 => Ok(Ee::AdminPermission),
       2 => Ok(Ee::OnlyProgOwner),
       3 => Ok(Ee::OnlyAdmin),
       4 => Ok(Ee::OnlyUser),
       5 => Ok(Ee::OnlyMaker),
-      6 => Ok(Ee::Xyz006),
+      6 => Ok(Ee::ProgramPaused),
       7 => Ok(Ee::NotWritable),
       8 => Ok(Ee::NotExecutable),
       9 => Ok(Ee::TokenProgram),
@@ -279,9 +347,9 @@ impl TryFrom<u32> for Ee {
       14 => Ok(Ee::EscrowMintX),
       15 => Ok(Ee::EscrowMintY),
       16 => Ok(Ee::EscrowId),
-      17 => Ok(Ee::Xyz017),
-      18 => Ok(Ee::Xyz018),
-      19 => Ok(Ee::Xyz019),
+      17 => Ok(Ee::EscrowNotExpired),
+      18 => Ok(Ee::EscrowTaker),
+      19 => Ok(Ee::OnlyPendingOwner),
       20 => Ok(Ee::ZeroU128),
       21 => Ok(Ee::ZeroU64),
       22 => Ok(Ee::ZeroU32),
@@ -297,11 +365,11 @@ impl TryFrom<u32> for Ee {
       32 => Ok(Ee::ByteSliceSize32),
       33 => Ok(Ee::ByteSliceSize10),
       34 => Ok(Ee::ByteSliceSize6),
-      35 => Ok(Ee::Xyz035),
-      36 => Ok(Ee::Xyz036),
-      37 => Ok(Ee::Xyz037),
-      38 => Ok(Ee::Xyz038),
-      39 => Ok(Ee::Xyz039),
+      35 => Ok(Ee::PayloadVersion),
+      36 => Ok(Ee::AccountDiscriminator),
+      37 => Ok(Ee::AccountVersion),
+      38 => Ok(Ee::DestNotWallet),
+      39 => Ok(Ee::ConfigHasBalances),
       40 => Ok(Ee::InputDataLen),
       41 => Ok(Ee::InputDataBump),
       42 => Ok(Ee::ByteForBool),
@@ -320,24 +388,24 @@ impl TryFrom<u32> for Ee {
       55 => Ok(Ee::UserPDA),
       56 => Ok(Ee::EscrowPDA),
       57 => Ok(Ee::ActionPDA),
-      58 => Ok(Ee::Xyz058),
-      59 => Ok(Ee::Xyz059),
+      58 => Ok(Ee::UserInsuffDeposit),
+      59 => Ok(Ee::UserInsuffRedeemable),
       60 => Ok(Ee::ConfigDataLengh),
       61 => Ok(Ee::VaultDataLengh),
       62 => Ok(Ee::AdminDataLengh),
       63 => Ok(Ee::UserDataLengh),
       64 => Ok(Ee::EscrowDataLengh),
       65 => Ok(Ee::ActionDataLengh),
-      66 => Ok(Ee::Xyz066),
-      67 => Ok(Ee::Xyz067),
-      68 => Ok(Ee::Xyz068),
-      69 => Ok(Ee::Xyz069),
+      66 => Ok(Ee::MintRegDataLengh),
+      67 => Ok(Ee::MintRegPDA),
+      68 => Ok(Ee::MintDisabled),
+      69 => Ok(Ee::MintDepositCap),
       70 => Ok(Ee::DecimalsValue),
       71 => Ok(Ee::MintDataLen),
       72 => Ok(Ee::MintOrMintAuthority),
       73 => Ok(Ee::MintOrTokenProgram),
-      74 => Ok(Ee::Xyz074),
-      75 => Ok(Ee::Xyz075),
+      74 => Ok(Ee::EscrowMaker),
+      75 => Ok(Ee::EscrowExpired),
       76 => Ok(Ee::EscrowInsuffTokenY),
       77 => Ok(Ee::EscrowInsuffTokenX),
       78 => Ok(Ee::MakerToWithdrawTokenY),
@@ -349,9 +417,9 @@ impl TryFrom<u32> for Ee {
       84 => Ok(Ee::AtaCheckX1),
       85 => Ok(Ee::ForeignAta),
       86 => Ok(Ee::AtaHasNoData),
-      87 => Ok(Ee::Xyz087),
-      88 => Ok(Ee::Xyz088),
-      89 => Ok(Ee::Xyz089),
+      87 => Ok(Ee::MintRegHasDeposits),
+      88 => Ok(Ee::DuplicateAccount),
+      89 => Ok(Ee::MultisigThreshold),
       90 => Ok(Ee::NoRentExemptMint),
       91 => Ok(Ee::NoRentExemptTokAcct),
       92 => Ok(Ee::NoRentExemptMint22),
@@ -361,15 +429,45 @@ impl TryFrom<u32> for Ee {
       96 => Ok(Ee::ToWallet),
       97 => Ok(Ee::ToWalletNoLamport),
       98 => Ok(Ee::ToWalletForeignPDA),
-      99 => Ok(Ee::Xyz099),
-      100 => Ok(Ee::Xyz100),
-      101 => Ok(Ee::Xyz101),
+      99 => Ok(Ee::MultisigSigner),
+      100 => Ok(Ee::FeeBps),
+      101 => Ok(Ee::MultisigApproved),
       102 => Ok(Ee::MultiplyOverflow),
       103 => Ok(Ee::DividedByZero),
       104 => Ok(Ee::Remainder),
       105 => Ok(Ee::EmptyData),
       106 => Ok(Ee::FunctionSelector),
       107 => Ok(Ee::ClockGet),
+      108 => Ok(Ee::TimelockRequired),
+      109 => Ok(Ee::TimelockDelay),
+      110 => Ok(Ee::ActionKind),
+      111 => Ok(Ee::ActionNotReady),
+      112 => Ok(Ee::ActionPayloadHash),
+      113 => Ok(Ee::MultisigPDA),
+      114 => Ok(Ee::MultisigDataLen),
+      115 => Ok(Ee::MultisigNotApproved),
+      116 => Ok(Ee::MetadataField),
+      117 => Ok(Ee::TransferFeeBasisPoints),
+      118 => Ok(Ee::TransferFeeConfig),
+      119 => Ok(Ee::WithdrawWithheldAuthority),
+      120 => Ok(Ee::MintExtensionFlags),
+      121 => Ok(Ee::MintExtensionDenied),
+      122 => Ok(Ee::ImmutableOwner),
+      123 => Ok(Ee::InterestBearingConfig),
+      124 => Ok(Ee::RateAuthority),
+      125 => Ok(Ee::PoolPDA),
+      126 => Ok(Ee::ShareMintPDA),
+      127 => Ok(Ee::PoolZeroOut),
+      128 => Ok(Ee::PoolSlippage),
+      129 => Ok(Ee::UnexpectedAccounts),
+      130 => Ok(Ee::AdminConfig),
+      131 => Ok(Ee::ActionConfig),
+      132 => Ok(Ee::MintRegConfig),
+      133 => Ok(Ee::UserConfig),
+      134 => Ok(Ee::EscrowConfig),
+      135 => Ok(Ee::MultisigTxConfig),
+      136 => Ok(Ee::ConfigHasOpenAccounts),
+      137 => Ok(Ee::VaultConfig),
       _ => Err(Ee::NotMapped.into()),
     }
   }
@@ -379,12 +477,12 @@ impl ToStr for Ee {
   fn to_str(&self) -> &'static str {
     match self {
       Ee::MethodDiscriminator => "MethodDiscriminator",
-      Ee::Xyz001 => "Xyz001",
+      Ee::AdminPermission => "AdminPermission",
       Ee::OnlyProgOwner => "OnlyProgOwner",
       Ee::OnlyAdmin => "OnlyAdmin",
       Ee::OnlyUser => "OnlyUser",
       Ee::OnlyMaker => "OnlyMaker",
-      Ee::Xyz006 => "Xyz006",
+      Ee::ProgramPaused => "ProgramPaused",
 
       Ee::NotWritable => "NotWritable",
       Ee::NotExecutable => "NotExecutable",
@@ -396,9 +494,9 @@ impl ToStr for Ee {
       Ee::EscrowMintX => "EscrowMintX",
       Ee::EscrowMintY => "EscrowMintY",
       Ee::EscrowId => "EscrowId",
-      Ee::Xyz017 => "Xyz017",
-      Ee::Xyz018 => "Xyz018",
-      Ee::Xyz019 => "Xyz019",
+      Ee::EscrowNotExpired => "EscrowNotExpired",
+      Ee::EscrowTaker => "EscrowTaker",
+      Ee::OnlyPendingOwner => "OnlyPendingOwner",
 
       Ee::ZeroU128 => "ZeroU128",
       Ee::ZeroU64 => "ZeroU64",
@@ -415,11 +513,11 @@ impl ToStr for Ee {
       Ee::ByteSliceSize32 => "ByteSliceSize32",
       Ee::ByteSliceSize10 => "ByteSliceSize10",
       Ee::ByteSliceSize6 => "ByteSliceSize6",
-      Ee::Xyz035 => "Xyz035",
-      Ee::Xyz036 => "Xyz036",
-      Ee::Xyz037 => "Xyz037",
-      Ee::Xyz038 => "Xyz038",
-      Ee::Xyz039 => "Xyz039",
+      Ee::PayloadVersion => "PayloadVersion",
+      Ee::AccountDiscriminator => "AccountDiscriminator",
+      Ee::AccountVersion => "AccountVersion",
+      Ee::DestNotWallet => "DestNotWallet",
+      Ee::ConfigHasBalances => "ConfigHasBalances",
 
       Ee::InputDataLen => "InputDataLen",
       Ee::InputDataBump => "InputDataBump",
@@ -440,8 +538,8 @@ impl ToStr for Ee {
       Ee::UserPDA => "UserPDA",
       Ee::ActionPDA => "ActionPDA",
       Ee::EscrowPDA => "EscrowPDA",
-      Ee::Xyz058 => "Xyz058",
-      Ee::Xyz059 => "Xyz059",
+      Ee::UserInsuffDeposit => "UserInsuffDeposit",
+      Ee::UserInsuffRedeemable => "UserInsuffRedeemable",
 
       Ee::ConfigDataLengh => "ConfigDataLengh",
       Ee::VaultDataLengh => "VaultDataLengh",
@@ -449,17 +547,17 @@ impl ToStr for Ee {
       Ee::UserDataLengh => "UserDataLengh",
       Ee::EscrowDataLengh => "EscrowDataLengh",
       Ee::ActionDataLengh => "ActionDataLengh",
-      Ee::Xyz066 => "Xyz066",
-      Ee::Xyz067 => "Xyz067",
-      Ee::Xyz068 => "Xyz068",
-      Ee::Xyz069 => "Xyz069",
+      Ee::MintRegDataLengh => "MintRegDataLengh",
+      Ee::MintRegPDA => "MintRegPDA",
+      Ee::MintDisabled => "MintDisabled",
+      Ee::MintDepositCap => "MintDepositCap",
 
       Ee::DecimalsValue => "DecimalsValue",
       Ee::MintDataLen => "MintDataLen",
       Ee::MintOrMintAuthority => "MintOrMintAuthority",
       Ee::MintOrTokenProgram => "MintOrTokenProgram",
-      Ee::Xyz074 => "Xyz074",
-      Ee::Xyz075 => "Xyz075",
+      Ee::EscrowMaker => "EscrowMaker",
+      Ee::EscrowExpired => "EscrowExpired",
       Ee::EscrowInsuffTokenY => "EscrowInsuffTokenY",
       Ee::EscrowInsuffTokenX => "EscrowInsuffTokenX",
       Ee::MakerToWithdrawTokenY => "MakerToWithdrawTokenY",
@@ -472,9 +570,9 @@ impl ToStr for Ee {
       Ee::AtaCheckX1 => "AtaCheckX1",
       Ee::ForeignAta => "ForeignAta",
       Ee::AtaHasNoData => "AtaHasNoData",
-      Ee::Xyz087 => "Xyz087",
-      Ee::Xyz088 => "Xyz088",
-      Ee::Xyz089 => "Xyz089",
+      Ee::MintRegHasDeposits => "MintRegHasDeposits",
+      Ee::DuplicateAccount => "DuplicateAccount",
+      Ee::MultisigThreshold => "MultisigThreshold",
 
       Ee::NoRentExemptMint => "NoRentExemptMint",
       Ee::NoRentExemptTokAcct => "NoRentExemptTokAcct",
@@ -485,16 +583,46 @@ impl ToStr for Ee {
       Ee::ToWallet => "ToWallet",
       Ee::ToWalletNoLamport => "ToWalletNoLamport",
       Ee::ToWalletForeignPDA => "ToWalletForeignPDA",
-      Ee::Xyz099 => "Xyz099",
+      Ee::MultisigSigner => "MultisigSigner",
 
-      Ee::Xyz100 => "Xyz100",
-      Ee::Xyz101 => "Xyz101",
+      Ee::FeeBps => "FeeBps",
+      Ee::MultisigApproved => "MultisigApproved",
       Ee::MultiplyOverflow => "MultiplyOverflow",
       Ee::DividedByZero => "DividedByZero",
       Ee::Remainder => "Remainder",
       Ee::EmptyData => "EmptyData",
       Ee::FunctionSelector => "FunctionSelector",
       Ee::ClockGet => "ClockGet",
+      Ee::TimelockRequired => "TimelockRequired",
+      Ee::TimelockDelay => "TimelockDelay",
+      Ee::ActionKind => "ActionKind",
+      Ee::ActionNotReady => "ActionNotReady",
+      Ee::ActionPayloadHash => "ActionPayloadHash",
+      Ee::MultisigPDA => "MultisigPDA",
+      Ee::MultisigDataLen => "MultisigDataLen",
+      Ee::MultisigNotApproved => "MultisigNotApproved",
+      Ee::MetadataField => "MetadataField",
+      Ee::TransferFeeBasisPoints => "TransferFeeBasisPoints",
+      Ee::TransferFeeConfig => "TransferFeeConfig",
+      Ee::WithdrawWithheldAuthority => "WithdrawWithheldAuthority",
+      Ee::MintExtensionFlags => "MintExtensionFlags",
+      Ee::MintExtensionDenied => "MintExtensionDenied",
+      Ee::ImmutableOwner => "ImmutableOwner",
+      Ee::InterestBearingConfig => "InterestBearingConfig",
+      Ee::RateAuthority => "RateAuthority",
+      Ee::PoolPDA => "PoolPDA",
+      Ee::ShareMintPDA => "ShareMintPDA",
+      Ee::PoolZeroOut => "PoolZeroOut",
+      Ee::PoolSlippage => "PoolSlippage",
+      Ee::UnexpectedAccounts => "UnexpectedAccounts",
+      Ee::AdminConfig => "AdminConfig",
+      Ee::ActionConfig => "ActionConfig",
+      Ee::MintRegConfig => "MintRegConfig",
+      Ee::UserConfig => "UserConfig",
+      Ee::EscrowConfig => "EscrowConfig",
+      Ee::MultisigTxConfig => "MultisigTxConfig",
+      Ee::ConfigHasOpenAccounts => "ConfigHasOpenAccounts",
+      Ee::VaultConfig => "VaultConfig",
       Ee::NotMapped => "NotMapped",
     }
   }
@@ -552,9 +680,6 @@ pub fn check_mint0b(
 
 pub fn check_mint22a(mint: &AccountView, token_program: &AccountView) -> ProgramResult {
   //if !mint.owned_by(mint_authority)
-  if mint.data_len() != pinocchio_token_2022::state::Mint::BASE_LEN {
-    return Ee::MintDataLen.e();
-  }
   if !token_program.address().eq(&pinocchio_token_2022::ID) {
     return Ee::TokenProgram.e();
   }
@@ -563,7 +688,8 @@ pub fn check_mint22a(mint: &AccountView, token_program: &AccountView) -> Program
       return Ee::MintOrTokenProgram.e();
     }
   }
-  Ok(())
+  //mints with extensions are longer than Mint::BASE_LEN
+  check_mint_interface(mint)
 }
 pub fn check_mint22b(
   mint: &AccountView,
@@ -685,12 +811,284 @@ pub fn derive_pda1(user: &Address, bstr: &[u8]) -> Result<(Address, u8), Program
   //Address::find_program_address(&[b"vault", user.address().as_ref()], &ID)
   // let (pda, _bump) =
   Address::try_find_program_address(&[bstr, user.as_ref()], &PROG_ADDR)
-    .ok_or_else(|| ProgramError::InvalidSeeds)
+    .ok_or(ProgramError::InvalidSeeds)
+}
+pub fn derive_pda2(
+  addr1: &Address,
+  addr2: &Address,
+  bstr: &[u8],
+) -> Result<(Address, u8), ProgramError> {
+  log!("derive_pda2");
+  Address::try_find_program_address(&[bstr, addr1.as_ref(), addr2.as_ref()], &PROG_ADDR)
+    .ok_or(ProgramError::InvalidSeeds)
+}
+pub fn derive_pda3(
+  addr1: &Address,
+  addr2: &Address,
+  addr3: &Address,
+  bstr: &[u8],
+) -> Result<(Address, u8), ProgramError> {
+  log!("derive_pda3");
+  Address::try_find_program_address(
+    &[bstr, addr1.as_ref(), addr2.as_ref(), addr3.as_ref()],
+    &PROG_ADDR,
+  )
+  .ok_or(ProgramError::InvalidSeeds)
+}
+/// make the User PDA of user and mint on config if it does not exist, then return it
+pub fn init_user_pda<'a>(
+  payer: &AccountView,
+  user: &AccountView,
+  mint: &AccountView,
+  config: &Address,
+  user_pda: &'a AccountView,
+) -> Result<&'a mut User, ProgramError> {
+  if user_pda.is_data_empty() {
+    log!("Make User PDA");
+    let (expected_user_pda, bump) =
+      derive_pda3(config, user.address(), mint.address(), User::SEED)?;
+    if expected_user_pda.ne(user_pda.address()) {
+      return Err(Ee::UserPDA.into());
+    }
+    let signer_seeds = [
+      Seed::from(User::SEED),
+      Seed::from(config.as_ref()),
+      Seed::from(user.address().as_ref()),
+      Seed::from(mint.address().as_ref()),
+      Seed::from(core::slice::from_ref(&bump)),
+    ];
+    create_pda(payer, user_pda, User::LEN, &PROG_ADDR, &signer_seeds)?;
+
+    let user_acct = User::init_account_view(user_pda)?;
+    user_acct.set_config(config);
+    user_acct.set_user(user.address());
+    user_acct.set_mint(mint.address());
+    user_acct.set_bump(bump);
+    return Ok(user_acct);
+  }
+  let user_acct = load_user_pda(user, mint, user_pda)?;
+  user_acct.check_config(config)?;
+  Ok(user_acct)
+}
+/// return the existing User PDA of user and mint
+pub fn load_user_pda<'a>(
+  user: &AccountView,
+  mint: &AccountView,
+  user_pda: &'a AccountView,
+) -> Result<&'a mut User, ProgramError> {
+  let user_acct = User::from_account_view(user_pda)?;
+  if user_acct.user().ne(user.address()) || user_acct.mint().ne(mint.address()) {
+    return Err(Ee::UserPDA.into());
+  }
+  Ok(user_acct)
 }
 /*let pda = pubkey::create_program_address(
     &[PDA_SEED, &[self.datas.bump as u8]],
     &PROG_ADDR,
 ) */
+/// return the Mint Registry PDA of a mint accepted by config
+pub fn load_mint_reg<'a>(
+  config: &Address,
+  mint: &AccountView,
+  mint_reg: &'a AccountView,
+) -> Result<&'a mut MintReg, ProgramError> {
+  let reg = MintReg::from_account_view(mint_reg)?;
+  if reg.mint().ne(mint.address()) {
+    return Err(Ee::MintNotAccepted.into());
+  }
+  if reg.config().ne(config) {
+    return Err(Ee::MintRegConfig.into());
+  }
+  Ok(reg)
+}
+/// make the Mint Registry PDA of a new mint accepted by config, recording its decimals
+pub fn init_mint_reg(
+  payer: &AccountView,
+  config: &Address,
+  mint: &AccountView,
+  mint_reg: &AccountView,
+  deposit_cap: u64,
+) -> ProgramResult {
+  let (expected_mint_reg, bump) = derive_pda2(config, mint.address(), MintReg::SEED)?;
+  if expected_mint_reg.ne(mint_reg.address()) {
+    return Ee::MintRegPDA.e();
+  }
+  let signer_seeds = [
+    Seed::from(MintReg::SEED),
+    Seed::from(config.as_ref()),
+    Seed::from(mint.address().as_ref()),
+    Seed::from(core::slice::from_ref(&bump)),
+  ];
+  create_pda(payer, mint_reg, MintReg::LEN, &PROG_ADDR, &signer_seeds)?;
+
+  let decimals = mint_decimals_interface(mint)?;
+  log!("decimals: {}, deposit_cap: {}", decimals, deposit_cap);
+  let reg: &mut MintReg = MintReg::init_account_view(mint_reg)?;
+  reg.set_config(config);
+  reg.set_mint(mint.address());
+  reg.set_deposit_cap(deposit_cap);
+  reg.set_decimals(decimals);
+  reg.set_is_enabled(true);
+  reg.set_updated_at(get_time()?);
+  reg.set_bump(bump);
+  Ok(())
+}
+/// make the Admin PDA of admin on config if it does not exist, then add permissions to it
+pub fn grant_admin(
+  payer: &AccountView,
+  config: &Address,
+  admin: &Address,
+  admin_pda: &AccountView,
+  permissions: u32,
+) -> ProgramResult {
+  let (expected_admin_pda, bump) = derive_pda2(config, admin, Admin::SEED)?;
+  if expected_admin_pda.ne(admin_pda.address()) {
+    return Ee::AdminPDA.e();
+  }
+  if admin_pda.is_data_empty() {
+    log!("Make Admin PDA");
+    let signer_seeds = [
+      Seed::from(Admin::SEED),
+      Seed::from(config.as_ref()),
+      Seed::from(admin.as_ref()),
+      Seed::from(core::slice::from_ref(&bump)),
+    ];
+    create_pda(payer, admin_pda, Admin::LEN, &PROG_ADDR, &signer_seeds)?;
+    write_header(admin_pda, &Admin::DISCRIMINATOR, Admin::VERSION)?;
+  }
+
+  admin_pda.check_borrow_mut()?;
+  let admin_acct: &mut Admin = Admin::from_account_view(admin_pda)?;
+  admin_acct.set_config(config);
+  admin_acct.set_admin(admin);
+  admin_acct.set_permissions(admin_acct.permissions() | permissions);
+  admin_acct.set_updated_at(get_time()?);
+  admin_acct.set_bump(bump);
+  log!("permissions: {}", admin_acct.permissions());
+  Ok(())
+}
+/// remove permissions from an Admin PDA, closing it to rent_dest when none is left
+pub fn revoke_admin(
+  admin_pda: &AccountView,
+  permissions: u32,
+  rent_dest: &AccountView,
+) -> ProgramResult {
+  admin_pda.check_borrow_mut()?;
+  let admin: &mut Admin = Admin::from_account_view(admin_pda)?;
+  let remaining = admin.permissions() & !permissions;
+  log!("remaining permissions: {}", remaining);
+  if remaining > 0 {
+    admin.set_permissions(remaining);
+    admin.set_updated_at(get_time()?);
+    return Ok(());
+  }
+
+  log!("Close Admin PDA");
+  close_pda(admin_pda, rent_dest)
+}
+/// zero the data of a program PDA and send its lamports to dest
+pub fn close_pda(pda: &AccountView, dest: &AccountView) -> ProgramResult {
+  {
+    let mut data = pda.try_borrow_mut()?;
+    data.fill(0);
+  }
+  let sum_lam = dest
+    .lamports()
+    .checked_add(pda.lamports())
+    .ok_or(ProgramError::ArithmeticOverflow)?;
+  dest.set_lamports(sum_lam);
+  pda.set_lamports(0);
+  pda.close()
+}
+/// sha256 of the given bytes via the runtime syscall
+#[cfg(any(target_os = "solana", target_arch = "bpf"))]
+pub fn sha256(data: &[u8]) -> [u8; 32] {
+  let mut hash = [0u8; 32];
+  let vals: [&[u8]; 1] = [data];
+  unsafe {
+    pinocchio::syscalls::sol_sha256(
+      vals.as_ptr() as *const u8,
+      vals.len() as u64,
+      hash.as_mut_ptr(),
+    );
+  }
+  hash
+}
+/// sha256 of the given bytes in plain Rust, for host builds without the syscall
+#[cfg(not(any(target_os = "solana", target_arch = "bpf")))]
+pub fn sha256(data: &[u8]) -> [u8; 32] {
+  const K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+  ];
+  let mut h: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+  ];
+  //the message is followed by 0x80, zero padding and the bit length, filling whole 64-byte blocks
+  let bit_len = (data.len() as u64).wrapping_mul(8).to_be_bytes();
+  let total = (data.len() + 9).div_ceil(64) * 64;
+  let byte_at = |i: usize| -> u8 {
+    if i < data.len() {
+      data[i]
+    } else if i == data.len() {
+      0x80
+    } else if i >= total - 8 {
+      bit_len[i + 8 - total]
+    } else {
+      0
+    }
+  };
+  for block in (0..total).step_by(64) {
+    let mut w = [0u32; 64];
+    for (t, word) in w.iter_mut().take(16).enumerate() {
+      let i = block + t * 4;
+      *word = u32::from_be_bytes([byte_at(i), byte_at(i + 1), byte_at(i + 2), byte_at(i + 3)]);
+    }
+    for t in 16..64 {
+      let s0 = w[t - 15].rotate_right(7) ^ w[t - 15].rotate_right(18) ^ (w[t - 15] >> 3);
+      let s1 = w[t - 2].rotate_right(17) ^ w[t - 2].rotate_right(19) ^ (w[t - 2] >> 10);
+      w[t] = w[t - 16]
+        .wrapping_add(s0)
+        .wrapping_add(w[t - 7])
+        .wrapping_add(s1);
+    }
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut hh] = h;
+    for t in 0..64 {
+      let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+      let ch = (e & f) ^ (!e & g);
+      let t1 = hh
+        .wrapping_add(s1)
+        .wrapping_add(ch)
+        .wrapping_add(K[t])
+        .wrapping_add(w[t]);
+      let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+      let maj = (a & b) ^ (a & c) ^ (b & c);
+      let t2 = s0.wrapping_add(maj);
+      hh = g;
+      g = f;
+      f = e;
+      e = d.wrapping_add(t1);
+      d = c;
+      c = b;
+      b = a;
+      a = t1.wrapping_add(t2);
+    }
+    for (state, v) in h.iter_mut().zip([a, b, c, d, e, f, g, hh]) {
+      *state = state.wrapping_add(v);
+    }
+  }
+  let mut hash = [0u8; 32];
+  for (chunk, word) in hash.chunks_exact_mut(4).zip(h) {
+    chunk.copy_from_slice(&word.to_be_bytes());
+  }
+  hash
+}
 pub fn check_pda(account: &AccountView) -> ProgramResult {
   if account.lamports() == 0 {
     return Ee::PdaNoLamport.e();
@@ -814,6 +1212,16 @@ pub fn data_len(data: &[u8], expected: usize) -> ProgramResult {
   }
   Ok(())
 }
+/// Typed instruction payloads start with this version byte
+pub const PAYLOAD_VERSION: u8 = 1;
+/// check the payload length and leading version byte, then return the payload after the version byte
+pub fn versioned_payload(data: &[u8], expected: usize) -> Result<&[u8], ProgramError> {
+  data_len(data, expected + 1)?;
+  if data[0] != PAYLOAD_VERSION {
+    return Err(Ee::PayloadVersion.into());
+  }
+  Ok(&data[1..])
+}
 pub fn check_decimals(mint: &AccountView, decimals: u8) -> ProgramResult {
   let mint_info = pinocchio_token::state::Mint::from_account_view(mint)?;
   if decimals != mint_info.decimals() {
@@ -850,14 +1258,14 @@ pub fn none_zero_u8(uint: u8) -> ProgramResult {
 }
 /// Parse a u64 from u8 array
 pub fn parse_u64(data: &[u8]) -> Result<u64, ProgramError> {
-  let bytes: [u8; 8] = data.try_into().or_else(|_e| Err(Ee::ByteSizeForU64))?;
+  let bytes: [u8; 8] = data.try_into().map_err(|_e| Ee::ByteSizeForU64)?;
 
   let amt = u64::from_le_bytes(bytes);
   // let amount = u64::from_le_bytes([data[0], data[1], data[2], data[3], data[4], data[5], data[6], data[7]]);
   Ok(amt)
 }
 pub fn parse_u32(data: &[u8]) -> Result<u32, ProgramError> {
-  let bytes: [u8; 4] = data.try_into().or_else(|_e| Err(Ee::ByteSizeForU32))?;
+  let bytes: [u8; 4] = data.try_into().map_err(|_e| Ee::ByteSizeForU32)?;
 
   let amt = u32::from_le_bytes(bytes);
   // let amount = u64::from_le_bytes([data[0], data[1], data[2], data[3]]);
@@ -867,15 +1275,15 @@ pub fn to32bytes(byte_slice: &[u8]) -> Result<&[u8; 32], ProgramError> {
   let bytes: &[u8; 32] = byte_slice.try_into().map_err(|_| Ee::ByteSliceSize32)?;
   //let mut str_u8array = [0u8; 32];
   //str_u8array.copy_from_slice(&data[10..42]);
-  return Ok(bytes);
+  Ok(bytes)
 }
 pub fn to10bytes(byte_slice: &[u8]) -> Result<&[u8; 10], ProgramError> {
   let bytes: &[u8; 10] = byte_slice.try_into().map_err(|_| Ee::ByteSliceSize10)?;
-  return Ok(bytes);
+  Ok(bytes)
 }
 pub fn to6bytes(byte_slice: &[u8]) -> Result<&[u8; 6], ProgramError> {
   let bytes: &[u8; 6] = byte_slice.try_into().map_err(|_| Ee::ByteSliceSize6)?;
-  return Ok(bytes);
+  Ok(bytes)
 }
 pub fn u8_to_bool(v: u8) -> Result<bool, ProgramError> {
   match v {
@@ -902,6 +1310,202 @@ pub fn sol_balc(from: &AccountView, amount: u64) -> ProgramResult {
   Ok(())
 }
 
+//----------------== SOL Pool
+/// make the shared SOL pool vault and its share mint if they do not exist, then return the pool bump for signing as the mint authority
+pub fn ensure_pool_accounts(
+  payer: &AccountView,
+  config_pda: &AccountView,
+  pool: &AccountView,
+  share_mint: &AccountView,
+  token_program: &AccountView,
+) -> Result<u8, ProgramError> {
+  log!("ensure_pool_accounts");
+  let (expected_pool, pool_bump) = derive_pda1(config_pda.address(), POOL_SEED)?;
+  if pool.address().ne(&expected_pool) {
+    return Err(Ee::PoolPDA.into());
+  }
+  let (expected_share_mint, share_mint_bump) = derive_pda1(config_pda.address(), SHARE_MINT_SEED)?;
+  if share_mint.address().ne(&expected_share_mint) {
+    return Err(Ee::ShareMintPDA.into());
+  }
+  if pool.owned_by(&pinocchio_system::ID) {
+    log!("Make pool vault");
+    let signer_seeds = [
+      Seed::from(POOL_SEED),
+      Seed::from(config_pda.address().as_ref()),
+      Seed::from(core::slice::from_ref(&pool_bump)),
+    ];
+    create_pda(payer, pool, VAULT_SIZE, &PROG_ADDR, &signer_seeds)?;
+    write_header(pool, &POOL_DISCRIMINATOR, POOL_VERSION)?;
+  } else {
+    check_pool(pool)?;
+  }
+  if share_mint.owned_by(&pinocchio_system::ID) {
+    log!("Make share mint");
+    let signer_seeds = [
+      Seed::from(SHARE_MINT_SEED),
+      Seed::from(config_pda.address().as_ref()),
+      Seed::from(core::slice::from_ref(&share_mint_bump)),
+    ];
+    create_pda(
+      payer,
+      share_mint,
+      Mint::LEN,
+      token_program.address(),
+      &signer_seeds,
+    )?;
+
+    pinocchio_token::instructions::InitializeMint2 {
+      mint: share_mint,
+      decimals: SHARE_DECIMALS,
+      mint_authority: pool.address(),
+      freeze_authority: None,
+    }
+    .invoke()?;
+  }
+  check_mint0a(share_mint, token_program)?;
+  Ok(pool_bump)
+}
+/// the pool vault is program-owned with the pool header
+pub fn check_pool(pool: &AccountView) -> ProgramResult {
+  check_pda(pool)?;
+  if pool.data_len() != VAULT_SIZE {
+    return Ee::VaultDataLengh.e();
+  }
+  check_header(pool, &POOL_DISCRIMINATOR, POOL_VERSION)
+}
+//----------------== SOL Vault
+/// make the SOL vault of a user on a config if it does not exist, recording the config it is booked to
+pub fn ensure_sol_vault(
+  user: &AccountView,
+  config: &Address,
+  vault: &AccountView,
+) -> ProgramResult {
+  let (expected_vault, bump) = derive_pda2(config, user.address(), VAULT_SEED)?;
+  if vault.address().ne(&expected_vault) {
+    return Ee::VaultPDA.e();
+  }
+  if !vault.owned_by(&pinocchio_system::ID) {
+    return check_sol_vault(vault, config);
+  }
+  log!("Make SOL vault");
+  let signer_seeds = [
+    Seed::from(VAULT_SEED),
+    Seed::from(config.as_ref()),
+    Seed::from(user.address().as_ref()),
+    Seed::from(core::slice::from_ref(&bump)),
+  ];
+  create_pda(user, vault, SOL_VAULT_SIZE, &PROG_ADDR, &signer_seeds)?;
+  write_header(vault, &SOL_VAULT_DISCRIMINATOR, SOL_VAULT_VERSION)?;
+  vault.try_borrow_mut()?[ACCOUNT_HEADER_SIZE..].copy_from_slice(config.as_ref());
+  Ok(())
+}
+/// the SOL vault is program-owned with the SOL vault header and is bound to config
+pub fn check_sol_vault(vault: &AccountView, config: &Address) -> ProgramResult {
+  check_pda(vault)?;
+  if vault.data_len() != SOL_VAULT_SIZE {
+    return Ee::VaultDataLengh.e();
+  }
+  check_header(vault, &SOL_VAULT_DISCRIMINATOR, SOL_VAULT_VERSION)?;
+  if vault.try_borrow()?[ACCOUNT_HEADER_SIZE..].ne(config.as_ref()) {
+    return Ee::VaultConfig.e();
+  }
+  Ok(())
+}
+/// make a PDA signed by its seeds. Anyone can send lamports to the address first, leaving it system-owned with a balance that fails CreateAccount, so it is then topped up to the rent minimum, allocated and assigned instead
+pub fn create_pda(
+  payer: &AccountView,
+  pda: &AccountView,
+  space: usize,
+  owner: &Address,
+  signer_seeds: &[Seed],
+) -> ProgramResult {
+  let lamports = Rent::get()?.try_minimum_balance(space)?;
+  let signer = Signer::from(signer_seeds);
+  if pda.lamports() == 0 {
+    return pinocchio_system::instructions::CreateAccount {
+      from: payer,
+      to: pda,
+      lamports,
+      space: space as u64,
+      owner,
+    }
+    .invoke_signed(&[signer]);
+  }
+  log!("pre-funded PDA: {} lamports", pda.lamports());
+  let top_up = lamports.saturating_sub(pda.lamports());
+  if top_up > 0 {
+    pinocchio_system::instructions::Transfer {
+      from: payer,
+      to: pda,
+      lamports: top_up,
+    }
+    .invoke()?;
+  }
+  pinocchio_system::instructions::Allocate {
+    account: pda,
+    space: space as u64,
+  }
+  .invoke_signed(core::slice::from_ref(&signer))?;
+  pinocchio_system::instructions::Assign {
+    account: pda,
+    owner,
+  }
+  .invoke_signed(&[signer])
+}
+/// lamports in the pool vault above its rent minimum
+pub fn pool_assets(pool: &AccountView) -> Result<u64, ProgramError> {
+  let (balance, min_balance) = rent_exempt(pool)?;
+  Ok(balance - min_balance)
+}
+/// shares minted for depositing `lamports`, rounded down in favor of the pool. One virtual share and lamport make donations to an empty pool unprofitable
+pub fn pool_shares_for(lamports: u64, assets: u64, supply: u64) -> Result<u64, ProgramError> {
+  mul_div_floor(
+    lamports,
+    supply
+      .checked_add(1)
+      .ok_or(ProgramError::ArithmeticOverflow)?,
+    assets
+      .checked_add(1)
+      .ok_or(ProgramError::ArithmeticOverflow)?,
+  )
+}
+/// lamports paid for burning `shares`, rounded down in favor of the pool
+pub fn pool_lamports_for(shares: u64, assets: u64, supply: u64) -> Result<u64, ProgramError> {
+  mul_div_floor(
+    shares,
+    assets
+      .checked_add(1)
+      .ok_or(ProgramError::ArithmeticOverflow)?,
+    supply
+      .checked_add(1)
+      .ok_or(ProgramError::ArithmeticOverflow)?,
+  )
+}
+
+//----------------== Math
+/// a * b / c rounded down, with u128 intermediate
+pub fn mul_div_floor(a: u64, b: u64, c: u64) -> Result<u64, ProgramError> {
+  if c == 0 {
+    return Err(Ee::DividedByZero.into());
+  }
+  let product = (a as u128)
+    .checked_mul(b as u128)
+    .ok_or(Ee::MultiplyOverflow)?;
+  u64::try_from(product / c as u128).map_err(|_| ProgramError::ArithmeticOverflow)
+}
+/// a * b / c rounded up, with u128 intermediate
+pub fn mul_div_ceil(a: u64, b: u64, c: u64) -> Result<u64, ProgramError> {
+  if c == 0 {
+    return Err(Ee::DividedByZero.into());
+  }
+  let product = (a as u128)
+    .checked_mul(b as u128)
+    .ok_or(Ee::MultiplyOverflow)?;
+  let quotient = product.div_ceil(c as u128);
+  u64::try_from(quotient).map_err(|_| ProgramError::ArithmeticOverflow)
+}
+
 //----------------== Token 2022 Interface
 const TOKEN_2022_ACCOUNT_DISCRIMINATOR_OFFSET: usize = 165;
 pub const TOKEN_2022_MINT_DISCRIMINATOR: u8 = 0x01;
@@ -962,6 +1566,145 @@ pub fn check_tokacct_interface(ata: &AccountView) -> ProgramResult {
   Ok(())
 }
 
+/// Token2022 extension types as in spl_token_2022::extension::ExtensionType
+pub const EXT_TRANSFER_FEE_CONFIG: u16 = 1;
+pub const EXT_MINT_CLOSE_AUTHORITY: u16 = 3;
+pub const EXT_DEFAULT_ACCOUNT_STATE: u16 = 6;
+pub const EXT_IMMUTABLE_OWNER: u16 = 7;
+pub const EXT_NON_TRANSFERABLE: u16 = 9;
+pub const EXT_INTEREST_BEARING_CONFIG: u16 = 10;
+pub const EXT_PERMANENT_DELEGATE: u16 = 12;
+pub const EXT_TRANSFER_HOOK: u16 = 14;
+pub const EXT_METADATA_POINTER: u16 = 18;
+pub const EXT_TOKEN_METADATA: u16 = 19;
+pub const EXT_PAUSABLE: u16 = 26;
+/// AccountState::Frozen as the DefaultAccountState extension value
+pub const ACCOUNT_STATE_FROZEN: u8 = 2;
+
+/// Iterates the TLV extensions of a Token2022 mint or token account as (extension type, value). Stops at the uninitialized padding or a truncated entry
+pub struct TlvExtensions<'a> {
+  data: &'a [u8],
+  offset: usize,
+}
+impl<'a> Iterator for TlvExtensions<'a> {
+  type Item = (u16, &'a [u8]);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    //[2 (extension type) + 2 (length)]
+    let header = self.data.get(self.offset..self.offset + 4)?;
+    let ext_type = u16::from_le_bytes([header[0], header[1]]);
+    let ext_len = u16::from_le_bytes([header[2], header[3]]) as usize;
+    let start = self.offset + 4;
+    if ext_type == 0 || start + ext_len > self.data.len() {
+      self.offset = self.data.len();
+      return None;
+    }
+    self.offset = start + ext_len;
+    Some((ext_type, &self.data[start..start + ext_len]))
+  }
+}
+/// the TLV extensions start after the account type byte. Base-length accounts have none
+pub fn tlv_extensions(data: &[u8]) -> TlvExtensions<'_> {
+  TlvExtensions {
+    data,
+    offset: TOKEN_2022_ACCOUNT_DISCRIMINATOR_OFFSET + 1,
+  }
+}
+/// reject a Token2022 mint with an extension the policy denies: with allowlist, every extension must be in ext_mask; without, none may be. A DefaultAccountState only counts when it is Frozen
+pub fn check_mint_ext_policy(mint: &AccountView, ext_mask: u64, allowlist: bool) -> ProgramResult {
+  if !mint.owned_by(&pinocchio_token_2022::ID) {
+    return Ok(());
+  }
+  let data = mint.try_borrow()?;
+  for (ext_type, value) in tlv_extensions(&data) {
+    if ext_type == EXT_DEFAULT_ACCOUNT_STATE && value.first() != Some(&ACCOUNT_STATE_FROZEN) {
+      continue;
+    }
+    let listed = ext_type < 64 && ext_mask & (1 << ext_type) != 0;
+    if listed != allowlist {
+      log!("denied extension: {}", ext_type);
+      return Ee::MintExtensionDenied.e();
+    }
+  }
+  Ok(())
+}
+
+/// mint must be owned by the given token program: either TokenLgc or Token2022
+pub fn check_mint_interface_prog(mint: &AccountView, token_program: &AccountView) -> ProgramResult {
+  check_mint_interface(mint)?;
+  if !mint.owned_by(token_program.address()) {
+    return Ee::MintOrTokenProgram.e();
+  }
+  Ok(())
+}
+/// the first 82 bytes of a Token2022 mint are the same as the legacy mint
+pub fn mint_decimals_interface(mint: &AccountView) -> Result<u8, ProgramError> {
+  check_mint_interface(mint)?;
+  let data = mint.try_borrow()?;
+  // SAFETY: check_mint_interface has checked the owner and that the data holds a full mint
+  let mint_info = unsafe { Mint22::from_bytes_unchecked(&data) };
+  Ok(mint_info.decimals())
+}
+pub fn check_decimals_interface(mint: &AccountView, decimals: u8) -> ProgramResult {
+  if decimals != mint_decimals_interface(mint)? {
+    return Ee::DecimalsValue.e();
+  }
+  Ok(())
+}
+/// the first 165 bytes of a Token2022 token account are the same as the legacy token account
+pub fn check_ata_interface(
+  ata: &AccountView,
+  owner: &AccountView,
+  mint: &AccountView,
+) -> ProgramResult {
+  if ata.data_len() == 0 {
+    return Ee::AtaHasNoData.e();
+  }
+  check_tokacct_interface(ata)?;
+  let data = ata.try_borrow()?;
+  // SAFETY: check_tokacct_interface has checked the owner and that the data holds a full token account
+  let ata_info = unsafe { TokenAccount22::from_bytes_unchecked(&data) };
+  if !ata_info.owner().eq(owner.address()) {
+    return Ee::AtaOrOwner.e();
+  }
+  if !ata_info.mint().eq(mint.address()) {
+    return Ee::AtaOrMint.e();
+  }
+  Ok(())
+}
+pub fn ata_amount_interface(ata: &AccountView) -> Result<u64, ProgramError> {
+  check_tokacct_interface(ata)?;
+  let data = ata.try_borrow()?;
+  // SAFETY: check_tokacct_interface has checked the owner and that the data holds a full token account
+  let ata_info = unsafe { TokenAccount22::from_bytes_unchecked(&data) };
+  Ok(ata_info.amount())
+}
+pub fn ata_balc_interface(from_ata: &AccountView, amount: u64) -> ProgramResult {
+  if ata_amount_interface(from_ata)? < amount {
+    return Err(ProgramError::InsufficientFunds);
+  }
+  Ok(())
+}
+/// rent is based on the account data length, which includes Token2022 extensions
+pub fn rent_exempt_mint_interface(account: &AccountView) -> ProgramResult {
+  let rent = Rent::from_account_view(account)?;
+  let lamports = Rent::try_minimum_balance(&rent, account.data_len())?;
+
+  if account.lamports() < lamports {
+    return Ee::NoRentExemptMint.e();
+  }
+  Ok(())
+}
+pub fn rent_exempt_tokacct_interface(account: &AccountView) -> ProgramResult {
+  let rent = Rent::from_account_view(account)?;
+  let lamports = Rent::try_minimum_balance(&rent, account.data_len())?;
+
+  if account.lamports() < lamports {
+    return Ee::NoRentExemptTokAcct.e();
+  }
+  Ok(())
+}
+
 pub fn get_time() -> Result<u32, ProgramError> {
   let clock = Clock::get().map_err(|_| Ee::ClockGet)?;
   let time = clock.unix_timestamp as u32;
@@ -3,14 +3,18 @@ use pinocchio::{error::ProgramError, AccountView, ProgramResult};
 use pinocchio_log::log;
 
 use crate::{
+  check_sol_vault, derive_pda2,
   instructions::{check_pda, check_signer, derive_pda1, parse_u64},
-  none_zero_u64, rent_exempt, Ee, VAULT_SEED,
+  none_zero_u64, rent_exempt, writable, Config, Ee, VAULT_SEED,
 };
 
-//  vault is owned by the program, matches the PDA derived from user. The withdrawn amount is everything above the rent minimum.
+//  vault is owned by the program, matches the PDA derived from config and user. The withdrawn amount is everything above the rent minimum.
+//  vaults seeded by the user alone were made before vaults were bound to a config, so their SOL is not booked on the config.
 pub struct WithdrawSol<'a> {
   pub user: &'a AccountView,
   pub vault: &'a AccountView,
+  pub config_pda: &'a AccountView,
+  pub is_legacy: bool,
   pub amount: u64,
 }
 impl<'a> WithdrawSol<'a> {
@@ -21,6 +25,8 @@ impl<'a> WithdrawSol<'a> {
     let WithdrawSol {
       user,
       vault,
+      config_pda,
+      is_legacy,
       amount,
     } = self;
     log!("withdrawSol process()");
@@ -30,15 +36,20 @@ impl<'a> WithdrawSol<'a> {
       let from_lam = vault
         .lamports()
         .checked_sub(amount)
-        .ok_or_else(|| ProgramError::InsufficientFunds)?;
+        .ok_or(ProgramError::InsufficientFunds)?;
       vault.set_lamports(from_lam);
 
       let sum_lam = user
         .lamports()
         .checked_add(amount)
-        .ok_or_else(|| ProgramError::ArithmeticOverflow)?;
+        .ok_or(ProgramError::ArithmeticOverflow)?;
       user.set_lamports(sum_lam);
     }
+    if is_legacy {
+      log!("Legacy vault is not booked on the config");
+    } else {
+      Config::from_account_view(config_pda)?.sub_sol_balance(amount)?;
+    }
     log!("{} lamports withdrawn from vault", amount);
     Ok(())
   }
@@ -51,18 +62,29 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for WithdrawSol<'a> {
     let (data, accounts) = value;
     log!("accounts len: {}, data len: {}", accounts.len(), data.len());
 
-    let [user, vault] = accounts else {
+    let [user, vault, config_pda] = accounts else {
       return Err(ProgramError::NotEnoughAccountKeys);
     };
     check_signer(user)?;
-    check_pda(vault)?;
+    writable(config_pda)?;
+    check_pda(config_pda)?;
+    config_pda.check_borrow_mut()?;
+    Config::check(config_pda)?;
 
     let amount = parse_u64(data)?;
     none_zero_u64(amount)?;
 
-    let (expected_vault_pda, _bump) = derive_pda1(user.address(), VAULT_SEED)?;
-    if vault.address() != &expected_vault_pda {
-      return Err(Ee::VaultPDA.into());
+    let (expected_vault_pda, _bump) =
+      derive_pda2(config_pda.address(), user.address(), VAULT_SEED)?;
+    let is_legacy = vault.address() != &expected_vault_pda;
+    if is_legacy {
+      check_pda(vault)?;
+      let (legacy_vault_pda, _bump) = derive_pda1(user.address(), VAULT_SEED)?;
+      if vault.address() != &legacy_vault_pda {
+        return Err(Ee::VaultPDA.into());
+      }
+    } else {
+      check_sol_vault(vault, config_pda.address())?;
     }
 
     // Compute how much can be withdrawn while keeping the account rent-exempt
@@ -77,13 +99,15 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountView])> for WithdrawSol<'a> {
     if vault_balc
       <= vault_min_balc
         .checked_add(amount)
-        .ok_or_else(|| ProgramError::ArithmeticOverflow)?
+        .ok_or(ProgramError::ArithmeticOverflow)?
     {
       return Err(Ee::PdaToBeBelowRentExempt.into());
     }
     Ok(Self {
       user,
       vault,
+      config_pda,
+      is_legacy,
       amount,
     })
   }
@@ -32,7 +32,7 @@ fn process_instruction(
   // `split_first` separates the first byte (discriminator) from the rest (payload).
   let (discriminator, data) = instruction_data
     .split_first()
-    .ok_or_else(|| ProgramError::InvalidInstructionData)?;
+    .ok_or(ProgramError::InvalidInstructionData)?;
 
   //reads the first byte as a discriminator to determine which method to call (here: 0 = DepositSol, 1 = WithdrawSol).
   match discriminator {
@@ -49,13 +49,50 @@ fn process_instruction(
     Token2022InitAta::DISCRIMINATOR => Token2022InitAta::try_from((data, accounts))?.process(),
     Token2022MintToken::DISCRIMINATOR => Token2022MintToken::try_from((data, accounts))?.process(),
     InitConfig::DISCRIMINATOR => InitConfig::try_from((data, accounts))?.process(),
-    UpdateConfig::DISCRIMINATOR => UpdateConfig::try_from((data, accounts))?.process(),
+    ConfigSetFee::DISCRIMINATOR => ConfigSetFee::try_from((data, accounts))?.process(),
     CloseConfigPda::DISCRIMINATOR => CloseConfigPda::try_from((data, accounts))?.process(),
     EscrowTokMake::DISCRIMINATOR => EscrowTokMake::try_from((data, accounts))?.process(),
     EscrowTokTake::DISCRIMINATOR => EscrowTokTake::try_from((data, accounts))?.process(),
     EscrowTokWithdraw::DISCRIMINATOR => EscrowTokWithdraw::try_from((data, accounts))?.process(),
     EscrowTokCancel::DISCRIMINATOR => EscrowTokCancel::try_from((data, accounts))?.process(),
-    ConfigResize::DISCRIMINATOR => ConfigResize::try_from((data, accounts))?.process(),
+    ConfigMigrate::DISCRIMINATOR => ConfigMigrate::try_from((data, accounts))?.process(),
+    EscrowTokExpire::DISCRIMINATOR => EscrowTokExpire::try_from((data, accounts))?.process(),
+    AdminGrant::DISCRIMINATOR => AdminGrant::try_from((data, accounts))?.process(),
+    AdminRevoke::DISCRIMINATOR => AdminRevoke::try_from((data, accounts))?.process(),
+    TokLgcVoucher::DISCRIMINATOR => TokLgcVoucher::try_from((data, accounts))?.process(),
+    OwnerPropose::DISCRIMINATOR => OwnerPropose::try_from((data, accounts))?.process(),
+    OwnerAccept::DISCRIMINATOR => OwnerAccept::try_from((data, accounts))?.process(),
+    OwnerCancel::DISCRIMINATOR => OwnerCancel::try_from((data, accounts))?.process(),
+    MintAdd::DISCRIMINATOR => MintAdd::try_from((data, accounts))?.process(),
+    MintRemove::DISCRIMINATOR => MintRemove::try_from((data, accounts))?.process(),
+    MintEnable::DISCRIMINATOR => MintEnable::try_from((data, accounts))?.process(),
+    MintDisable::DISCRIMINATOR => MintDisable::try_from((data, accounts))?.process(),
+    ConfigReconcile::DISCRIMINATOR => ConfigReconcile::try_from((data, accounts))?.process(),
+    ConfigSetStatus::DISCRIMINATOR => ConfigSetStatus::try_from((data, accounts))?.process(),
+    ConfigSetLabel::DISCRIMINATOR => ConfigSetLabel::try_from((data, accounts))?.process(),
+    ConfigSetAuthorized::DISCRIMINATOR => {
+      ConfigSetAuthorized::try_from((data, accounts))?.process()
+    }
+    ActionEnqueue::DISCRIMINATOR => ActionEnqueue::try_from((data, accounts))?.process(),
+    ActionExecute::DISCRIMINATOR => ActionExecute::try_from((data, accounts))?.process(),
+    ActionCancel::DISCRIMINATOR => ActionCancel::try_from((data, accounts))?.process(),
+    MultisigInit::DISCRIMINATOR => MultisigInit::try_from((data, accounts))?.process(),
+    MultisigPropose::DISCRIMINATOR => MultisigPropose::try_from((data, accounts))?.process(),
+    MultisigApprove::DISCRIMINATOR => MultisigApprove::try_from((data, accounts))?.process(),
+    MultisigExecute::DISCRIMINATOR => MultisigExecute::try_from((data, accounts))?.process(),
+    MultisigCancel::DISCRIMINATOR => MultisigCancel::try_from((data, accounts))?.process(),
+    Token2022UpdateMetadata::DISCRIMINATOR => {
+      Token2022UpdateMetadata::try_from((data, accounts))?.process()
+    }
+    Token2022HarvestFees::DISCRIMINATOR => {
+      Token2022HarvestFees::try_from((data, accounts))?.process()
+    }
+    ConfigSetExtPolicy::DISCRIMINATOR => ConfigSetExtPolicy::try_from((data, accounts))?.process(),
+    Token2022UpdateRate::DISCRIMINATOR => {
+      Token2022UpdateRate::try_from((data, accounts))?.process()
+    }
+    PoolDepositSol::DISCRIMINATOR => PoolDepositSol::try_from((data, accounts))?.process(),
+    PoolWithdrawSol::DISCRIMINATOR => PoolWithdrawSol::try_from((data, accounts))?.process(),
     _ => Err(Ee::MethodDiscriminator.into()),
   } //file names start with a lower case + Camel cases, but struct names start with Upper case + Camel cases!
 }
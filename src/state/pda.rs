@@ -1,52 +1,106 @@
 use pinocchio::{error::ProgramError, AccountView, Address, ProgramResult};
+use pinocchio_log::log;
 
-use crate::{none_zero_u64, Ee, PROG_ADDR};
+use crate::{
+  check_mint_ext_policy, mul_div_ceil, none_zero_u64, Ee, EXT_DEFAULT_ACCOUNT_STATE,
+  EXT_NON_TRANSFERABLE, EXT_PAUSABLE, EXT_PERMANENT_DELEGATE, EXT_TRANSFER_HOOK, PROG_ADDR,
+};
 
 //Vault to hold SOL and control Tokens, and has no struct to be declared
 pub const VAULT_SEED: &[u8] = b"vault";
 pub const ACCOUNT_DISCRIMINATOR_SIZE: usize = 8;
 pub const VAULT_SIZE: usize = ACCOUNT_DISCRIMINATOR_SIZE + size_of::<u64>(); //SOL amount
 
+//Shared SOL pool vault of VAULT_SIZE with a header, and its share mint whose mint authority is the pool vault
+pub const POOL_SEED: &[u8] = b"pool";
+pub const POOL_DISCRIMINATOR: [u8; ACCOUNT_DISCRIMINATOR_SIZE] = *b"pool\0\0\0\0";
+pub const POOL_VERSION: u8 = 1;
+pub const SHARE_MINT_SEED: &[u8] = b"share_mint";
+pub const SHARE_DECIMALS: u8 = 9;
+pub const ACCOUNT_HEADER_SIZE: usize = ACCOUNT_DISCRIMINATOR_SIZE + 1; //discriminator + version
+
+//SOL vault of a user on one config, seeded by the config and the user, with a header and the config whose sol_balance books it
+pub const SOL_VAULT_DISCRIMINATOR: [u8; ACCOUNT_DISCRIMINATOR_SIZE] = *b"solvault";
+pub const SOL_VAULT_VERSION: u8 = 1;
+pub const SOL_VAULT_SIZE: usize = ACCOUNT_HEADER_SIZE + 32; //header + config
+
+/// every program account starts with an 8-byte discriminator and a version byte, so accounts of the same size cannot be confused
+pub fn check_header(
+  pda: &AccountView,
+  discriminator: &[u8; ACCOUNT_DISCRIMINATOR_SIZE],
+  version: u8,
+) -> ProgramResult {
+  let data = unsafe { pda.borrow_unchecked() };
+  if data.len() < ACCOUNT_HEADER_SIZE || data[..ACCOUNT_DISCRIMINATOR_SIZE].ne(discriminator) {
+    return Ee::AccountDiscriminator.e();
+  }
+  if data[ACCOUNT_DISCRIMINATOR_SIZE] != version {
+    return Ee::AccountVersion.e();
+  }
+  Ok(())
+}
+/// write the discriminator and version into a newly created account
+pub fn write_header(
+  pda: &AccountView,
+  discriminator: &[u8; ACCOUNT_DISCRIMINATOR_SIZE],
+  version: u8,
+) -> ProgramResult {
+  let mut data = pda.try_borrow_mut()?;
+  if data.len() < ACCOUNT_HEADER_SIZE {
+    return Ee::AccountDiscriminator.e();
+  }
+  if data[..ACCOUNT_HEADER_SIZE].iter().any(|b| *b != 0) {
+    return Err(ProgramError::AccountAlreadyInitialized);
+  }
+  data[..ACCOUNT_DISCRIMINATOR_SIZE].copy_from_slice(discriminator);
+  data[ACCOUNT_DISCRIMINATOR_SIZE] = version;
+  Ok(())
+}
+
 //TODO: Bytemuck is a great library that makes it easy to read and write byte arrays as structs.
+/// Layout changes bump VERSION and append new fields, then ConfigMigrate upgrades existing configs in place. The headerless baseline layout is version 0
 #[derive(Clone, Debug)]
-#[repr(C)] //0..8 	Discriminator 	8 bytes
+#[repr(C)]
 pub struct Config {
-  mint0: Address,         // 32
-  mint1: Address,         // 32
-  mint2: Address,         // 32
-  mint3: Address,         // 32
-  vault: Address,         // 32
-  prog_owner: Address,    // 32
-  admin: Address,         // 32
-  str_u8array: [u8; 32],  // 32
-  fee: [u8; 8],           // 8 for u64,
-  sol_balance: [u8; 8],   // 8
+  discriminator: [u8; 8],  // 8
+  version: u8,             // 1
+  vault: Address,          // 32
+  prog_owner: Address,     // 32
+  seed_owner: Address, // 32 the prog_owner at InitConfig, kept to sign for the config and vault PDAs after ownership changes
+  pending_owner: Address, // 32 proposed prog_owner to accept the ownership
+  str_u8array: [u8; 32], // 32
+  fee: [u8; 8],        // 8 for u64, in basis points of the escrow take amount
+  sol_balance: [u8; 8], // 8
   token_balance: [u8; 8], // 8
-  updated_at: [u8; 4],    // 4 for u32
-  is_authorized: bool,    // 1
-  status: u8,             // 1
-  vault_bump: u8,         // 1
-  bump: u8,               // 1
-} // padding: [u8; 6] since the struct size needs to be aligned to 32 bytes.
+  updated_at: [u8; 4], // 4 for u32
+  is_authorized: bool, // 1
+  fee_on_x: bool,      // 1 take the fee from the token_x leg, else from the token_y leg
+  status: u8,          // 1
+  vault_bump: u8,      // 1
+  bump: u8,            // 1
+  timelock_delay: [u8; 4], // 4 for u32 seconds between queueing and executing an Action. 0 for no timelock
+  ext_mask: [u8; 8],       // 8 for u64 bitmask of Token2022 mint extension types
+  ext_allowlist: bool, // 1 accept only the extensions in ext_mask, else reject the extensions in ext_mask
+  open_escrows: [u8; 4], // 4 for u32 escrows made on this config and not yet closed
+  open_positions: [u8; 4], // 4 for u32 User PDAs booked on this config holding a deposit or a redeemable balance
+} // 223, no padding since every field has an alignment of 1
 
 impl Config {
   pub const LEN: usize = core::mem::size_of::<Self>();
   pub const SEED: &[u8] = b"config";
+  pub const DISCRIMINATOR: [u8; ACCOUNT_DISCRIMINATOR_SIZE] = *b"config\0\0";
+  pub const VERSION: u8 = 1;
+  pub const FEE_BPS_MAX: u64 = 10_000;
+  pub const PROG_OWNER_OFFSET: usize = core::mem::offset_of!(Config, prog_owner);
+  /// extensions that let a mint freeze, block or claw back the tokens held by the program
+  pub const DEFAULT_EXT_DENY: u64 = 1 << EXT_DEFAULT_ACCOUNT_STATE
+    | 1 << EXT_NON_TRANSFERABLE
+    | 1 << EXT_PERMANENT_DELEGATE
+    | 1 << EXT_TRANSFER_HOOK
+    | 1 << EXT_PAUSABLE;
   //Getters or Accessors: Safe Direct value copy, no reference created
-  pub fn mint0(&self) -> &Address {
-    &self.mint0
-  }
-  pub fn mint1(&self) -> &Address {
-    &self.mint1
-  }
-  pub fn mint2(&self) -> &Address {
-    &self.mint2
-  }
-  pub fn mint3(&self) -> &Address {
-    &self.mint3
-  }
-  pub fn mints(&self) -> [&Address; 4] {
-    [&self.mint0, &self.mint1, &self.mint2, &self.mint3]
+  pub fn version(&self) -> u8 {
+    self.version
   }
   pub fn vault(&self) -> &Address {
     &self.vault
@@ -54,8 +108,12 @@ impl Config {
   pub fn prog_owner(&self) -> &Address {
     &self.prog_owner
   }
-  pub fn admin(&self) -> &Address {
-    &self.admin
+  /// the config PDA is derived from [SEED, seed_owner, bump] and the vault from [VAULT_SEED, seed_owner, vault_bump]
+  pub fn seed_owner(&self) -> &Address {
+    &self.seed_owner
+  }
+  pub fn pending_owner(&self) -> &Address {
+    &self.pending_owner
   }
   pub fn str_u8array(&self) -> &[u8; 32] {
     &self.str_u8array
@@ -66,6 +124,9 @@ impl Config {
   pub fn is_authorized(&self) -> bool {
     self.is_authorized
   }
+  pub fn fee_on_x(&self) -> bool {
+    self.fee_on_x
+  }
   pub fn vault_bump(&self) -> u8 {
     self.vault_bump
   }
@@ -84,6 +145,43 @@ impl Config {
   pub fn updated_at(&self) -> u32 {
     u32::from_le_bytes(self.updated_at)
   }
+  pub fn timelock_delay(&self) -> u32 {
+    u32::from_le_bytes(self.timelock_delay)
+  }
+  pub fn ext_mask(&self) -> u64 {
+    u64::from_le_bytes(self.ext_mask)
+  }
+  pub fn ext_allowlist(&self) -> bool {
+    self.ext_allowlist
+  }
+  pub fn open_escrows(&self) -> u32 {
+    u32::from_le_bytes(self.open_escrows)
+  }
+  pub fn open_positions(&self) -> u32 {
+    u32::from_le_bytes(self.open_positions)
+  }
+  /// the config cannot be closed, even by force, while escrows or user positions still reference it
+  pub fn check_no_open_accounts(&self) -> ProgramResult {
+    if self.open_escrows() > 0 || self.open_positions() > 0 {
+      return Ee::ConfigHasOpenAccounts.e();
+    }
+    Ok(())
+  }
+  /// reject Token2022 mints with extensions denied by this config
+  pub fn check_mint_extensions(&self, mint: &AccountView) -> ProgramResult {
+    check_mint_ext_policy(mint, self.ext_mask(), self.ext_allowlist())
+  }
+  /// sensitive changes must be queued as an Action while a timelock delay is set
+  pub fn check_no_timelock(&self) -> ProgramResult {
+    if self.timelock_delay() > 0 {
+      return Ee::TimelockRequired.e();
+    }
+    Ok(())
+  }
+  /// fee in basis points of amount, rounded up in favor of the protocol
+  pub fn fee_amount(&self, amount: u64) -> Result<u64, ProgramError> {
+    mul_div_ceil(amount, self.fee(), Self::FEE_BPS_MAX)
+  }
   /* pub fn close_authority(&self) -> Option<&Address> {
       if self.has_close_authority() {
           Some(self.close_authority_unchecked())
@@ -91,6 +189,13 @@ impl Config {
           None
       }
   }*/
+  /// users cannot deposit, pay, redeem, make or take while paused. Cancel and withdraw are still allowed
+  pub fn check_not_paused(&self) -> ProgramResult {
+    if self.status() == Status::Paused {
+      return Ee::ProgramPaused.e();
+    }
+    Ok(())
+  }
   //----------== read
   pub fn check(pda: &AccountView) -> ProgramResult {
     if pda.data_len() != Self::LEN {
@@ -101,65 +206,111 @@ impl Config {
         return Ee::ConfigIsForeign.e();
       }
     }
+    check_header(pda, &Self::DISCRIMINATOR, Self::VERSION)?;
     // CHECK alignment for the most restrictive field (u64 in this case)... Alignment requirement checking can be removed ONLY IF you know all numbers are using u8 arrays
     /*if (pda.borrow_mut_data_unchecked().as_ptr() as usize) % core::mem::align_of::<Self>() != 0 { return Err();  }*/
     Ok(())
   }
+  /// check a config of the baseline or the current layout is owned by prog_owner, and return its version. The headerless baseline layout is version 0
+  pub fn check_migratable(pda: &AccountView, prog_owner: &Address) -> Result<u8, ProgramError> {
+    let data = pda.try_borrow()?;
+    if data.len() == ConfigBaseline::LEN
+      && data[..ACCOUNT_DISCRIMINATOR_SIZE].ne(&Self::DISCRIMINATOR)
+    {
+      let offset = ConfigBaseline::PROG_OWNER_OFFSET;
+      if data[offset..offset + 32].ne(prog_owner.as_ref()) {
+        return Err(Ee::OnlyProgOwner.into());
+      }
+      return Ok(0);
+    }
+    if data.len() < Self::PROG_OWNER_OFFSET + 32
+      || data[..ACCOUNT_DISCRIMINATOR_SIZE].ne(&Self::DISCRIMINATOR)
+    {
+      return Err(Ee::AccountDiscriminator.into());
+    }
+    let version = data[ACCOUNT_DISCRIMINATOR_SIZE];
+    if version != Self::VERSION {
+      return Err(Ee::AccountVersion.into());
+    }
+    if data[Self::PROG_OWNER_OFFSET..Self::PROG_OWNER_OFFSET + 32].ne(prog_owner.as_ref()) {
+      return Err(Ee::OnlyProgOwner.into());
+    }
+    Ok(version)
+  }
+  /// rewrite the baseline layout, read before resizing to LEN, into the current one. The unused admin is dropped
+  pub fn rewrite_from_baseline(
+    pda: &AccountView,
+    old: &ConfigBaseline,
+    seed_owner: &Address,
+  ) -> ProgramResult {
+    log!("version 0: write the header and move every field");
+    pda.try_borrow_mut()?.fill(0);
+    let config = Self::init_account_view(pda)?;
+    config.vault = old.vault;
+    config.prog_owner = old.prog_owner;
+    config.seed_owner = *seed_owner;
+    config.str_u8array = old.str_u8array;
+    config.fee = old.fee;
+    config.sol_balance = old.sol_balance;
+    config.token_balance = old.token_balance;
+    config.updated_at = old.updated_at;
+    config.is_authorized = old.is_authorized;
+    config.status = old.status;
+    config.vault_bump = old.vault_bump;
+    config.bump = old.bump;
+    config.ext_mask = Self::DEFAULT_EXT_DENY.to_le_bytes();
+    Ok(())
+  }
   //better to use setters below
+  /// write the header of a newly created account, then load it
+  #[allow(clippy::mut_from_ref)]
+  pub fn init_account_view(pda: &AccountView) -> Result<&mut Self, ProgramError> {
+    write_header(pda, &Self::DISCRIMINATOR, Self::VERSION)?;
+    Self::from_account_view(pda)
+  }
+  /// The data lives in the runtime's account buffer rather than in `pda`, so handing out `&mut Self`
+  /// from `&AccountView` mirrors pinocchio; callers must not hold two views of one account at once.
+  #[allow(clippy::mut_from_ref)]
   pub fn from_account_view(pda: &AccountView) -> Result<&mut Self, ProgramError> {
     Self::check(pda)?;
     unsafe { Ok(&mut *(pda.borrow_unchecked_mut().as_ptr() as *mut Self)) }
-    /*Ok(Ref::map(account_info.try_borrow_data()?, |data| unsafe {
-        Self::from_bytes_unchecked(data)
-    })) */
   }
-  //Must: there are no mutable borrows of the account data
+  /// # Safety
+  /// There must be no mutable borrows of the account data while the returned reference lives.
   #[inline]
   pub unsafe fn from_account_info_unchecked(pda: &AccountView) -> Result<&Self, ProgramError> {
     Self::check(pda)?;
-    Ok(Self::from_bytes_unchecked(pda.borrow_unchecked_mut()))
-    //Ok(&mut *(pda.borrow_mut_data_unchecked().as_ptr() as *mut Self))
+    Ok(Self::from_bytes_unchecked(pda.borrow_unchecked()))
   }
-  /// The caller must ensure that `bytes` contains a valid representation of `Account`, and
-  /// it is properly aligned to be interpreted as an instance of `Account`.
-  /// At the moment `Account` has an alignment of 1 byte.
+  /// # Safety
+  /// The caller must ensure that `bytes` contains a valid representation of `Config`, and
+  /// it is properly aligned to be interpreted as an instance of `Config`.
+  /// At the moment `Config` has an alignment of 1 byte.
   /// This method does not perform a length validation.
   pub unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self {
-    &*(bytes.as_ptr() as *const &Config)
+    &*(bytes.as_ptr() as *const Config)
   }
   //----------== Setters
-  pub fn set_mint0(&mut self, pkey: &Address) {
-    self.mint0 = *pkey;
-  }
-  pub fn set_mint1(&mut self, pkey: &Address) {
-    self.mint1 = *pkey;
-  }
-  pub fn set_mint2(&mut self, pkey: &Address) {
-    self.mint2 = *pkey;
-  }
-  pub fn set_mint3(&mut self, pkey: &Address) {
-    self.mint3 = *pkey;
-  }
-  pub fn set_mints(&mut self, mints: [&Address; 4]) {
-    self.mint0 = *mints[0];
-    self.mint1 = *mints[1];
-    self.mint2 = *mints[2];
-    self.mint3 = *mints[3];
-  }
   pub fn set_vault(&mut self, pkey: &Address) {
     self.vault = *pkey;
   }
   pub fn set_prog_owner(&mut self, pkey: &Address) {
     self.prog_owner = *pkey;
   }
-  pub fn set_admin(&mut self, pkey: &Address) {
-    self.admin = *pkey;
+  pub fn set_seed_owner(&mut self, pkey: &Address) {
+    self.seed_owner = *pkey;
+  }
+  pub fn set_pending_owner(&mut self, pkey: &Address) {
+    self.pending_owner = *pkey;
   }
   pub fn set_str_u8array(&mut self, str_u8array: [u8; 32]) {
     self.str_u8array = str_u8array;
   }
   pub fn set_fee(&mut self, amt: u64) -> ProgramResult {
     none_zero_u64(amt)?;
+    if amt > Self::FEE_BPS_MAX {
+      return Ee::FeeBps.e();
+    }
     self.fee = amt.to_le_bytes();
     Ok(())
   }
@@ -169,6 +320,41 @@ impl Config {
   pub fn set_token_balance(&mut self, amt: u64) {
     self.token_balance = amt.to_le_bytes();
   }
+  /// SOL deposited by users into their vaults
+  pub fn add_sol_balance(&mut self, amt: u64) -> ProgramResult {
+    let balance = self
+      .sol_balance()
+      .checked_add(amt)
+      .ok_or(ProgramError::ArithmeticOverflow)?;
+    self.set_sol_balance(balance);
+    Ok(())
+  }
+  /// only vaults bound to this config are booked here, so withdrawing more than was deposited is an error
+  pub fn sub_sol_balance(&mut self, amt: u64) -> ProgramResult {
+    let balance = self
+      .sol_balance()
+      .checked_sub(amt)
+      .ok_or(ProgramError::InsufficientFunds)?;
+    self.set_sol_balance(balance);
+    Ok(())
+  }
+  /// tokens held by the config vault from payments and fees, summed in raw units across mints. It is only reported, never used to gate a transfer
+  pub fn add_token_balance(&mut self, amt: u64) -> ProgramResult {
+    let balance = self
+      .token_balance()
+      .checked_add(amt)
+      .ok_or(ProgramError::ArithmeticOverflow)?;
+    self.set_token_balance(balance);
+    Ok(())
+  }
+  /// saturates at 0 so bookkeeping drift never traps a redemption; ConfigReconcile reports the drift
+  pub fn sub_token_balance(&mut self, amt: u64) {
+    let balance = self.token_balance();
+    if balance < amt {
+      log!("token_balance drift: {} < {}", balance, amt);
+    }
+    self.set_token_balance(balance.saturating_sub(amt));
+  }
   pub fn set_updated_at(&mut self, amt: u32) {
     self.updated_at = amt.to_le_bytes();
   }
@@ -184,6 +370,45 @@ impl Config {
   pub fn set_is_authorized(&mut self, boo: bool) {
     self.is_authorized = boo;
   }
+  pub fn set_fee_on_x(&mut self, boo: bool) {
+    self.fee_on_x = boo;
+  }
+  pub fn set_timelock_delay(&mut self, delay: u32) -> ProgramResult {
+    if delay > Action::MAX_DELAY {
+      return Ee::TimelockDelay.e();
+    }
+    self.timelock_delay = delay.to_le_bytes();
+    Ok(())
+  }
+  pub fn set_ext_policy(&mut self, ext_mask: u64, allowlist: bool) {
+    self.ext_mask = ext_mask.to_le_bytes();
+    self.ext_allowlist = allowlist;
+  }
+  pub fn add_open_escrow(&mut self) -> ProgramResult {
+    let count = self
+      .open_escrows()
+      .checked_add(1)
+      .ok_or(ProgramError::ArithmeticOverflow)?;
+    self.open_escrows = count.to_le_bytes();
+    Ok(())
+  }
+  /// saturates at 0 like sub_sol_balance, so an escrow can always be closed
+  pub fn sub_open_escrow(&mut self) {
+    self.open_escrows = self.open_escrows().saturating_sub(1).to_le_bytes();
+  }
+  /// count a User PDA as open while it holds a deposit or a redeemable balance. Call with its state before and after an update
+  pub fn update_open_positions(&mut self, was_open: bool, is_open: bool) -> ProgramResult {
+    let count = match (was_open, is_open) {
+      (false, true) => self
+        .open_positions()
+        .checked_add(1)
+        .ok_or(ProgramError::ArithmeticOverflow)?,
+      (true, false) => self.open_positions().saturating_sub(1),
+      _ => return Ok(()),
+    };
+    self.open_positions = count.to_le_bytes();
+    Ok(())
+  }
 }
 
 //#[repr(C)] keeps the struct layout the same across different architectures
@@ -210,16 +435,55 @@ impl From<u8> for Status {
 } //Status::Uninitialized as u8
 
 //------------==
+/// Config of the baseline release: no header, four fixed mint slots and an admin. Only read by ConfigMigrate
 #[derive(Clone, Debug)]
-#[repr(C)] //0..8 	Discriminator 	8 bytes
+#[repr(C)]
+pub struct ConfigBaseline {
+  mints: [Address; 4],    // 128 replaced by the Mint Registry PDAs
+  vault: Address,         // 32
+  prog_owner: Address,    // 32
+  admin: Address,         // 32 dropped in the current layout
+  str_u8array: [u8; 32],  // 32
+  fee: [u8; 8],           // 8 for u64
+  sol_balance: [u8; 8],   // 8
+  token_balance: [u8; 8], // 8
+  updated_at: [u8; 4],    // 4 for u32
+  is_authorized: bool,    // 1
+  status: u8,             // 1
+  vault_bump: u8,         // 1
+  bump: u8,               // 1
+} // 288, no padding
+impl ConfigBaseline {
+  pub const LEN: usize = core::mem::size_of::<Self>();
+  pub const PROG_OWNER_OFFSET: usize = core::mem::offset_of!(ConfigBaseline, prog_owner);
+
+  /// copy the baseline fields out of a config checked by Config::check_migratable
+  pub fn read(pda: &AccountView) -> Result<Self, ProgramError> {
+    let data = pda.try_borrow()?;
+    if data.len() != Self::LEN {
+      return Err(Ee::ConfigDataLengh.into());
+    }
+    Ok(unsafe { (data.as_ptr() as *const Self).read_unaligned() })
+  }
+}
+
+//------------==
+#[derive(Clone, Debug)]
+#[repr(C)]
 pub struct Escrow {
+  discriminator: [u8; 8], //8
+  version: u8,            //1
+  config: Address, //32 the config this offer was made on; its pause and extension policy apply at every step
   maker: Address, //32; PDA needs at least 1 Address to keep PDA addresses from being exhausted by all users using u64. This also gives each user his own Escrow id.
-  //taker: Address,   //32 hidden from maker
-  mint_x: Address,   //32
-  mint_y: Address,   //32
+  taker: Address, //32 designated taker; zeroed address means anyone can take
+  mint_x: Address, //32
+  mint_y: Address, //32
   amount_x: [u8; 8], //8 the offered amount from maker. This field gives taker easier way to view
-  amount_y: [u8; 8], //8 the wanted amount to maker. The token_y price in mint_x = this Escrow PDA ATA_X amount / amount_y
+  amount_y: [u8; 8], //8 the wanted amount to maker. The token_y price in mint_x = amount_y / amount_x, fixed for partial fills
+  remaining_x: [u8; 8], //8 token_x not yet taken
+  received_y: [u8; 8], //8 token_y paid by takers, not yet withdrawn by maker
   id: [u8; 8],       //8
+  expires_at: [u8; 4], //4 for u32; 0 means the offer never expires
   decimal_x: u8,     //1
   decimal_y: u8,     //1
   bump: u8,          //1
@@ -229,10 +493,27 @@ impl Escrow {
   //pub const LEN: usize = 32 + 32 + 32 + 8 +8+ 1;
 
   pub const SEED: &[u8] = b"escrow";
+  pub const DISCRIMINATOR: [u8; ACCOUNT_DISCRIMINATOR_SIZE] = *b"escrow\0\0";
+  pub const VERSION: u8 = 1;
 
+  pub fn config(&self) -> &Address {
+    &self.config
+  }
+  pub fn check_config(&self, config_pda: &AccountView) -> ProgramResult {
+    if self.config().ne(config_pda.address()) {
+      return Ee::EscrowConfig.e();
+    }
+    Ok(())
+  }
   pub fn maker(&self) -> &Address {
     &self.maker
   }
+  pub fn taker(&self) -> &Address {
+    &self.taker
+  }
+  pub fn is_public(&self) -> bool {
+    self.taker.as_array() == &[0u8; 32]
+  }
   pub fn mint_x(&self) -> &Address {
     &self.mint_x
   }
@@ -248,6 +529,19 @@ impl Escrow {
   pub fn amount_y(&self) -> u64 {
     u64::from_le_bytes(self.amount_y)
   }
+  pub fn remaining_x(&self) -> u64 {
+    u64::from_le_bytes(self.remaining_x)
+  }
+  pub fn received_y(&self) -> u64 {
+    u64::from_le_bytes(self.received_y)
+  }
+  pub fn expires_at(&self) -> u32 {
+    u32::from_le_bytes(self.expires_at)
+  }
+  pub fn is_expired(&self, time: u32) -> bool {
+    let expires_at = self.expires_at();
+    expires_at > 0 && time >= expires_at
+  }
   pub fn decimal_x(&self) -> u8 {
     self.decimal_x
   }
@@ -257,9 +551,15 @@ impl Escrow {
   pub fn bump(&self) -> u8 {
     self.bump
   }
+  pub fn set_config(&mut self, pkey: &Address) {
+    self.config = *pkey;
+  }
   pub fn set_maker(&mut self, pkey: &Address) {
     self.maker = *pkey;
   }
+  pub fn set_taker(&mut self, pkey: &Address) {
+    self.taker = *pkey;
+  }
   pub fn set_mint_x(&mut self, pkey: &Address) {
     self.mint_x = *pkey;
   }
@@ -280,6 +580,15 @@ impl Escrow {
     self.amount_y = amt.to_le_bytes();
     Ok(())
   }
+  pub fn set_remaining_x(&mut self, amt: u64) {
+    self.remaining_x = amt.to_le_bytes();
+  }
+  pub fn set_received_y(&mut self, amt: u64) {
+    self.received_y = amt.to_le_bytes();
+  }
+  pub fn set_expires_at(&mut self, amt: u32) {
+    self.expires_at = amt.to_le_bytes();
+  }
   pub fn set_decimal_x(&mut self, amt: u8) {
     self.decimal_x = amt;
   }
@@ -298,19 +607,816 @@ impl Escrow {
         return Ee::EscrowIsForeign.e();
       }
     }
+    check_header(pda, &Self::DISCRIMINATOR, Self::VERSION)?;
     Ok(())
   }
+  /// write the header of a newly created account, then load it
+  #[allow(clippy::mut_from_ref)]
+  pub fn init_account_view(pda: &AccountView) -> Result<&mut Self, ProgramError> {
+    write_header(pda, &Self::DISCRIMINATOR, Self::VERSION)?;
+    Self::from_account_view(pda)
+  }
+  #[allow(clippy::mut_from_ref)]
   pub fn from_account_view(pda: &AccountView) -> Result<&mut Self, ProgramError> {
     Self::check(pda)?;
     unsafe { Ok(&mut *(pda.borrow_unchecked_mut().as_ptr() as *mut Self)) }
   }
+  /// # Safety
+  /// `bytes` must hold a valid `Escrow`; its alignment is 1 byte and the length is not checked.
   pub unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self {
-    &*(bytes.as_ptr() as *const &&Escrow)
+    &*(bytes.as_ptr() as *const Escrow)
   }
+  /// # Safety
+  /// There must be no mutable borrows of the account data while the returned reference lives.
   #[inline]
   pub unsafe fn from_account_info_unchecked(pda: &AccountView) -> Result<&Self, ProgramError> {
     Self::check(pda)?;
-    Ok(Self::from_bytes_unchecked(pda.borrow_unchecked_mut()))
-    //unsafe { Ok(&mut *(pda.borrow_mut_data_unchecked().as_ptr() as *mut Self)) }
+    Ok(Self::from_bytes_unchecked(pda.borrow_unchecked()))
+  }
+}
+
+//------------==
+/// Admin PDA: one per config and admin address, granted and revoked by the prog_owner of that config
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct Admin {
+  discriminator: [u8; 8], //8
+  version: u8,            //1
+  config: Address,        //32 the config PDA these permissions apply to
+  admin: Address,         //32
+  permissions: [u8; 4],   //4 for u32 bitmask
+  updated_at: [u8; 4],    //4 for u32
+  bump: u8,               //1
+}
+impl Admin {
+  pub const LEN: usize = core::mem::size_of::<Admin>();
+  pub const SEED: &[u8] = b"admin";
+  pub const DISCRIMINATOR: [u8; ACCOUNT_DISCRIMINATOR_SIZE] = *b"admin\0\0\0";
+  pub const VERSION: u8 = 1;
+  //permission bits
+  pub const PAUSE: u32 = 1;
+  pub const FEE: u32 = 1 << 1;
+  pub const MINTS: u32 = 1 << 2;
+  pub const TREASURY: u32 = 1 << 3;
+  pub const ALL: u32 = Self::PAUSE | Self::FEE | Self::MINTS | Self::TREASURY;
+
+  pub fn config(&self) -> &Address {
+    &self.config
+  }
+  pub fn admin(&self) -> &Address {
+    &self.admin
+  }
+  pub fn permissions(&self) -> u32 {
+    u32::from_le_bytes(self.permissions)
+  }
+  pub fn has(&self, permission: u32) -> bool {
+    self.permissions() & permission == permission
+  }
+  pub fn updated_at(&self) -> u32 {
+    u32::from_le_bytes(self.updated_at)
+  }
+  pub fn bump(&self) -> u8 {
+    self.bump
+  }
+  pub fn set_config(&mut self, pkey: &Address) {
+    self.config = *pkey;
+  }
+  pub fn set_admin(&mut self, pkey: &Address) {
+    self.admin = *pkey;
+  }
+  pub fn set_permissions(&mut self, permissions: u32) {
+    self.permissions = permissions.to_le_bytes();
+  }
+  pub fn set_updated_at(&mut self, amt: u32) {
+    self.updated_at = amt.to_le_bytes();
+  }
+  pub fn set_bump(&mut self, amt: u8) {
+    self.bump = amt;
+  }
+  pub fn check(pda: &AccountView) -> ProgramResult {
+    if pda.data_len() != Self::LEN {
+      return Ee::AdminDataLengh.e();
+    }
+    unsafe {
+      if pda.owner().ne(&PROG_ADDR) {
+        return Ee::AdminPDA.e();
+      }
+    }
+    check_header(pda, &Self::DISCRIMINATOR, Self::VERSION)?;
+    Ok(())
+  }
+  /// write the header of a newly created account, then load it
+  #[allow(clippy::mut_from_ref)]
+  pub fn init_account_view(pda: &AccountView) -> Result<&mut Self, ProgramError> {
+    write_header(pda, &Self::DISCRIMINATOR, Self::VERSION)?;
+    Self::from_account_view(pda)
+  }
+  #[allow(clippy::mut_from_ref)]
+  pub fn from_account_view(pda: &AccountView) -> Result<&mut Self, ProgramError> {
+    Self::check(pda)?;
+    unsafe { Ok(&mut *(pda.borrow_unchecked_mut().as_ptr() as *mut Self)) }
+  }
+  /// the prog_owner has all permissions; any other signer needs its own Admin PDA granted on this config
+  pub fn signer_permissions(
+    config_pda: &AccountView,
+    config: &Config,
+    signer: &AccountView,
+    admin_pda: &AccountView,
+  ) -> Result<u32, ProgramError> {
+    if config.prog_owner().eq(signer.address()) {
+      return Ok(Self::ALL);
+    }
+    let admin = Self::from_account_view(admin_pda)?;
+    if admin.admin().ne(signer.address()) {
+      return Err(Ee::OnlyAdmin.into());
+    }
+    if admin.config().ne(config_pda.address()) {
+      return Err(Ee::AdminConfig.into());
+    }
+    Ok(admin.permissions())
+  }
+}
+
+//------------==
+/// User PDA: one per config, user and mint, recording the user's token positions in the vault booked on that config
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct User {
+  discriminator: [u8; 8],   //8
+  version: u8,              //1
+  config: Address,          //32 the config whose Mint Registry books these positions, set when made
+  user: Address,            //32
+  mint: Address,            //32
+  deposited: [u8; 8],       //8 current amount in the user vault
+  redeemable: [u8; 8],      //8 amount the user may redeem from the config vault
+  total_deposited: [u8; 8], //8 lifetime
+  total_withdrawn: [u8; 8], //8 lifetime
+  total_paid: [u8; 8],      //8 lifetime
+  total_redeemed: [u8; 8],  //8 lifetime
+  updated_at: [u8; 4],      //4 for u32, the last activity
+  bump: u8,                 //1
+}
+impl User {
+  pub const LEN: usize = core::mem::size_of::<User>();
+  pub const SEED: &[u8] = b"user";
+  pub const DISCRIMINATOR: [u8; ACCOUNT_DISCRIMINATOR_SIZE] = *b"user\0\0\0\0";
+  pub const VERSION: u8 = 1;
+
+  pub fn config(&self) -> &Address {
+    &self.config
+  }
+  pub fn check_config(&self, config: &Address) -> ProgramResult {
+    if self.config().ne(config) {
+      return Ee::UserConfig.e();
+    }
+    Ok(())
+  }
+  pub fn user(&self) -> &Address {
+    &self.user
+  }
+  pub fn mint(&self) -> &Address {
+    &self.mint
+  }
+  pub fn deposited(&self) -> u64 {
+    u64::from_le_bytes(self.deposited)
+  }
+  pub fn redeemable(&self) -> u64 {
+    u64::from_le_bytes(self.redeemable)
+  }
+  pub fn total_deposited(&self) -> u64 {
+    u64::from_le_bytes(self.total_deposited)
+  }
+  pub fn total_withdrawn(&self) -> u64 {
+    u64::from_le_bytes(self.total_withdrawn)
+  }
+  pub fn total_paid(&self) -> u64 {
+    u64::from_le_bytes(self.total_paid)
+  }
+  pub fn total_redeemed(&self) -> u64 {
+    u64::from_le_bytes(self.total_redeemed)
+  }
+  /// a position is open while it holds a deposit or a redeemable balance, which keeps its config from being closed
+  pub fn is_open(&self) -> bool {
+    self.deposited() > 0 || self.redeemable() > 0
+  }
+  pub fn updated_at(&self) -> u32 {
+    u32::from_le_bytes(self.updated_at)
+  }
+  pub fn bump(&self) -> u8 {
+    self.bump
+  }
+  pub fn set_config(&mut self, pkey: &Address) {
+    self.config = *pkey;
+  }
+  pub fn set_user(&mut self, pkey: &Address) {
+    self.user = *pkey;
+  }
+  pub fn set_mint(&mut self, pkey: &Address) {
+    self.mint = *pkey;
+  }
+  pub fn set_updated_at(&mut self, amt: u32) {
+    self.updated_at = amt.to_le_bytes();
+  }
+  pub fn set_bump(&mut self, amt: u8) {
+    self.bump = amt;
+  }
+  pub fn add_deposit(&mut self, amt: u64) -> ProgramResult {
+    let deposited = self.deposited().checked_add(amt);
+    let total = self.total_deposited().checked_add(amt);
+    let (Some(deposited), Some(total)) = (deposited, total) else {
+      return Err(ProgramError::ArithmeticOverflow);
+    };
+    self.deposited = deposited.to_le_bytes();
+    self.total_deposited = total.to_le_bytes();
+    Ok(())
+  }
+  pub fn sub_withdrawal(&mut self, amt: u64) -> ProgramResult {
+    let deposited = self
+      .deposited()
+      .checked_sub(amt)
+      .ok_or(Ee::UserInsuffDeposit)?;
+    let total = self
+      .total_withdrawn()
+      .checked_add(amt)
+      .ok_or(ProgramError::ArithmeticOverflow)?;
+    self.deposited = deposited.to_le_bytes();
+    self.total_withdrawn = total.to_le_bytes();
+    Ok(())
+  }
+  /// paying into the config vault credits the same amount as redeemable
+  pub fn add_paid(&mut self, amt: u64) -> ProgramResult {
+    let total = self.total_paid().checked_add(amt);
+    let redeemable = self.redeemable().checked_add(amt);
+    let (Some(total), Some(redeemable)) = (total, redeemable) else {
+      return Err(ProgramError::ArithmeticOverflow);
+    };
+    self.total_paid = total.to_le_bytes();
+    self.redeemable = redeemable.to_le_bytes();
+    Ok(())
+  }
+  pub fn add_redeemable(&mut self, amt: u64) -> ProgramResult {
+    let redeemable = self
+      .redeemable()
+      .checked_add(amt)
+      .ok_or(ProgramError::ArithmeticOverflow)?;
+    self.redeemable = redeemable.to_le_bytes();
+    Ok(())
+  }
+  pub fn sub_redeemed(&mut self, amt: u64) -> ProgramResult {
+    let redeemable = self
+      .redeemable()
+      .checked_sub(amt)
+      .ok_or(Ee::UserInsuffRedeemable)?;
+    let total = self
+      .total_redeemed()
+      .checked_add(amt)
+      .ok_or(ProgramError::ArithmeticOverflow)?;
+    self.redeemable = redeemable.to_le_bytes();
+    self.total_redeemed = total.to_le_bytes();
+    Ok(())
+  }
+  pub fn check(pda: &AccountView) -> ProgramResult {
+    if pda.data_len() != Self::LEN {
+      return Ee::UserDataLengh.e();
+    }
+    unsafe {
+      if pda.owner().ne(&PROG_ADDR) {
+        return Ee::UserPDA.e();
+      }
+    }
+    check_header(pda, &Self::DISCRIMINATOR, Self::VERSION)?;
+    Ok(())
+  }
+  /// write the header of a newly created account, then load it
+  #[allow(clippy::mut_from_ref)]
+  pub fn init_account_view(pda: &AccountView) -> Result<&mut Self, ProgramError> {
+    write_header(pda, &Self::DISCRIMINATOR, Self::VERSION)?;
+    Self::from_account_view(pda)
+  }
+  #[allow(clippy::mut_from_ref)]
+  pub fn from_account_view(pda: &AccountView) -> Result<&mut Self, ProgramError> {
+    Self::check(pda)?;
+    unsafe { Ok(&mut *(pda.borrow_unchecked_mut().as_ptr() as *mut Self)) }
+  }
+}
+
+//------------==
+/// Mint Registry PDA: one per config and accepted mint, made by admins of that config with the MINTS permission
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct MintReg {
+  discriminator: [u8; 8], //8
+  version: u8,            //1
+  config: Address,        //32 the config whose admins manage this mint
+  mint: Address,          //32
+  deposit_cap: [u8; 8],   //8 max total deposited in user vaults. 0 for no cap
+  deposited: [u8; 8],     //8 total deposited in user vaults
+  updated_at: [u8; 4],    //4 for u32
+  decimals: u8,           //1 recorded at registration
+  is_enabled: bool,       //1
+  bump: u8,               //1
+}
+impl MintReg {
+  pub const LEN: usize = core::mem::size_of::<MintReg>();
+  pub const SEED: &[u8] = b"mint_reg";
+  pub const DISCRIMINATOR: [u8; ACCOUNT_DISCRIMINATOR_SIZE] = *b"mint_reg";
+  pub const VERSION: u8 = 1;
+
+  pub fn config(&self) -> &Address {
+    &self.config
+  }
+  pub fn mint(&self) -> &Address {
+    &self.mint
+  }
+  pub fn deposit_cap(&self) -> u64 {
+    u64::from_le_bytes(self.deposit_cap)
+  }
+  pub fn deposited(&self) -> u64 {
+    u64::from_le_bytes(self.deposited)
+  }
+  pub fn updated_at(&self) -> u32 {
+    u32::from_le_bytes(self.updated_at)
+  }
+  pub fn decimals(&self) -> u8 {
+    self.decimals
+  }
+  pub fn is_enabled(&self) -> bool {
+    self.is_enabled
+  }
+  pub fn bump(&self) -> u8 {
+    self.bump
+  }
+  pub fn set_config(&mut self, pkey: &Address) {
+    self.config = *pkey;
+  }
+  pub fn set_mint(&mut self, pkey: &Address) {
+    self.mint = *pkey;
+  }
+  pub fn set_deposit_cap(&mut self, amt: u64) {
+    self.deposit_cap = amt.to_le_bytes();
+  }
+  pub fn set_updated_at(&mut self, amt: u32) {
+    self.updated_at = amt.to_le_bytes();
+  }
+  pub fn set_decimals(&mut self, amt: u8) {
+    self.decimals = amt;
+  }
+  pub fn set_is_enabled(&mut self, is_enabled: bool) {
+    self.is_enabled = is_enabled;
+  }
+  pub fn set_bump(&mut self, amt: u8) {
+    self.bump = amt;
+  }
+  pub fn check_enabled(&self) -> ProgramResult {
+    if !self.is_enabled {
+      return Ee::MintDisabled.e();
+    }
+    Ok(())
+  }
+  pub fn add_deposit(&mut self, amt: u64) -> ProgramResult {
+    let deposited = self
+      .deposited()
+      .checked_add(amt)
+      .ok_or(ProgramError::ArithmeticOverflow)?;
+    let cap = self.deposit_cap();
+    if cap > 0 && deposited > cap {
+      return Ee::MintDepositCap.e();
+    }
+    self.deposited = deposited.to_le_bytes();
+    Ok(())
+  }
+  pub fn sub_withdrawal(&mut self, amt: u64) -> ProgramResult {
+    let deposited = self
+      .deposited()
+      .checked_sub(amt)
+      .ok_or(ProgramError::ArithmeticOverflow)?;
+    self.deposited = deposited.to_le_bytes();
+    Ok(())
+  }
+  pub fn check(pda: &AccountView) -> ProgramResult {
+    if pda.data_len() != Self::LEN {
+      return Ee::MintRegDataLengh.e();
+    }
+    unsafe {
+      if pda.owner().ne(&PROG_ADDR) {
+        return Ee::MintRegPDA.e();
+      }
+    }
+    check_header(pda, &Self::DISCRIMINATOR, Self::VERSION)?;
+    Ok(())
+  }
+  /// write the header of a newly created account, then load it
+  #[allow(clippy::mut_from_ref)]
+  pub fn init_account_view(pda: &AccountView) -> Result<&mut Self, ProgramError> {
+    write_header(pda, &Self::DISCRIMINATOR, Self::VERSION)?;
+    Self::from_account_view(pda)
+  }
+  #[allow(clippy::mut_from_ref)]
+  pub fn from_account_view(pda: &AccountView) -> Result<&mut Self, ProgramError> {
+    Self::check(pda)?;
+    unsafe { Ok(&mut *(pda.borrow_unchecked_mut().as_ptr() as *mut Self)) }
+  }
+}
+
+//------------==
+/// Action PDA: a sensitive config change queued by an admin of that config. Anyone can execute it once eta has passed while the proposer still holds the permission, and the prog_owner can cancel it before then
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct Action {
+  discriminator: [u8; 8], //8
+  version: u8,            //1
+  config: Address,        //32 the config PDA this change applies to
+  proposer: Address,      //32 paid the rent, which goes back to the proposer on execute or cancel
+  payload_hash: [u8; 32], //32 sha256 of the payload given to ActionExecute
+  id: [u8; 8],            //8
+  eta: [u8; 4],           //4 for u32, the earliest execution time
+  kind: u8,               //1
+  bump: u8,               //1
+}
+impl Action {
+  pub const LEN: usize = core::mem::size_of::<Action>();
+  pub const SEED: &[u8] = b"action";
+  pub const DISCRIMINATOR: [u8; ACCOUNT_DISCRIMINATOR_SIZE] = *b"action\0\0";
+  pub const VERSION: u8 = 1;
+  pub const MAX_DELAY: u32 = 30 * 24 * 3600;
+  //kinds, each with its payload after the kind byte
+  pub const SET_FEE: u8 = 1; //fee: 8, fee_on_x: 1
+  pub const SET_DELAY: u8 = 2; //timelock_delay: 4
+  pub const MINT_ADD: u8 = 3; //mint: 32, deposit_cap: 8
+  pub const MINT_REMOVE: u8 = 4; //mint: 32
+  pub const ACCEPT_OWNER: u8 = 5; //multisig only: accept the ownership proposed to the multisig
+  pub const SET_EXT_POLICY: u8 = 6; //ext_mask: 8, allowlist: 1
+  pub const SET_RATE: u8 = 7; //mint: 32, rate: 2 for i16
+  pub const ADMIN_GRANT: u8 = 8; //admin: 32, permissions: 4
+  pub const ADMIN_REVOKE: u8 = 9; //admin: 32, permissions: 4
+  pub const PROPOSE_OWNER: u8 = 10; //new_owner: 32
+  pub const SET_STATUS: u8 = 11; //status: 1
+  pub const SET_LABEL: u8 = 12; //label: 32
+  pub const SET_AUTHORIZED: u8 = 13; //is_authorized: 1
+  pub const CLOSE_CONFIG: u8 = 14; //multisig only: force: 1, dest: 32
+  pub const MIGRATE_CONFIG: u8 = 15; //multisig only: seed_owner: 32
+
+  /// payload length including the kind byte
+  pub fn payload_len(kind: u8) -> Result<usize, ProgramError> {
+    match kind {
+      Self::SET_FEE => Ok(10),
+      Self::SET_DELAY => Ok(5),
+      Self::MINT_ADD => Ok(41),
+      Self::MINT_REMOVE => Ok(33),
+      Self::ACCEPT_OWNER => Ok(1),
+      Self::SET_EXT_POLICY => Ok(10),
+      Self::SET_RATE => Ok(35),
+      Self::ADMIN_GRANT | Self::ADMIN_REVOKE => Ok(37),
+      Self::PROPOSE_OWNER | Self::SET_LABEL | Self::MIGRATE_CONFIG => Ok(33),
+      Self::SET_STATUS | Self::SET_AUTHORIZED => Ok(2),
+      Self::CLOSE_CONFIG => Ok(34),
+      _ => Err(Ee::ActionKind.into()),
+    }
+  }
+  /// the delay, admins, ownership, label and authorization are owner-only; other kinds need the matching admin permission
+  pub fn permission(kind: u8) -> Result<Option<u32>, ProgramError> {
+    match kind {
+      Self::SET_DELAY
+      | Self::ADMIN_GRANT
+      | Self::ADMIN_REVOKE
+      | Self::PROPOSE_OWNER
+      | Self::SET_LABEL
+      | Self::SET_AUTHORIZED => Ok(None),
+      Self::SET_STATUS => Ok(Some(Admin::PAUSE)),
+      Self::SET_FEE | Self::SET_RATE => Ok(Some(Admin::FEE)),
+      Self::MINT_ADD | Self::MINT_REMOVE | Self::SET_EXT_POLICY => Ok(Some(Admin::MINTS)),
+      _ => Err(Ee::ActionKind.into()),
+    }
+  }
+  /// the proposer must hold the permission of kind on this config, when queued and again when executed
+  pub fn check_proposer(
+    kind: u8,
+    config_pda: &AccountView,
+    config: &Config,
+    proposer: &AccountView,
+    admin_pda: &AccountView,
+  ) -> ProgramResult {
+    match Self::permission(kind)? {
+      None => {
+        if config.prog_owner().ne(proposer.address()) {
+          return Ee::OnlyProgOwner.e();
+        }
+      }
+      Some(permission) => {
+        let permissions = Admin::signer_permissions(config_pda, config, proposer, admin_pda)?;
+        if permissions & permission == 0 {
+          return Ee::AdminPermission.e();
+        }
+      }
+    }
+    Ok(())
+  }
+  pub fn config(&self) -> &Address {
+    &self.config
+  }
+  pub fn proposer(&self) -> &Address {
+    &self.proposer
+  }
+  pub fn payload_hash(&self) -> &[u8; 32] {
+    &self.payload_hash
+  }
+  pub fn id(&self) -> u64 {
+    u64::from_le_bytes(self.id)
+  }
+  pub fn eta(&self) -> u32 {
+    u32::from_le_bytes(self.eta)
+  }
+  pub fn kind(&self) -> u8 {
+    self.kind
+  }
+  pub fn bump(&self) -> u8 {
+    self.bump
+  }
+  pub fn set_config(&mut self, pkey: &Address) {
+    self.config = *pkey;
+  }
+  pub fn set_proposer(&mut self, pkey: &Address) {
+    self.proposer = *pkey;
+  }
+  pub fn set_payload_hash(&mut self, hash: [u8; 32]) {
+    self.payload_hash = hash;
+  }
+  pub fn set_id(&mut self, id: u64) {
+    self.id = id.to_le_bytes();
+  }
+  pub fn set_eta(&mut self, eta: u32) {
+    self.eta = eta.to_le_bytes();
+  }
+  pub fn set_kind(&mut self, kind: u8) {
+    self.kind = kind;
+  }
+  pub fn set_bump(&mut self, amt: u8) {
+    self.bump = amt;
+  }
+  pub fn check_config(&self, config_pda: &AccountView) -> ProgramResult {
+    if self.config().ne(config_pda.address()) {
+      return Ee::ActionConfig.e();
+    }
+    Ok(())
+  }
+  pub fn check_ready(&self, time: u32) -> ProgramResult {
+    if time < self.eta() {
+      return Ee::ActionNotReady.e();
+    }
+    Ok(())
+  }
+  pub fn check(pda: &AccountView) -> ProgramResult {
+    if pda.data_len() != Self::LEN {
+      return Ee::ActionDataLengh.e();
+    }
+    unsafe {
+      if pda.owner().ne(&PROG_ADDR) {
+        return Ee::ActionPDA.e();
+      }
+    }
+    check_header(pda, &Self::DISCRIMINATOR, Self::VERSION)?;
+    Ok(())
+  }
+  /// write the header of a newly created account, then load it
+  #[allow(clippy::mut_from_ref)]
+  pub fn init_account_view(pda: &AccountView) -> Result<&mut Self, ProgramError> {
+    write_header(pda, &Self::DISCRIMINATOR, Self::VERSION)?;
+    Self::from_account_view(pda)
+  }
+  #[allow(clippy::mut_from_ref)]
+  pub fn from_account_view(pda: &AccountView) -> Result<&mut Self, ProgramError> {
+    Self::check(pda)?;
+    unsafe { Ok(&mut *(pda.borrow_unchecked_mut().as_ptr() as *mut Self)) }
+  }
+}
+
+//------------==
+/// Multisig PDA: an M-of-N signer set. Make it the prog_owner, or grant it an Admin PDA, to manage the config through MultisigTx proposals
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct Multisig {
+  discriminator: [u8; 8],                    //8
+  version: u8,                               //1
+  creator: Address,                          //32
+  signers: [Address; Multisig::MAX_SIGNERS], //32 * 10
+  id: [u8; 8],                               //8
+  signer_count: u8,                          //1
+  threshold: u8,                             //1
+  bump: u8,                                  //1
+}
+impl Multisig {
+  pub const LEN: usize = core::mem::size_of::<Multisig>();
+  pub const SEED: &[u8] = b"multisig";
+  pub const DISCRIMINATOR: [u8; ACCOUNT_DISCRIMINATOR_SIZE] = *b"multisig";
+  pub const VERSION: u8 = 1;
+  pub const MAX_SIGNERS: usize = 10;
+
+  pub fn creator(&self) -> &Address {
+    &self.creator
+  }
+  pub fn signers(&self) -> &[Address] {
+    &self.signers[..self.signer_count as usize]
+  }
+  pub fn id(&self) -> u64 {
+    u64::from_le_bytes(self.id)
+  }
+  pub fn signer_count(&self) -> u8 {
+    self.signer_count
+  }
+  pub fn threshold(&self) -> u8 {
+    self.threshold
+  }
+  pub fn bump(&self) -> u8 {
+    self.bump
+  }
+  pub fn set_creator(&mut self, pkey: &Address) {
+    self.creator = *pkey;
+  }
+  /// signers must be unique, and the threshold between 1 and the signer count
+  pub fn set_signers(&mut self, signers: &[Address], threshold: u8) -> ProgramResult {
+    if signers.is_empty() || signers.len() > Self::MAX_SIGNERS {
+      return Ee::MultisigSigner.e();
+    }
+    if threshold == 0 || threshold as usize > signers.len() {
+      return Ee::MultisigThreshold.e();
+    }
+    for (i, signer) in signers.iter().enumerate() {
+      if signer.eq(&Address::default()) {
+        return Ee::MultisigSigner.e();
+      }
+      if signers[..i].contains(signer) {
+        return Ee::DuplicateAccount.e();
+      }
+      self.signers[i] = *signer;
+    }
+    self.signer_count = signers.len() as u8;
+    self.threshold = threshold;
+    Ok(())
+  }
+  pub fn set_id(&mut self, id: u64) {
+    self.id = id.to_le_bytes();
+  }
+  pub fn set_bump(&mut self, amt: u8) {
+    self.bump = amt;
+  }
+  /// the signer's position in the signer set, which is its bit in MultisigTx approvals
+  pub fn signer_index(&self, signer: &Address) -> Result<u8, ProgramError> {
+    match self.signers().iter().position(|s| s.eq(signer)) {
+      Some(index) => Ok(index as u8),
+      None => Err(Ee::MultisigSigner.into()),
+    }
+  }
+  pub fn check(pda: &AccountView) -> ProgramResult {
+    if pda.data_len() != Self::LEN {
+      return Ee::MultisigDataLen.e();
+    }
+    unsafe {
+      if pda.owner().ne(&PROG_ADDR) {
+        return Ee::MultisigPDA.e();
+      }
+    }
+    check_header(pda, &Self::DISCRIMINATOR, Self::VERSION)?;
+    Ok(())
+  }
+  /// write the header of a newly created account, then load it
+  #[allow(clippy::mut_from_ref)]
+  pub fn init_account_view(pda: &AccountView) -> Result<&mut Self, ProgramError> {
+    write_header(pda, &Self::DISCRIMINATOR, Self::VERSION)?;
+    Self::from_account_view(pda)
+  }
+  #[allow(clippy::mut_from_ref)]
+  pub fn from_account_view(pda: &AccountView) -> Result<&mut Self, ProgramError> {
+    Self::check(pda)?;
+    unsafe { Ok(&mut *(pda.borrow_unchecked_mut().as_ptr() as *mut Self)) }
+  }
+}
+
+//------------==
+/// MultisigTx PDA: a config change proposed to a Multisig. Its signers approve it; once the threshold is met it waits the config timelock delay, then anyone can execute it
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct MultisigTx {
+  discriminator: [u8; 8], //8
+  version: u8,            //1
+  config: Address,        //32 the config this change targets, whose timelock delay applies
+  multisig: Address,      //32
+  proposer: Address,      //32 paid the rent, which goes back to the proposer on execute or cancel
+  payload_hash: [u8; 32], //32 sha256 of the payload given to MultisigExecute, with the same kinds as Action
+  id: [u8; 8],            //8
+  approvals: [u8; 2],     //2 for u16, one bit per signer index
+  eta: [u8; 4],           //4 for u32, 0 until the threshold is met
+  kind: u8,               //1
+  bump: u8,               //1
+}
+impl MultisigTx {
+  pub const LEN: usize = core::mem::size_of::<MultisigTx>();
+  pub const SEED: &[u8] = b"multisig_tx";
+  pub const DISCRIMINATOR: [u8; ACCOUNT_DISCRIMINATOR_SIZE] = *b"msig_tx\0";
+  pub const VERSION: u8 = 1;
+
+  pub fn config(&self) -> &Address {
+    &self.config
+  }
+  pub fn check_config(&self, config_pda: &AccountView) -> ProgramResult {
+    if self.config().ne(config_pda.address()) {
+      return Ee::MultisigTxConfig.e();
+    }
+    Ok(())
+  }
+  pub fn multisig(&self) -> &Address {
+    &self.multisig
+  }
+  pub fn proposer(&self) -> &Address {
+    &self.proposer
+  }
+  pub fn payload_hash(&self) -> &[u8; 32] {
+    &self.payload_hash
+  }
+  pub fn id(&self) -> u64 {
+    u64::from_le_bytes(self.id)
+  }
+  pub fn approvals(&self) -> u16 {
+    u16::from_le_bytes(self.approvals)
+  }
+  pub fn approval_count(&self) -> u32 {
+    self.approvals().count_ones()
+  }
+  pub fn eta(&self) -> u32 {
+    u32::from_le_bytes(self.eta)
+  }
+  pub fn kind(&self) -> u8 {
+    self.kind
+  }
+  pub fn bump(&self) -> u8 {
+    self.bump
+  }
+  pub fn set_config(&mut self, pkey: &Address) {
+    self.config = *pkey;
+  }
+  pub fn set_multisig(&mut self, pkey: &Address) {
+    self.multisig = *pkey;
+  }
+  pub fn set_proposer(&mut self, pkey: &Address) {
+    self.proposer = *pkey;
+  }
+  pub fn set_payload_hash(&mut self, hash: [u8; 32]) {
+    self.payload_hash = hash;
+  }
+  pub fn set_id(&mut self, id: u64) {
+    self.id = id.to_le_bytes();
+  }
+  pub fn set_kind(&mut self, kind: u8) {
+    self.kind = kind;
+  }
+  pub fn set_bump(&mut self, amt: u8) {
+    self.bump = amt;
+  }
+  /// record the approval of the signer at index. Once the threshold is met, the eta is set to time + delay
+  pub fn approve(&mut self, index: u8, threshold: u8, time: u32, delay: u32) -> ProgramResult {
+    let bit = 1u16 << index;
+    if self.approvals() & bit != 0 {
+      return Ee::MultisigApproved.e();
+    }
+    self.approvals = (self.approvals() | bit).to_le_bytes();
+    if self.eta() == 0 && self.approval_count() >= threshold as u32 {
+      let eta = time
+        .checked_add(delay)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+      self.eta = eta.to_le_bytes();
+      log!("threshold met, eta: {}", eta);
+    }
+    Ok(())
+  }
+  pub fn check_ready(&self, time: u32) -> ProgramResult {
+    if self.eta() == 0 {
+      return Ee::MultisigNotApproved.e();
+    }
+    if time < self.eta() {
+      return Ee::ActionNotReady.e();
+    }
+    Ok(())
+  }
+  pub fn check(pda: &AccountView) -> ProgramResult {
+    if pda.data_len() != Self::LEN {
+      return Ee::MultisigDataLen.e();
+    }
+    unsafe {
+      if pda.owner().ne(&PROG_ADDR) {
+        return Ee::MultisigPDA.e();
+      }
+    }
+    check_header(pda, &Self::DISCRIMINATOR, Self::VERSION)?;
+    Ok(())
+  }
+  /// write the header of a newly created account, then load it
+  #[allow(clippy::mut_from_ref)]
+  pub fn init_account_view(pda: &AccountView) -> Result<&mut Self, ProgramError> {
+    write_header(pda, &Self::DISCRIMINATOR, Self::VERSION)?;
+    Self::from_account_view(pda)
+  }
+  #[allow(clippy::mut_from_ref)]
+  pub fn from_account_view(pda: &AccountView) -> Result<&mut Self, ProgramError> {
+    Self::check(pda)?;
+    unsafe { Ok(&mut *(pda.borrow_unchecked_mut().as_ptr() as *mut Self)) }
   }
 }
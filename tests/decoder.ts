@@ -9,10 +9,13 @@ import type {
 import {
 	fixDecoderSize,
 	getAddressDecoder,
+	getArrayDecoder,
 	getBooleanDecoder,
+	getBytesDecoder,
 	getEnumDecoder,
 	getStructDecoder,
 	getU8Decoder,
+	getU16Decoder,
 	getU32Decoder,
 	getU64Decoder,
 	getUtf8Decoder,
@@ -38,43 +41,52 @@ const decoded = ammConfigDecoder.decode(bytes);*/
 //---------------== ConfigPDA
 //converted from Rust code. XyzAcct, xyzAcctDecoder, DecodedXyzAcct should all match in field order and types!
 export type ConfigAcct = {
-	mint0: Address;
-	mint1: Address;
-	mint2: Address;
-	mint3: Address;
+	discriminator: ReadonlyUint8Array;
+	version: number;
 	vault: Address;
 	progOwner: Address;
-	admin: Address;
+	seedOwner: Address;
+	pendingOwner: Address;
 	str: string;
 	fee: bigint;
 	solBalance: bigint;
 	tokenBalance: bigint;
 	updatedAt: number;
 	isAuthorized: boolean;
+	feeOnX: boolean;
 	status: Status;
 	vaultBump: number;
 	bump: number;
+	timelockDelay: number;
+	extMask: bigint;
+	extAllowlist: boolean;
+	openEscrows: number;
+	openPositions: number;
 };
 export const configAcctDecoder: FixedSizeDecoder<ConfigAcct> = getStructDecoder(
 	[
-		//["discriminator", fixDecoderSize(getBytesDecoder(), 4)],//only for accounts made by Anchor
-		["mint0", getAddressDecoder()],
-		["mint1", getAddressDecoder()],
-		["mint2", getAddressDecoder()],
-		["mint3", getAddressDecoder()],
+		["discriminator", fixDecoderSize(getBytesDecoder(), 8)],
+		["version", getU8Decoder()],
 		["vault", getAddressDecoder()],
 		["progOwner", getAddressDecoder()],
-		["admin", getAddressDecoder()],
+		["seedOwner", getAddressDecoder()],
+		["pendingOwner", getAddressDecoder()],
 		["str", fixDecoderSize(getUtf8Decoder(), 32)],
 		["fee", getU64Decoder()],
 		["solBalance", getU64Decoder()],
 		["tokenBalance", getU64Decoder()],
 		["updatedAt", getU32Decoder()],
 		["isAuthorized", getBooleanDecoder()],
+		["feeOnX", getBooleanDecoder()],
 		["status", getEnumDecoder(Status)],
 		//https://github.com/anza-xyz/kit/tree/main/packages/codecs-data-structures#enum-codec
 		["vaultBump", getU8Decoder()],
 		["bump", getU8Decoder()],
+		["timelockDelay", getU32Decoder()],
+		["extMask", getU64Decoder()],
+		["extAllowlist", getBooleanDecoder()],
+		["openEscrows", getU32Decoder()],
+		["openPositions", getU32Decoder()],
 		//["padding", getArrayDecoder(getU64Decoder(), { size: 3 })],
 	],
 );
@@ -84,21 +96,25 @@ export const solanaKitDecodeConfig = (
 ) => {
 	const decoded = configAcctDecoder.decode(bytes);
 	if (isVerbose) {
-		ll("mint0:", decoded.mint0);
-		ll("mint1:", decoded.mint1);
-		ll("mint2:", decoded.mint2);
-		ll("mint3:", decoded.mint3);
+		ll("version:", decoded.version);
 		ll("vault:", decoded.vault);
 		ll("progOwner:", decoded.progOwner);
-		ll("admin:", decoded.admin);
+		ll("seedOwner:", decoded.seedOwner);
+		ll("pendingOwner:", decoded.pendingOwner);
 		ll("str:", decoded.str);
 		ll("fee:", decoded.fee);
 		ll("solBalance:", decoded.solBalance);
 		ll("tokenBalance:", decoded.tokenBalance);
 		ll("updatedAt:", decoded.updatedAt);
 		ll("isAuthorized:", decoded.isAuthorized);
+		ll("feeOnX:", decoded.feeOnX);
 		ll("status:", decoded.status);
 		ll("bump:", decoded.bump);
+		ll("timelockDelay:", decoded.timelockDelay);
+		ll("extMask:", decoded.extMask);
+		ll("extAllowlist:", decoded.extAllowlist);
+		ll("openEscrows:", decoded.openEscrows);
+		ll("openPositions:", decoded.openPositions);
 	}
 	return decoded;
 };
@@ -109,63 +125,230 @@ export const solanaKitDecodeDev = (
 	if (!bytes) throw new Error("bytes invalid");
 	const decoded = solanaKitDecodeConfig(bytes, true);
 	const decodedV1: ConfigAcctDev = {
-		mint0: new PublicKey(decoded.mint0.toString()),
-		mint1: new PublicKey(decoded.mint1.toString()),
-		mint2: new PublicKey(decoded.mint2.toString()),
-		mint3: new PublicKey(decoded.mint3.toString()),
+		version: decoded.version,
 		vault: new PublicKey(decoded.vault.toString()),
 		progOwner: new PublicKey(decoded.progOwner.toString()),
-		admin: new PublicKey(decoded.admin.toString()),
+		seedOwner: new PublicKey(decoded.seedOwner.toString()),
+		pendingOwner: new PublicKey(decoded.pendingOwner.toString()),
 		str: decoded.str,
 		fee: decoded.fee,
 		solBalance: decoded.solBalance,
 		tokenBalance: decoded.tokenBalance,
 		updatedAt: decoded.updatedAt,
 		isAuthorized: decoded.isAuthorized,
+		feeOnX: decoded.feeOnX,
 		status: decoded.status,
 		bump: decoded.bump,
+		timelockDelay: decoded.timelockDelay,
+		extMask: decoded.extMask,
+		extAllowlist: decoded.extAllowlist,
+		openEscrows: decoded.openEscrows,
+		openPositions: decoded.openPositions,
 	};
 	return decodedV1;
 };
 export type ConfigAcctDev = {
-	mint0: PublicKey;
-	mint1: PublicKey;
-	mint2: PublicKey;
-	mint3: PublicKey;
+	version: number;
 	vault: PublicKey;
 	progOwner: PublicKey;
-	admin: PublicKey;
+	seedOwner: PublicKey;
+	pendingOwner: PublicKey;
 	str: string;
 	fee: bigint;
 	solBalance: bigint;
 	tokenBalance: bigint;
 	updatedAt: number;
 	isAuthorized: boolean;
+	feeOnX: boolean;
 	status: Status;
 	bump: number;
+	timelockDelay: number;
+	extMask: bigint;
+	extAllowlist: boolean;
+	openEscrows: number;
+	openPositions: number;
+};
+//Token2022 ExtensionType ids used as bits of the Config extMask
+export const MintExtension = {
+	transferFeeConfig: 1,
+	defaultAccountState: 6,
+	immutableOwner: 7,
+	nonTransferable: 9,
+	interestBearingConfig: 10,
+	permanentDelegate: 12,
+	transferHook: 14,
+	metadataPointer: 18,
+	tokenMetadata: 19,
+	pausable: 26,
+};
+export const extBit = (extensionType: number) => 1n << BigInt(extensionType);
+export const defaultExtDeny =
+	extBit(MintExtension.defaultAccountState) |
+	extBit(MintExtension.nonTransferable) |
+	extBit(MintExtension.permanentDelegate) |
+	extBit(MintExtension.transferHook) |
+	extBit(MintExtension.pausable);
+//---------------== AdminPDA
+export const AdminPermission = {
+	pause: 1,
+	fee: 1 << 1,
+	mints: 1 << 2,
+	treasury: 1 << 3,
+};
+export type AdminAcct = {
+	discriminator: ReadonlyUint8Array;
+	version: number;
+	config: Address;
+	admin: Address;
+	permissions: number;
+	updatedAt: number;
+	bump: number;
+};
+export const adminAcctDecoder: FixedSizeDecoder<AdminAcct> = getStructDecoder([
+	["discriminator", fixDecoderSize(getBytesDecoder(), 8)],
+	["version", getU8Decoder()],
+	["config", getAddressDecoder()],
+	["admin", getAddressDecoder()],
+	["permissions", getU32Decoder()],
+	["updatedAt", getU32Decoder()],
+	["bump", getU8Decoder()],
+]);
+export const solanaKitDecodeAdmin = (
+	bytes: ReadonlyUint8Array | Uint8Array<ArrayBufferLike> | undefined,
+) => {
+	if (!bytes) throw new Error("bytes invalid");
+	const decoded = adminAcctDecoder.decode(bytes);
+	ll("config:", decoded.config);
+	ll("admin:", decoded.admin);
+	ll("permissions:", decoded.permissions);
+	ll("updatedAt:", decoded.updatedAt);
+	ll("bump:", decoded.bump);
+	return decoded;
+};
+//---------------== MintRegPDA
+export type MintRegAcct = {
+	discriminator: ReadonlyUint8Array;
+	version: number;
+	config: Address;
+	mint: Address;
+	depositCap: bigint;
+	deposited: bigint;
+	updatedAt: number;
+	decimals: number;
+	isEnabled: boolean;
+	bump: number;
+};
+export const mintRegAcctDecoder: FixedSizeDecoder<MintRegAcct> =
+	getStructDecoder([
+		["discriminator", fixDecoderSize(getBytesDecoder(), 8)],
+		["version", getU8Decoder()],
+		["config", getAddressDecoder()],
+		["mint", getAddressDecoder()],
+		["depositCap", getU64Decoder()],
+		["deposited", getU64Decoder()],
+		["updatedAt", getU32Decoder()],
+		["decimals", getU8Decoder()],
+		["isEnabled", getBooleanDecoder()],
+		["bump", getU8Decoder()],
+	]);
+export const solanaKitDecodeMintReg = (
+	bytes: ReadonlyUint8Array | Uint8Array<ArrayBufferLike> | undefined,
+) => {
+	if (!bytes) throw new Error("bytes invalid");
+	const decoded = mintRegAcctDecoder.decode(bytes);
+	ll("config:", decoded.config);
+	ll("mint:", decoded.mint);
+	ll("depositCap:", decoded.depositCap);
+	ll("deposited:", decoded.deposited);
+	ll("updatedAt:", decoded.updatedAt);
+	ll("decimals:", decoded.decimals);
+	ll("isEnabled:", decoded.isEnabled);
+	ll("bump:", decoded.bump);
+	return decoded;
+};
+//---------------== UserPDA
+export type UserAcct = {
+	discriminator: ReadonlyUint8Array;
+	version: number;
+	config: Address;
+	user: Address;
+	mint: Address;
+	deposited: bigint;
+	redeemable: bigint;
+	totalDeposited: bigint;
+	totalWithdrawn: bigint;
+	totalPaid: bigint;
+	totalRedeemed: bigint;
+	updatedAt: number;
+	bump: number;
+};
+export const userAcctDecoder: FixedSizeDecoder<UserAcct> = getStructDecoder([
+	["discriminator", fixDecoderSize(getBytesDecoder(), 8)],
+	["version", getU8Decoder()],
+	["config", getAddressDecoder()],
+	["user", getAddressDecoder()],
+	["mint", getAddressDecoder()],
+	["deposited", getU64Decoder()],
+	["redeemable", getU64Decoder()],
+	["totalDeposited", getU64Decoder()],
+	["totalWithdrawn", getU64Decoder()],
+	["totalPaid", getU64Decoder()],
+	["totalRedeemed", getU64Decoder()],
+	["updatedAt", getU32Decoder()],
+	["bump", getU8Decoder()],
+]);
+export const solanaKitDecodeUser = (
+	bytes: ReadonlyUint8Array | Uint8Array<ArrayBufferLike> | undefined,
+) => {
+	if (!bytes) throw new Error("bytes invalid");
+	const decoded = userAcctDecoder.decode(bytes);
+	ll("user:", decoded.user);
+	ll("mint:", decoded.mint);
+	ll("deposited:", decoded.deposited);
+	ll("redeemable:", decoded.redeemable);
+	ll("totalDeposited:", decoded.totalDeposited);
+	ll("totalWithdrawn:", decoded.totalWithdrawn);
+	ll("totalPaid:", decoded.totalPaid);
+	ll("totalRedeemed:", decoded.totalRedeemed);
+	ll("updatedAt:", decoded.updatedAt);
+	ll("bump:", decoded.bump);
+	return decoded;
 };
 //---------------== EscrowPDA
 //converted from Rust code. XyzAcct, xyzAcctDecoder, DecodedXyzAcct should all match in field order and types!
 export type EscrowAcct = {
+	discriminator: ReadonlyUint8Array;
+	version: number;
+	config: Address;
 	maker: Address;
-	//taker: Address;
+	taker: Address;
 	mintX: Address;
 	mintY: Address;
 	amountX: bigint;
 	amountY: bigint;
+	remainingX: bigint;
+	receivedY: bigint;
 	id: bigint;
+	expiresAt: number;
 	decimalX: number;
 	decimalY: number;
 	bump: number;
 };
 export const escrowAcctDecoder: FixedSizeDecoder<EscrowAcct> = getStructDecoder(
 	[
+		["discriminator", fixDecoderSize(getBytesDecoder(), 8)],
+		["version", getU8Decoder()],
+		["config", getAddressDecoder()],
 		["maker", getAddressDecoder()],
+		["taker", getAddressDecoder()],
 		["mintX", getAddressDecoder()],
 		["mintY", getAddressDecoder()],
 		["amountX", getU64Decoder()],
 		["amountY", getU64Decoder()],
+		["remainingX", getU64Decoder()],
+		["receivedY", getU64Decoder()],
 		["id", getU64Decoder()],
+		["expiresAt", getU32Decoder()],
 		["decimalX", getU8Decoder()],
 		["decimalY", getU8Decoder()],
 		["bump", getU8Decoder()],
@@ -177,12 +360,17 @@ export const solanaKitDecodeEscrow = (
 ) => {
 	const decoded = escrowAcctDecoder.decode(bytes);
 	if (isVerbose) {
+		ll("config:", decoded.config);
 		ll("maker :", decoded.maker);
+		ll("taker :", decoded.taker);
 		ll("mintX  :", decoded.mintX);
 		ll("mintY  :", decoded.mintY);
 		ll("amountX:", decoded.amountX);
 		ll("amountY:", decoded.amountY);
+		ll("remainingX:", decoded.remainingX);
+		ll("receivedY:", decoded.receivedY);
 		ll("id:", decoded.id);
+		ll("expiresAt:", decoded.expiresAt);
 		ll("decimalX:", decoded.decimalX);
 		ll("decimalY:", decoded.decimalY);
 		ll("bump:", decoded.bump);
@@ -196,12 +384,17 @@ export const solanaKitDecodeEscrowDev = (
 	if (!bytes) throw new Error("bytes invalid");
 	const decoded = solanaKitDecodeEscrow(bytes, true);
 	const decodedV1: EscrowAcctDev = {
+		config: new PublicKey(decoded.config.toString()),
 		maker: new PublicKey(decoded.maker.toString()),
+		taker: new PublicKey(decoded.taker.toString()),
 		mintX: new PublicKey(decoded.mintX.toString()),
 		mintY: new PublicKey(decoded.mintY.toString()),
 		amountX: decoded.amountX,
 		amountY: decoded.amountY,
+		remainingX: decoded.remainingX,
+		receivedY: decoded.receivedY,
 		id: decoded.id,
+		expiresAt: decoded.expiresAt,
 		decimalX: decoded.decimalX,
 		decimalY: decoded.decimalY,
 		bump: decoded.bump,
@@ -209,16 +402,149 @@ export const solanaKitDecodeEscrowDev = (
 	return decodedV1;
 };
 export type EscrowAcctDev = {
+	config: PublicKey;
 	maker: PublicKey;
+	taker: PublicKey;
 	mintX: PublicKey;
 	mintY: PublicKey;
 	amountX: bigint;
 	amountY: bigint;
+	remainingX: bigint;
+	receivedY: bigint;
 	id: bigint;
+	expiresAt: number;
 	decimalX: number;
 	decimalY: number;
 	bump: number;
 };
+//---------------== ActionPDA
+export const ActionKind = {
+	setFee: 1,
+	setDelay: 2,
+	mintAdd: 3,
+	mintRemove: 4,
+	acceptOwner: 5, //multisig only
+	setExtPolicy: 6,
+	setRate: 7,
+	adminGrant: 8,
+	adminRevoke: 9,
+	proposeOwner: 10,
+	setStatus: 11,
+	setLabel: 12,
+	setAuthorized: 13,
+	closeConfig: 14, //multisig only
+	migrateConfig: 15, //multisig only
+};
+export type ActionAcct = {
+	discriminator: ReadonlyUint8Array;
+	version: number;
+	config: Address;
+	proposer: Address;
+	payloadHash: ReadonlyUint8Array;
+	id: bigint;
+	eta: number;
+	kind: number;
+	bump: number;
+};
+export const actionAcctDecoder: FixedSizeDecoder<ActionAcct> = getStructDecoder(
+	[
+		["discriminator", fixDecoderSize(getBytesDecoder(), 8)],
+		["version", getU8Decoder()],
+		["config", getAddressDecoder()],
+		["proposer", getAddressDecoder()],
+		["payloadHash", fixDecoderSize(getBytesDecoder(), 32)],
+		["id", getU64Decoder()],
+		["eta", getU32Decoder()],
+		["kind", getU8Decoder()],
+		["bump", getU8Decoder()],
+	],
+);
+export const solanaKitDecodeAction = (
+	bytes: ReadonlyUint8Array | Uint8Array<ArrayBufferLike> | undefined,
+) => {
+	if (!bytes) throw new Error("bytes invalid");
+	const decoded = actionAcctDecoder.decode(bytes);
+	ll("config:", decoded.config);
+	ll("proposer:", decoded.proposer);
+	ll("id:", decoded.id);
+	ll("eta:", decoded.eta);
+	ll("kind:", decoded.kind);
+	ll("bump:", decoded.bump);
+	return decoded;
+};
+//---------------== MultisigPDA
+export type MultisigAcct = {
+	discriminator: ReadonlyUint8Array;
+	version: number;
+	creator: Address;
+	signers: Address[];
+	id: bigint;
+	signerCount: number;
+	threshold: number;
+	bump: number;
+};
+export const multisigAcctDecoder: FixedSizeDecoder<MultisigAcct> =
+	getStructDecoder([
+		["discriminator", fixDecoderSize(getBytesDecoder(), 8)],
+		["version", getU8Decoder()],
+		["creator", getAddressDecoder()],
+		["signers", getArrayDecoder(getAddressDecoder(), { size: 10 })],
+		["id", getU64Decoder()],
+		["signerCount", getU8Decoder()],
+		["threshold", getU8Decoder()],
+		["bump", getU8Decoder()],
+	]);
+export const solanaKitDecodeMultisig = (
+	bytes: ReadonlyUint8Array | Uint8Array<ArrayBufferLike> | undefined,
+) => {
+	if (!bytes) throw new Error("bytes invalid");
+	const decoded = multisigAcctDecoder.decode(bytes);
+	ll("creator:", decoded.creator);
+	ll("signers:", decoded.signers.slice(0, decoded.signerCount));
+	ll("threshold:", decoded.threshold);
+	ll("bump:", decoded.bump);
+	return decoded;
+};
+export type MultisigTxAcct = {
+	discriminator: ReadonlyUint8Array;
+	version: number;
+	config: Address;
+	multisig: Address;
+	proposer: Address;
+	payloadHash: ReadonlyUint8Array;
+	id: bigint;
+	approvals: number;
+	eta: number;
+	kind: number;
+	bump: number;
+};
+export const multisigTxAcctDecoder: FixedSizeDecoder<MultisigTxAcct> =
+	getStructDecoder([
+		["discriminator", fixDecoderSize(getBytesDecoder(), 8)],
+		["version", getU8Decoder()],
+		["config", getAddressDecoder()],
+		["multisig", getAddressDecoder()],
+		["proposer", getAddressDecoder()],
+		["payloadHash", fixDecoderSize(getBytesDecoder(), 32)],
+		["id", getU64Decoder()],
+		["approvals", getU16Decoder()],
+		["eta", getU32Decoder()],
+		["kind", getU8Decoder()],
+		["bump", getU8Decoder()],
+	]);
+export const solanaKitDecodeMultisigTx = (
+	bytes: ReadonlyUint8Array | Uint8Array<ArrayBufferLike> | undefined,
+) => {
+	if (!bytes) throw new Error("bytes invalid");
+	const decoded = multisigTxAcctDecoder.decode(bytes);
+	ll("config:", decoded.config);
+	ll("multisig:", decoded.multisig);
+	ll("proposer:", decoded.proposer);
+	ll("approvals:", decoded.approvals);
+	ll("eta:", decoded.eta);
+	ll("kind:", decoded.kind);
+	return decoded;
+};
 //---------------==
 export type DecodedAccount = {
 	executable: boolean;
@@ -1,4 +1,5 @@
 import { expect } from "bun:test";
+import { createHash } from "node:crypto";
 import {
 	ACCOUNT_SIZE,
 	AccountLayout,
@@ -6,6 +7,7 @@ import {
 	getAssociatedTokenAddressSync,
 	MINT_SIZE,
 	MintLayout,
+	TOKEN_2022_PROGRAM_ID,
 	TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
 import {
@@ -94,6 +96,68 @@ export const vaultOBump = vaultOOut.bump;
 export const vault1 = vaultOut1.pda;
 export const vault2 = vaultOut2.pda;
 export const vault3 = vaultOut3.pda;
+//SOL vaults of users on a config, seeded by the config and the user
+export const findSolVault = (
+	userAddr: PublicKey,
+	pdaName: string,
+	config = configPDA,
+	progAddr = vaultProgAddr,
+): PdaOut => {
+	const [pda, bump] = PublicKey.findProgramAddressSync(
+		[Buffer.from("vault"), config.toBuffer(), userAddr.toBuffer()],
+		progAddr,
+	);
+	ll(`${pdaName} pda: ${pda.toBase58()}, bump: ${bump}`);
+	return { pda, bump };
+};
+export const solVaultO = findSolVault(owner, "SolVaultO").pda;
+export const solVault1 = findSolVault(user1, "SolVault1").pda;
+//shared SOL pool vault and its share mint, whose mint authority is the pool vault
+export const poolPDA = findPdaV1(configPDA, "PoolPDA", "pool").pda;
+export const shareMint = findPdaV1(configPDA, "ShareMint", "share_mint").pda;
+
+//Admin PDAs are seeded by the config they apply to
+export const findAdmin = (
+	admin: PublicKey,
+	config = configPDA,
+	progAddr = vaultProgAddr,
+): PdaOut => {
+	const [pda, bump] = PublicKey.findProgramAddressSync(
+		[Buffer.from("admin"), config.toBuffer(), admin.toBuffer()],
+		progAddr,
+	);
+	ll(`AdminPDA pda: ${pda.toBase58()}, bump: ${bump}`);
+	return { pda, bump };
+};
+
+//Mint Registry PDAs are seeded by the config accepting the mint
+export const findMintReg = (
+	mint: PublicKey,
+	config = configPDA,
+	progAddr = vaultProgAddr,
+): PdaOut => {
+	const [pda, bump] = PublicKey.findProgramAddressSync(
+		[Buffer.from("mint_reg"), config.toBuffer(), mint.toBuffer()],
+		progAddr,
+	);
+	ll(`MintRegPDA pda: ${pda.toBase58()}, bump: ${bump}`);
+	return { pda, bump };
+};
+
+//User PDAs are seeded by the config booking the positions
+export const findUser = (
+	user: PublicKey,
+	mint: PublicKey,
+	config = configPDA,
+	progAddr = vaultProgAddr,
+): PdaOut => {
+	const [pda, bump] = PublicKey.findProgramAddressSync(
+		[Buffer.from("user"), config.toBuffer(), user.toBuffer(), mint.toBuffer()],
+		progAddr,
+	);
+	ll(`UserPDA pda: ${pda.toBase58()}, bump: ${bump}`);
+	return { pda, bump };
+};
 
 export const findEscrow = (
 	maker: PublicKey,
@@ -112,6 +176,57 @@ export const findEscrow = (
 	return { pda, bump };
 };
 
+export const findAction = (
+	proposer: PublicKey,
+	id: bigint,
+	progAddr = vaultProgAddr,
+): PdaOut => {
+	const [pda, bump] = PublicKey.findProgramAddressSync(
+		[
+			Buffer.from("action"),
+			proposer.toBuffer(),
+			Buffer.copyBytesFrom(bigintToBytes(id)),
+		],
+		progAddr,
+	);
+	ll(`Action ${id}: ${pda.toBase58()}, bump: ${bump}`);
+	return { pda, bump };
+};
+
+export const findMultisig = (
+	creator: PublicKey,
+	id: bigint,
+	progAddr = vaultProgAddr,
+): PdaOut => {
+	const [pda, bump] = PublicKey.findProgramAddressSync(
+		[
+			Buffer.from("multisig"),
+			creator.toBuffer(),
+			Buffer.copyBytesFrom(bigintToBytes(id)),
+		],
+		progAddr,
+	);
+	ll(`Multisig ${id}: ${pda.toBase58()}, bump: ${bump}`);
+	return { pda, bump };
+};
+
+export const findMultisigTx = (
+	multisig: PublicKey,
+	id: bigint,
+	progAddr = vaultProgAddr,
+): PdaOut => {
+	const [pda, bump] = PublicKey.findProgramAddressSync(
+		[
+			Buffer.from("multisig_tx"),
+			multisig.toBuffer(),
+			Buffer.copyBytesFrom(bigintToBytes(id)),
+		],
+		progAddr,
+	);
+	ll(`MultisigTx ${id}: ${pda.toBase58()}, bump: ${bump}`);
+	return { pda, bump };
+};
+
 //-------------== LiteSVM Methods
 export const sendSol = (addrTo: PublicKey, amount: bigint, signer: Keypair) => {
 	const blockhash = svm.latestBlockhash();
@@ -142,11 +257,8 @@ export const makeAccount = (
 	sendTxns(svm, blockhash, ixs, [signer], "", SYSTEM_PROGRAM);
 };
 //-------------== Program Methods
-const _testMint = (item: PublicKey) => item === undefined;
 export const initConfig = (
-	mints: PublicKey[],
 	progOwner: PublicKey,
-	progAdmin: PublicKey,
 	isAuthorized: boolean,
 	status: Status,
 	fee: bigint,
@@ -160,177 +272,960 @@ export const initConfig = (
 		...bigintToBytes(fee),
 		...strToU8Fixed(str),
 	];
-	if (mints.length < 4) throw new Error("mints length should be >= 4");
-	if (mints[0] === undefined) throw new Error("mint0");
-	if (mints[1] === undefined) throw new Error("mint1");
-	if (mints[2] === undefined) throw new Error("mint2");
-	if (mints[3] === undefined) throw new Error("mint3");
-	//	if (mints.some(testMint)) { }
-	// for (const mint of mints) {
-	// 	if (mint === undefined) throw new Error("");
-	// }
 
 	const blockhash = svm.latestBlockhash();
 	const ix = new TransactionInstruction({
 		keys: [
 			{ pubkey: signer.publicKey, isSigner: true, isWritable: true },
 			{ pubkey: configPDA, isSigner: false, isWritable: true },
-			{ pubkey: mints[0], isSigner: false, isWritable: false },
-			{ pubkey: mints[1], isSigner: false, isWritable: false },
-			{ pubkey: mints[2], isSigner: false, isWritable: false },
-			{ pubkey: mints[3], isSigner: false, isWritable: false },
-			{ pubkey: vaultO, isSigner: false, isWritable: false },
-			{ pubkey: progOwner, isSigner: false, isWritable: false },
-			{ pubkey: progAdmin, isSigner: false, isWritable: false },
+			{ pubkey: vaultO, isSigner: false, isWritable: false },
+			{ pubkey: progOwner, isSigner: false, isWritable: false },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+		],
+		programId: vaultProgAddr,
+		data: Buffer.from([disc, ...argData]),
+	});
+	sendTxns(svm, blockhash, [ix], [signer]);
+};
+export const payloadVersion = 1;
+//typed Config setters and admin instructions: data is [disc, payloadVersion, ...payload]
+export const configSetRaw = (
+	disc: number,
+	argData: number[],
+	keys: PublicKey[],
+	signer: Keypair,
+	expectedError = "",
+) => {
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: signer.publicKey, isSigner: true, isWritable: false },
+			{ pubkey: configPDA, isSigner: false, isWritable: true },
+			...keys.map((pubkey) => ({ pubkey, isSigner: false, isWritable: false })),
+		],
+		programId: vaultProgAddr,
+		data: Buffer.from([disc, ...argData]),
+	});
+	sendTxns(svm, blockhash, [ix], [signer], expectedError);
+};
+export const configSetFee = (
+	fee: bigint,
+	feeOnX: boolean,
+	signer: Keypair,
+	adminPda = configPDA,
+	expectedError = "",
+) => {
+	const argData = [payloadVersion, ...bigintToBytes(fee), boolToByte(feeOnX)];
+	configSetRaw(13, argData, [adminPda], signer, expectedError);
+};
+//extMask bits are Token2022 ExtensionType ids
+export const configSetExtPolicy = (
+	extMask: bigint,
+	allowlist: boolean,
+	signer: Keypair,
+	adminPda = configPDA,
+	expectedError = "",
+) => {
+	const argData = [
+		payloadVersion,
+		...bigintToBytes(extMask),
+		boolToByte(allowlist),
+	];
+	configSetRaw(46, argData, [adminPda], signer, expectedError);
+};
+export const configSetStatus = (
+	status: Status,
+	signer: Keypair,
+	adminPda = configPDA,
+	expectedError = "",
+) => {
+	const argData = [payloadVersion, statusToByte(status)];
+	configSetRaw(32, argData, [adminPda], signer, expectedError);
+};
+export const configSetLabel = (
+	str: string,
+	signer: Keypair,
+	expectedError = "",
+) => {
+	const argData = [payloadVersion, ...strToU8Fixed(str)];
+	configSetRaw(34, argData, [], signer, expectedError);
+};
+export const configSetAuthorized = (
+	isAuthorized: boolean,
+	signer: Keypair,
+	expectedError = "",
+) => {
+	const argData = [payloadVersion, boolToByte(isAuthorized)];
+	configSetRaw(35, argData, [], signer, expectedError);
+};
+//resize the config in place to the current layout and rewrite the headerless baseline. seedOwner is only needed when the config was made by an earlier prog_owner
+export const configMigrate = (
+	signer: Keypair,
+	expectedError = "",
+	seedOwner?: PublicKey,
+) => {
+	const disc = 19;
+	const blockhash = svm.latestBlockhash();
+	const keys = [
+		{ pubkey: signer.publicKey, isSigner: true, isWritable: true },
+		{ pubkey: configPDA, isSigner: false, isWritable: true },
+		{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+	];
+	if (seedOwner)
+		keys.push({ pubkey: seedOwner, isSigner: false, isWritable: false });
+	const ix = new TransactionInstruction({
+		keys,
+		programId: vaultProgAddr,
+		data: Buffer.from([disc]),
+	});
+	sendTxns(svm, blockhash, [ix], [signer], expectedError);
+};
+//force: close even if the config still records SOL or token balances
+export const closeConfig = (
+	signer: Keypair,
+	configPDA: PublicKey,
+	dest: PublicKey,
+	force = false,
+	expectedError = "",
+) => {
+	const disc = 14;
+	ll("configPDA:", configPDA.toBase58());
+	ll("dest:", dest.toBase58());
+
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: signer.publicKey, isSigner: true, isWritable: false },
+			{ pubkey: configPDA, isSigner: false, isWritable: true },
+			{ pubkey: dest, isSigner: false, isWritable: true },
+		],
+		programId: vaultProgAddr,
+		data: Buffer.from([disc, boolToByte(force)]),
+	});
+	sendTxns(svm, blockhash, [ix], [signer], expectedError);
+};
+
+//-------------== Timelocked Actions
+//payload: [kind, ...arguments]; only its sha256 is stored at enqueue
+export const actionEnqueue = (
+	signer: Keypair,
+	id: bigint,
+	payload: number[],
+	adminPda = configPDA,
+	expectedError = "",
+) => {
+	const disc = 36;
+	const payloadHash = createHash("sha256").update(Buffer.from(payload)).digest();
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: signer.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: configPDA, isSigner: false, isWritable: false },
+			{ pubkey: adminPda, isSigner: false, isWritable: false },
+			{
+				pubkey: findAction(signer.publicKey, id).pda,
+				isSigner: false,
+				isWritable: true,
+			},
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+		],
+		programId: vaultProgAddr,
+		data: Buffer.from([
+			disc,
+			payloadVersion,
+			...bigintToBytes(id),
+			payload[0] ?? 0,
+			...payloadHash,
+		]),
+	});
+	sendTxns(svm, blockhash, [ix], [signer], expectedError);
+};
+//the system and token programs in remaining accounts are read-only
+const isProgram = (pubkey: PublicKey) =>
+	pubkey.equals(SYSTEM_PROGRAM) ||
+	pubkey.equals(TOKEN_PROGRAM_ID) ||
+	pubkey.equals(TOKEN_2022_PROGRAM_ID);
+//remaining: [mint, mint_reg, system_program] for MintAdd, [mint, mint_reg] for MintRemove, [mint, token_program] for SetRate, [admin_pda, system_program] for AdminGrant, [admin_pda] for AdminRevoke
+//adminPda: the proposer's Admin PDA to re-check its permission, or any account for owner-only kinds
+export const actionExecute = (
+	signer: Keypair,
+	proposer: PublicKey,
+	id: bigint,
+	payload: number[],
+	remaining: PublicKey[] = [],
+	expectedError = "",
+	adminPda = configPDA,
+) => {
+	const disc = 37;
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: signer.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: configPDA, isSigner: false, isWritable: true },
+			{
+				pubkey: findAction(proposer, id).pda,
+				isSigner: false,
+				isWritable: true,
+			},
+			{ pubkey: proposer, isSigner: false, isWritable: true },
+			{ pubkey: adminPda, isSigner: false, isWritable: false },
+			...remaining.map((pubkey) => ({
+				pubkey,
+				isSigner: false,
+				isWritable: !isProgram(pubkey),
+			})),
+		],
+		programId: vaultProgAddr,
+		data: Buffer.from([disc, ...payload]),
+	});
+	sendTxns(svm, blockhash, [ix], [signer], expectedError);
+};
+export const actionCancel = (
+	signer: Keypair,
+	proposer: PublicKey,
+	id: bigint,
+	expectedError = "",
+) => {
+	const disc = 38;
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: signer.publicKey, isSigner: true, isWritable: false },
+			{ pubkey: configPDA, isSigner: false, isWritable: false },
+			{
+				pubkey: findAction(proposer, id).pda,
+				isSigner: false,
+				isWritable: true,
+			},
+			{ pubkey: proposer, isSigner: false, isWritable: true },
+		],
+		programId: vaultProgAddr,
+		data: Buffer.from([disc]),
+	});
+	sendTxns(svm, blockhash, [ix], [signer], expectedError);
+};
+
+//-------------== Multisig
+export const multisigInit = (
+	creator: Keypair,
+	id: bigint,
+	threshold: number,
+	signers: PublicKey[],
+	expectedError = "",
+) => {
+	const disc = 39;
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: creator.publicKey, isSigner: true, isWritable: true },
+			{
+				pubkey: findMultisig(creator.publicKey, id).pda,
+				isSigner: false,
+				isWritable: true,
+			},
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+		],
+		programId: vaultProgAddr,
+		data: Buffer.from([
+			disc,
+			...bigintToBytes(id),
+			threshold,
+			...signers.flatMap((signer) => [...signer.toBytes()]),
+		]),
+	});
+	sendTxns(svm, blockhash, [ix], [creator], expectedError);
+};
+//payload: [kind, ...arguments] with the same kinds as the Actions
+export const multisigPropose = (
+	signer: Keypair,
+	multisig: PublicKey,
+	id: bigint,
+	payload: number[],
+	expectedError = "",
+) => {
+	const disc = 40;
+	const payloadHash = createHash("sha256").update(Buffer.from(payload)).digest();
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: signer.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: configPDA, isSigner: false, isWritable: false },
+			{ pubkey: multisig, isSigner: false, isWritable: false },
+			{
+				pubkey: findMultisigTx(multisig, id).pda,
+				isSigner: false,
+				isWritable: true,
+			},
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+		],
+		programId: vaultProgAddr,
+		data: Buffer.from([
+			disc,
+			payloadVersion,
+			...bigintToBytes(id),
+			payload[0] ?? 0,
+			...payloadHash,
+		]),
+	});
+	sendTxns(svm, blockhash, [ix], [signer], expectedError);
+};
+export const multisigApprove = (
+	signer: Keypair,
+	multisig: PublicKey,
+	id: bigint,
+	expectedError = "",
+	config = configPDA,
+) => {
+	const disc = 41;
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: signer.publicKey, isSigner: true, isWritable: false },
+			{ pubkey: config, isSigner: false, isWritable: false },
+			{ pubkey: multisig, isSigner: false, isWritable: false },
+			{
+				pubkey: findMultisigTx(multisig, id).pda,
+				isSigner: false,
+				isWritable: true,
+			},
+		],
+		programId: vaultProgAddr,
+		data: Buffer.from([disc]),
+	});
+	sendTxns(svm, blockhash, [ix], [signer], expectedError);
+};
+//remaining: [mint, mint_reg, system_program] for MintAdd, [mint, mint_reg] for MintRemove, [mint, token_program] for SetRate, [admin_pda, system_program] for AdminGrant, [admin_pda] for AdminRevoke, [dest] for CloseConfig, [system_program] for MigrateConfig
+export const multisigExecute = (
+	signer: Keypair,
+	multisig: PublicKey,
+	proposer: PublicKey,
+	id: bigint,
+	payload: number[],
+	remaining: PublicKey[] = [],
+	expectedError = "",
+	config = configPDA,
+) => {
+	const disc = 42;
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: signer.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: config, isSigner: false, isWritable: true },
+			{ pubkey: multisig, isSigner: false, isWritable: false },
+			{
+				pubkey: findAdmin(multisig, config).pda,
+				isSigner: false,
+				isWritable: false,
+			},
+			{
+				pubkey: findMultisigTx(multisig, id).pda,
+				isSigner: false,
+				isWritable: true,
+			},
+			{ pubkey: proposer, isSigner: false, isWritable: true },
+			...remaining.map((pubkey) => ({
+				pubkey,
+				isSigner: false,
+				isWritable: !isProgram(pubkey),
+			})),
+		],
+		programId: vaultProgAddr,
+		data: Buffer.from([disc, ...payload]),
+	});
+	sendTxns(svm, blockhash, [ix], [signer], expectedError);
+};
+export const multisigCancel = (
+	signer: Keypair,
+	multisig: PublicKey,
+	id: bigint,
+	expectedError = "",
+) => {
+	const disc = 43;
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: signer.publicKey, isSigner: true, isWritable: true },
+			{
+				pubkey: findMultisigTx(multisig, id).pda,
+				isSigner: false,
+				isWritable: true,
+			},
+		],
+		programId: vaultProgAddr,
+		data: Buffer.from([disc]),
+	});
+	sendTxns(svm, blockhash, [ix], [signer], expectedError);
+};
+
+export const adminGrant = (
+	progOwnerSigner: Keypair,
+	adminPda: PublicKey,
+	admin: PublicKey,
+	permissions: number,
+	expectedError = "",
+) => {
+	const disc = 21;
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: progOwnerSigner.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: configPDA, isSigner: false, isWritable: false },
+			{ pubkey: adminPda, isSigner: false, isWritable: true },
+			{ pubkey: admin, isSigner: false, isWritable: false },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+		],
+		programId: vaultProgAddr,
+		data: Buffer.from([disc, payloadVersion, ...bigintToBytes(permissions, 32)]),
+	});
+	sendTxns(svm, blockhash, [ix], [progOwnerSigner], expectedError);
+};
+export const adminRevoke = (
+	progOwnerSigner: Keypair,
+	adminPda: PublicKey,
+	permissions: number,
+	expectedError = "",
+) => {
+	const disc = 22;
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: progOwnerSigner.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: configPDA, isSigner: false, isWritable: false },
+			{ pubkey: adminPda, isSigner: false, isWritable: true },
+		],
+		programId: vaultProgAddr,
+		data: Buffer.from([disc, payloadVersion, ...bigintToBytes(permissions, 32)]),
+	});
+	sendTxns(svm, blockhash, [ix], [progOwnerSigner], expectedError);
+};
+
+export const ownerPropose = (
+	progOwnerSigner: Keypair,
+	newOwner: PublicKey,
+	expectedError = "",
+) => {
+	const disc = 24;
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: progOwnerSigner.publicKey, isSigner: true, isWritable: false },
+			{ pubkey: configPDA, isSigner: false, isWritable: true },
+			{ pubkey: newOwner, isSigner: false, isWritable: false },
+		],
+		programId: vaultProgAddr,
+		data: Buffer.from([disc]),
+	});
+	sendTxns(svm, blockhash, [ix], [progOwnerSigner], expectedError);
+};
+export const ownerAccept = (pendingOwnerSigner: Keypair, expectedError = "") => {
+	const disc = 25;
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: pendingOwnerSigner.publicKey, isSigner: true, isWritable: false },
+			{ pubkey: configPDA, isSigner: false, isWritable: true },
+		],
+		programId: vaultProgAddr,
+		data: Buffer.from([disc]),
+	});
+	sendTxns(svm, blockhash, [ix], [pendingOwnerSigner], expectedError);
+};
+export const ownerCancel = (progOwnerSigner: Keypair, expectedError = "") => {
+	const disc = 26;
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: progOwnerSigner.publicKey, isSigner: true, isWritable: false },
+			{ pubkey: configPDA, isSigner: false, isWritable: true },
+		],
+		programId: vaultProgAddr,
+		data: Buffer.from([disc]),
+	});
+	sendTxns(svm, blockhash, [ix], [progOwnerSigner], expectedError);
+};
+
+export const mintAdd = (
+	signer: Keypair,
+	mint: PublicKey,
+	depositCap: bigint,
+	adminPda = configPDA,
+	expectedError = "",
+) => {
+	const disc = 27;
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: signer.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: configPDA, isSigner: false, isWritable: false },
+			{ pubkey: adminPda, isSigner: false, isWritable: false },
+			{ pubkey: mint, isSigner: false, isWritable: false },
+			{ pubkey: findMintReg(mint).pda, isSigner: false, isWritable: true },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+		],
+		programId: vaultProgAddr,
+		data: Buffer.from([disc, payloadVersion, ...bigintToBytes(depositCap)]),
+	});
+	sendTxns(svm, blockhash, [ix], [signer], expectedError);
+};
+//disc: 28 MintRemove, 29 MintEnable, 30 MintDisable
+const mintRegIx = (
+	disc: number,
+	signer: Keypair,
+	mint: PublicKey,
+	adminPda: PublicKey,
+	expectedError: string,
+) => {
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: signer.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: configPDA, isSigner: false, isWritable: false },
+			{ pubkey: adminPda, isSigner: false, isWritable: false },
+			{ pubkey: mint, isSigner: false, isWritable: false },
+			{ pubkey: findMintReg(mint).pda, isSigner: false, isWritable: true },
+		],
+		programId: vaultProgAddr,
+		data: Buffer.from([disc]),
+	});
+	sendTxns(svm, blockhash, [ix], [signer], expectedError);
+};
+export const mintRemove = (
+	signer: Keypair,
+	mint: PublicKey,
+	adminPda = configPDA,
+	expectedError = "",
+) => mintRegIx(28, signer, mint, adminPda, expectedError);
+export const mintEnable = (
+	signer: Keypair,
+	mint: PublicKey,
+	adminPda = configPDA,
+	expectedError = "",
+) => mintRegIx(29, signer, mint, adminPda, expectedError);
+export const mintDisable = (
+	signer: Keypair,
+	mint: PublicKey,
+	adminPda = configPDA,
+	expectedError = "",
+) => mintRegIx(30, signer, mint, adminPda, expectedError);
+
+//accounts: SOL vaults and config vault ATAs to compare against the Config balances. Returns the logs with the drift
+export const configReconcile = (
+	signer: Keypair,
+	accounts: PublicKey[],
+	adminPda = configPDA,
+	expectedError = "",
+): string[] => {
+	const disc = 31;
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: signer.publicKey, isSigner: true, isWritable: false },
+			{ pubkey: configPDA, isSigner: false, isWritable: false },
+			{ pubkey: adminPda, isSigner: false, isWritable: false },
+			...accounts.map((pubkey) => ({
+				pubkey,
+				isSigner: false,
+				isWritable: false,
+			})),
+		],
+		programId: vaultProgAddr,
+		data: Buffer.from([disc]),
+	});
+	const tx = new Transaction();
+	tx.recentBlockhash = blockhash;
+	tx.add(ix);
+	tx.sign(signer);
+	const simRes = svm.simulateTransaction(tx);
+	sendTxns(svm, blockhash, [ix], [signer], expectedError);
+	return simRes.meta().logs();
+};
+
+export const depositSol = (
+	vaultPdaX: PublicKey,
+	amount: bigint,
+	signer: Keypair,
+	expectedError = "",
+) => {
+	const disc = 0;
+	const argData = bigintToBytes(amount);
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: signer.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: vaultPdaX, isSigner: false, isWritable: true },
+			{ pubkey: configPDA, isSigner: false, isWritable: true },
 			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
 		],
 		programId: vaultProgAddr,
 		data: Buffer.from([disc, ...argData]),
 	});
-	sendTxns(svm, blockhash, [ix], [signer]);
+	sendTxns(svm, blockhash, [ix], [signer], expectedError);
 };
-export const updateConfig = (
-	bytes4bools: number[],
-	bytes4u8s: number[],
-	bytes4u32s: number[],
-	bytes4u64s: number[],
-	acct1: PublicKey,
-	acct2: PublicKey,
-	str: string,
+export const withdrawSol = (
+	vaultPdaX: PublicKey,
+	amount: bigint,
 	signer: Keypair,
+	expectedError = "",
 ) => {
-	const disc = 13;
-	const argData = [
-		...bytes4bools,
-		...bytes4u8s,
-		...bytes4u32s,
-		...bytes4u64s,
-		...strToU8Fixed(str),
-	];
-	ll("acct1:", acct1.toBase58());
-	ll("acct2:", acct2.toBase58());
-
+	const disc = 1;
+	const argData = bigintToBytes(amount);
 	const blockhash = svm.latestBlockhash();
 	const ix = new TransactionInstruction({
 		keys: [
 			{ pubkey: signer.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: vaultPdaX, isSigner: false, isWritable: true },
 			{ pubkey: configPDA, isSigner: false, isWritable: true },
-			{ pubkey: acct1, isSigner: false, isWritable: false },
-			{ pubkey: acct2, isSigner: false, isWritable: false },
 		],
 		programId: vaultProgAddr,
 		data: Buffer.from([disc, ...argData]),
 	});
-	sendTxns(svm, blockhash, [ix], [signer]);
+	sendTxns(svm, blockhash, [ix], [signer], expectedError);
 };
-export const configResize = (
+//minShares: slippage limit on the shares minted
+export const poolDepositSol = (
+	amount: bigint,
+	minShares: bigint,
 	signer: Keypair,
-	configPDA: PublicKey,
-	firstProgOwner: PublicKey,
-	newSize: bigint,
+	expectedError = "",
 ) => {
-	const disc = 19;
-	ll("configPDA:", configPDA.toBase58());
-	ll("newSize:", newSize);
-	const argData = [...bigintToBytes(newSize, 64)];
+	const disc = 48;
+	const argData = [...bigintToBytes(amount), ...bigintToBytes(minShares)];
 	const blockhash = svm.latestBlockhash();
 	const ix = new TransactionInstruction({
 		keys: [
 			{ pubkey: signer.publicKey, isSigner: true, isWritable: true },
 			{ pubkey: configPDA, isSigner: false, isWritable: true },
-			{ pubkey: firstProgOwner, isSigner: false, isWritable: false },
+			{ pubkey: poolPDA, isSigner: false, isWritable: true },
+			{ pubkey: shareMint, isSigner: false, isWritable: true },
+			{
+				pubkey: getAta(shareMint, signer.publicKey),
+				isSigner: false,
+				isWritable: true,
+			},
+			{ pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
 			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+			{ pubkey: ATokenGPvbd, isSigner: false, isWritable: false },
 		],
 		programId: vaultProgAddr,
 		data: Buffer.from([disc, ...argData]),
 	});
-	sendTxns(svm, blockhash, [ix], [signer]);
+	sendTxns(svm, blockhash, [ix], [signer], expectedError);
 };
-export const closeConfig = (
+//minLamports: slippage limit on the lamports paid out
+export const poolWithdrawSol = (
+	shares: bigint,
+	minLamports: bigint,
 	signer: Keypair,
-	configPDA: PublicKey,
-	dest: PublicKey,
+	expectedError = "",
 ) => {
-	const disc = 14;
-	ll("configPDA:", configPDA.toBase58());
-	ll("dest:", dest.toBase58());
-
+	const disc = 49;
+	const argData = [...bigintToBytes(shares), ...bigintToBytes(minLamports)];
 	const blockhash = svm.latestBlockhash();
 	const ix = new TransactionInstruction({
 		keys: [
 			{ pubkey: signer.publicKey, isSigner: true, isWritable: true },
 			{ pubkey: configPDA, isSigner: false, isWritable: true },
-			{ pubkey: dest, isSigner: false, isWritable: false },
+			{ pubkey: poolPDA, isSigner: false, isWritable: true },
+			{ pubkey: shareMint, isSigner: false, isWritable: true },
+			{
+				pubkey: getAta(shareMint, signer.publicKey),
+				isSigner: false,
+				isWritable: true,
+			},
+			{ pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
 		],
 		programId: vaultProgAddr,
-		data: Buffer.from([disc]),
+		data: Buffer.from([disc, ...argData]),
 	});
-	sendTxns(svm, blockhash, [ix], [signer]);
+	sendTxns(svm, blockhash, [ix], [signer], expectedError);
 };
-
-export const depositSol = (
-	vaultPdaX: PublicKey,
-	amount: bigint,
+export const lgcInitMint = (
 	signer: Keypair,
+	mintKp: Keypair,
+	mintAuthority: PublicKey,
+	freezeAuthorityOpt: PublicKey,
+	decimals: number,
+	//argData: Uint8Array<ArrayBufferLike>,
+	tokenProg = TOKEN_PROGRAM_ID,
 ) => {
-	const disc = 0;
-	const argData = bigintToBytes(amount);
+	const disc = 2;
+	checkDecimals(decimals);
 	const blockhash = svm.latestBlockhash();
 	const ix = new TransactionInstruction({
 		keys: [
 			{ pubkey: signer.publicKey, isSigner: true, isWritable: true },
-			{ pubkey: vaultPdaX, isSigner: false, isWritable: true },
+			{ pubkey: mintKp.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: mintAuthority, isSigner: false, isWritable: false },
+			{ pubkey: tokenProg, isSigner: false, isWritable: false },
+			{ pubkey: freezeAuthorityOpt, isSigner: false, isWritable: false },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+		],
+		programId: vaultProgAddr,
+		data: Buffer.from([disc, decimals]),
+	});
+	sendTxns(svm, blockhash, [ix], [signer, mintKp]);
+};
+//mint with MetadataPointer + TokenMetadata extensions; the mint authority is also the metadata update authority
+export const MintExt = {
+	transferFee: 1,
+	nonTransferable: 2,
+	interestBearing: 4,
+};
+export type Tok22MintExtensions = {
+	transferFee?: {
+		basisPoints: number;
+		maximumFee: bigint;
+		withdrawAuthority: PublicKey;
+	};
+	nonTransferable?: boolean;
+	//basis points per year; the config PDA becomes the rate authority
+	interestRate?: number;
+	//Admin PDA of the mint authority with the FEE permission, or the config PDA for the prog owner
+	rateAdminPda?: PublicKey;
+	extraAccounts?: PublicKey[];
+};
+export const tok22InitMint = (
+	signer: Keypair,
+	mintKp: Keypair,
+	mintAuthorityKp: Keypair,
+	decimals: number,
+	name: string,
+	symbol: string,
+	uri: string,
+	expectedError = "",
+	extensions: Tok22MintExtensions = {},
+) => {
+	const disc = 9;
+	checkDecimals(decimals);
+	const argData = [
+		decimals,
+		...strToU8Fixed(name, 10),
+		...strToU8Fixed(symbol, 6),
+		...strToU8Fixed(uri, 32),
+	];
+	const { transferFee, nonTransferable, interestRate, rateAdminPda, extraAccounts } =
+		extensions;
+	const interestBearing = interestRate !== undefined;
+	if (transferFee || nonTransferable || interestBearing) {
+		argData.push(
+			(transferFee ? MintExt.transferFee : 0) |
+				(nonTransferable ? MintExt.nonTransferable : 0) |
+				(interestBearing ? MintExt.interestBearing : 0),
+		);
+	}
+	if (transferFee) {
+		argData.push(
+			transferFee.basisPoints & 0xff, //u16
+			transferFee.basisPoints >> 8,
+			...bigintToBytes(transferFee.maximumFee),
+			...transferFee.withdrawAuthority.toBytes(),
+		);
+	}
+	const rest: { pubkey: PublicKey; isSigner: boolean; isWritable: boolean }[] =
+		[];
+	if (interestBearing) {
+		argData.push(interestRate & 0xff, (interestRate >> 8) & 0xff); //i16
+		rest.push({ pubkey: configPDA, isSigner: false, isWritable: false });
+		rest.push({
+			pubkey: rateAdminPda ?? configPDA,
+			isSigner: false,
+			isWritable: false,
+		});
+	}
+	for (const pubkey of extraAccounts ?? []) {
+		rest.push({ pubkey, isSigner: false, isWritable: false });
+	}
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: signer.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: mintKp.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: mintAuthorityKp.publicKey, isSigner: true, isWritable: false },
+			{ pubkey: TOKEN_2022_PROGRAM_ID, isSigner: false, isWritable: false },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
 			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+			...rest,
 		],
 		programId: vaultProgAddr,
 		data: Buffer.from([disc, ...argData]),
 	});
-	sendTxns(svm, blockhash, [ix], [signer]);
+	sendTxns(
+		svm,
+		blockhash,
+		[ix],
+		[signer, mintKp, mintAuthorityKp],
+		expectedError,
+	);
 };
-export const withdrawSol = (
-	vaultPdaX: PublicKey,
-	amount: bigint,
+//extension types of a Token2022 mint or token account, read from its TLV entries after the account type byte
+export const tlvExtensionTypes = (data: Uint8Array | undefined) => {
+	const buf = Buffer.from(data ?? []);
+	const types: number[] = [];
+	let offset = 166;
+	while (offset + 4 <= buf.length) {
+		const extType = buf.readUInt16LE(offset);
+		if (extType === 0) break;
+		types.push(extType);
+		offset += 4 + buf.readUInt16LE(offset + 2);
+	}
+	return types;
+};
+//value of one TLV extension of a Token2022 mint or token account
+export const tlvExtensionData = (
+	data: Uint8Array | undefined,
+	extensionType: number,
+) => {
+	const buf = Buffer.from(data ?? []);
+	let offset = 166;
+	while (offset + 4 <= buf.length) {
+		const extType = buf.readUInt16LE(offset);
+		if (extType === 0) break;
+		const len = buf.readUInt16LE(offset + 2);
+		if (extType === extensionType) {
+			return buf.subarray(offset + 4, offset + 4 + len);
+		}
+		offset += 4 + len;
+	}
+	return undefined;
+};
+//immutableOwner: require the ImmutableOwner extension on the new ATA
+export const tok22InitAta = (
 	signer: Keypair,
+	toWallet: PublicKey,
+	mint: PublicKey,
+	immutableOwner = false,
 	expectedError = "",
 ) => {
-	const disc = 1;
-	const argData = bigintToBytes(amount);
+	const disc = 10;
+	const ata = getAta(mint, toWallet, true, TOKEN_2022_PROGRAM_ID);
 	const blockhash = svm.latestBlockhash();
 	const ix = new TransactionInstruction({
 		keys: [
 			{ pubkey: signer.publicKey, isSigner: true, isWritable: true },
-			{ pubkey: vaultPdaX, isSigner: false, isWritable: true },
+			{ pubkey: toWallet, isSigner: false, isWritable: false },
+			{ pubkey: mint, isSigner: false, isWritable: false },
+			{ pubkey: ata, isSigner: false, isWritable: true },
+			{ pubkey: TOKEN_2022_PROGRAM_ID, isSigner: false, isWritable: false },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+			{ pubkey: ATokenGPvbd, isSigner: false, isWritable: false },
+		],
+		programId: vaultProgAddr,
+		data: Buffer.from([disc, boolToByte(immutableOwner)]),
+	});
+	sendTxns(svm, blockhash, [ix], [signer], expectedError);
+	return ata;
+};
+export const MetadataField = { name: 0, symbol: 1, uri: 2, key: 3 };
+//key is only used with MetadataField.key
+export const tok22UpdateMetadata = (
+	updateAuthorityKp: Keypair,
+	mint: PublicKey,
+	field: number,
+	key: string,
+	value: string,
+	expectedError = "",
+) => {
+	const disc = 44;
+	const argData = [field, ...strToU8Fixed(key), ...strToU8Fixed(value)];
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: updateAuthorityKp.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: mint, isSigner: false, isWritable: true },
+			{ pubkey: TOKEN_2022_PROGRAM_ID, isSigner: false, isWritable: false },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
 		],
 		programId: vaultProgAddr,
 		data: Buffer.from([disc, ...argData]),
 	});
+	sendTxns(svm, blockhash, [ix], [updateAuthorityKp], expectedError);
+};
+//rate: basis points per year, for mints whose rate authority is the config PDA
+export const tok22UpdateRate = (
+	rate: number,
+	mint: PublicKey,
+	signer: Keypair,
+	adminPda = configPDA,
+	expectedError = "",
+) => {
+	const disc = 47;
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: signer.publicKey, isSigner: true, isWritable: false },
+			{ pubkey: configPDA, isSigner: false, isWritable: true },
+			{ pubkey: adminPda, isSigner: false, isWritable: false },
+			{ pubkey: mint, isSigner: false, isWritable: true },
+			{ pubkey: TOKEN_2022_PROGRAM_ID, isSigner: false, isWritable: false },
+		],
+		programId: vaultProgAddr,
+		data: Buffer.from([
+			disc,
+			payloadVersion,
+			rate & 0xff, //i16
+			(rate >> 8) & 0xff,
+		]),
+	});
 	sendTxns(svm, blockhash, [ix], [signer], expectedError);
 };
-export const lgcInitMint = (
+//sources: token accounts to harvest withheld fees from
+export const tok22HarvestFees = (
 	signer: Keypair,
-	mintKp: Keypair,
-	mintAuthority: PublicKey,
-	freezeAuthorityOpt: PublicKey,
+	mint: PublicKey,
+	vaultAta: PublicKey,
+	sources: PublicKey[] = [],
+	expectedError = "",
+) => {
+	const disc = 45;
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: signer.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: configPDA, isSigner: false, isWritable: true },
+			{ pubkey: mint, isSigner: false, isWritable: true },
+			{ pubkey: vaultO, isSigner: false, isWritable: false },
+			{ pubkey: vaultAta, isSigner: false, isWritable: true },
+			{ pubkey: TOKEN_2022_PROGRAM_ID, isSigner: false, isWritable: false },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+			{ pubkey: ATokenGPvbd, isSigner: false, isWritable: false },
+			...sources.map((pubkey) => ({
+				pubkey,
+				isSigner: false,
+				isWritable: true,
+			})),
+		],
+		programId: vaultProgAddr,
+		data: Buffer.from([disc]),
+	});
+	sendTxns(svm, blockhash, [ix], [signer], expectedError);
+};
+export const tok22MintToken = (
+	mintAuthority: Keypair,
+	toWallet: PublicKey,
+	mint: PublicKey,
 	decimals: number,
-	//argData: Uint8Array<ArrayBufferLike>,
-	tokenProg = TOKEN_PROGRAM_ID,
+	amount: bigint,
 ) => {
-	const disc = 2;
+	const disc = 11;
 	checkDecimals(decimals);
+	checkBigint(amount, "amount");
+	const argData = [decimals, ...bigintToBytes(amount)];
+	const ata = getAta(mint, toWallet, true, TOKEN_2022_PROGRAM_ID);
 	const blockhash = svm.latestBlockhash();
 	const ix = new TransactionInstruction({
 		keys: [
-			{ pubkey: signer.publicKey, isSigner: true, isWritable: true },
-			{ pubkey: mintKp.publicKey, isSigner: true, isWritable: true },
-			{ pubkey: mintAuthority, isSigner: false, isWritable: false },
-			{ pubkey: tokenProg, isSigner: false, isWritable: false },
-			{ pubkey: freezeAuthorityOpt, isSigner: false, isWritable: false },
+			{ pubkey: mintAuthority.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: toWallet, isSigner: false, isWritable: false },
+			{ pubkey: mint, isSigner: false, isWritable: true },
+			{ pubkey: ata, isSigner: false, isWritable: true },
+			{ pubkey: TOKEN_2022_PROGRAM_ID, isSigner: false, isWritable: false },
 			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+			{ pubkey: ATokenGPvbd, isSigner: false, isWritable: false },
 		],
 		programId: vaultProgAddr,
-		data: Buffer.from([disc, decimals]),
+		data: Buffer.from([disc, ...argData]),
 	});
-	sendTxns(svm, blockhash, [ix], [signer, mintKp]);
+	sendTxns(svm, blockhash, [ix], [mintAuthority]);
 };
 export const lgcInitAta = (
 	signer: Keypair,
@@ -395,10 +1290,13 @@ export const lgcDeposit = (
 	toWallet: PublicKey,
 	mint: PublicKey,
 	configPda: PublicKey,
+	mintReg: PublicKey,
+	userPda: PublicKey,
 	decimals: number,
 	amount: bigint,
 	tokenProg = TOKEN_PROGRAM_ID,
 	atokenProg = ATokenGPvbd,
+	expectedError = "",
 ) => {
 	const disc = 5;
 	checkDecimals(decimals);
@@ -413,6 +1311,8 @@ export const lgcDeposit = (
 			{ pubkey: toWallet, isSigner: false, isWritable: false },
 			{ pubkey: mint, isSigner: false, isWritable: false },
 			{ pubkey: configPda, isSigner: false, isWritable: true },
+			{ pubkey: mintReg, isSigner: false, isWritable: true },
+			{ pubkey: userPda, isSigner: false, isWritable: true },
 			{ pubkey: tokenProg, isSigner: false, isWritable: false },
 			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
 			{ pubkey: atokenProg, isSigner: false, isWritable: false },
@@ -420,7 +1320,7 @@ export const lgcDeposit = (
 		programId: vaultProgAddr,
 		data: Buffer.from([disc, ...argData]),
 	});
-	sendTxns(svm, blockhash, [ix], [userSigner]);
+	sendTxns(svm, blockhash, [ix], [userSigner], expectedError);
 };
 export const lgcWithdraw = (
 	userSigner: Keypair,
@@ -428,10 +1328,14 @@ export const lgcWithdraw = (
 	toAta: PublicKey,
 	fromWallet: PublicKey,
 	mint: PublicKey,
+	configPda: PublicKey,
+	mintReg: PublicKey,
+	userPda: PublicKey,
 	decimals: number,
 	amount: bigint,
 	tokenProg = TOKEN_PROGRAM_ID,
 	atokenProg = ATokenGPvbd,
+	expectedError = "",
 ) => {
 	const disc = 6;
 	checkDecimals(decimals);
@@ -445,6 +1349,9 @@ export const lgcWithdraw = (
 			{ pubkey: toAta, isSigner: false, isWritable: true },
 			{ pubkey: fromWallet, isSigner: false, isWritable: false },
 			{ pubkey: mint, isSigner: false, isWritable: false },
+			{ pubkey: configPda, isSigner: false, isWritable: true },
+			{ pubkey: mintReg, isSigner: false, isWritable: true },
+			{ pubkey: userPda, isSigner: false, isWritable: true },
 			{ pubkey: tokenProg, isSigner: false, isWritable: false },
 			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
 			{ pubkey: atokenProg, isSigner: false, isWritable: false },
@@ -452,7 +1359,7 @@ export const lgcWithdraw = (
 		programId: vaultProgAddr,
 		data: Buffer.from([disc, ...argData]),
 	});
-	sendTxns(svm, blockhash, [ix], [userSigner]);
+	sendTxns(svm, blockhash, [ix], [userSigner], expectedError);
 };
 export const lgcPay = (
 	userSigner: Keypair,
@@ -461,6 +1368,8 @@ export const lgcPay = (
 	vault: PublicKey,
 	mint: PublicKey,
 	configPda: PublicKey,
+	mintReg: PublicKey,
+	userPda: PublicKey,
 	decimals: number,
 	amount: bigint,
 	tokenProg = TOKEN_PROGRAM_ID,
@@ -479,6 +1388,8 @@ export const lgcPay = (
 			{ pubkey: vault, isSigner: false, isWritable: false },
 			{ pubkey: mint, isSigner: false, isWritable: false },
 			{ pubkey: configPda, isSigner: false, isWritable: true },
+			{ pubkey: mintReg, isSigner: false, isWritable: false },
+			{ pubkey: userPda, isSigner: false, isWritable: true },
 			{ pubkey: tokenProg, isSigner: false, isWritable: false },
 			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
 			{ pubkey: atokenProg, isSigner: false, isWritable: false },
@@ -495,10 +1406,13 @@ export const lgcRedeem = (
 	vault: PublicKey,
 	configPDA: PublicKey,
 	mint: PublicKey,
+	mintReg: PublicKey,
+	userPda: PublicKey,
 	decimals: number,
 	amount: bigint,
 	tokenProg = TOKEN_PROGRAM_ID,
 	atokenProg = ATokenGPvbd,
+	expectedError = "",
 ) => {
 	const disc = 8;
 	checkDecimals(decimals);
@@ -511,8 +1425,10 @@ export const lgcRedeem = (
 			{ pubkey: fromAta, isSigner: false, isWritable: true },
 			{ pubkey: toAta, isSigner: false, isWritable: true },
 			{ pubkey: vault, isSigner: false, isWritable: false },
-			{ pubkey: configPDA, isSigner: false, isWritable: false },
+			{ pubkey: configPDA, isSigner: false, isWritable: true },
 			{ pubkey: mint, isSigner: false, isWritable: false },
+			{ pubkey: mintReg, isSigner: false, isWritable: false },
+			{ pubkey: userPda, isSigner: false, isWritable: true },
 			{ pubkey: tokenProg, isSigner: false, isWritable: false },
 			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
 			{ pubkey: atokenProg, isSigner: false, isWritable: false },
@@ -520,7 +1436,36 @@ export const lgcRedeem = (
 		programId: vaultProgAddr,
 		data: Buffer.from([disc, ...argData]),
 	});
-	sendTxns(svm, blockhash, [ix], [userSigner]);
+	sendTxns(svm, blockhash, [ix], [userSigner], expectedError);
+};
+export const lgcVoucher = (
+	signer: Keypair,
+	user: PublicKey,
+	mint: PublicKey,
+	mintReg: PublicKey,
+	userPda: PublicKey,
+	amount: bigint,
+	adminPda = configPDA,
+	expectedError = "",
+) => {
+	const disc = 23;
+	checkBigint(amount, "amount");
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: signer.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: configPDA, isSigner: false, isWritable: true },
+			{ pubkey: adminPda, isSigner: false, isWritable: false },
+			{ pubkey: user, isSigner: false, isWritable: false },
+			{ pubkey: mint, isSigner: false, isWritable: false },
+			{ pubkey: mintReg, isSigner: false, isWritable: false },
+			{ pubkey: userPda, isSigner: false, isWritable: true },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+		],
+		programId: vaultProgAddr,
+		data: Buffer.from([disc, payloadVersion, ...bigintToBytes(amount)]),
+	});
+	sendTxns(svm, blockhash, [ix], [signer], expectedError);
 };
 export const makeTokEscrow = (
 	userSigner: Keypair,
@@ -537,6 +1482,9 @@ export const makeTokEscrow = (
 	id: bigint,
 	tokenProg = TOKEN_PROGRAM_ID,
 	atokenProg = ATokenGPvbd,
+	expiresAt = 0,
+	taker = PublicKey.default,
+	expectedError = "",
 ) => {
 	const disc = 15;
 	checkDecimals(decimalX, "decimalX");
@@ -550,6 +1498,8 @@ export const makeTokEscrow = (
 		decimalY,
 		...bigintToBytes(amountY),
 		...bigintToBytes(id),
+		...bigintToBytes(expiresAt, 32),
+		...taker.toBytes(),
 	];
 	const blockhash = svm.latestBlockhash();
 	const ix = new TransactionInstruction({
@@ -568,7 +1518,7 @@ export const makeTokEscrow = (
 		programId: vaultProgAddr,
 		data: Buffer.from([disc, ...argData]),
 	});
-	sendTxns(svm, blockhash, [ix], [userSigner]);
+	sendTxns(svm, blockhash, [ix], [userSigner], expectedError);
 };
 export const takeTokEscrow = (
 	userSigner: Keypair,
@@ -580,13 +1530,19 @@ export const takeTokEscrow = (
 	mintY: PublicKey,
 	escrowPDA: PublicKey,
 	configPDA: PublicKey,
+	maker: PublicKey,
+	makerAtaY: PublicKey,
+	vault: PublicKey,
+	vaultAta: PublicKey,
 	decimalX: number,
 	amountX: bigint,
 	decimalY: number,
 	amountY: bigint,
 	id: bigint,
-	tokenProg = TOKEN_PROGRAM_ID,
+	tokenProgX = TOKEN_PROGRAM_ID,
+	tokenProgY = TOKEN_PROGRAM_ID,
 	atokenProg = ATokenGPvbd,
+	expectedError = "",
 ) => {
 	const disc = 16;
 	checkDecimals(decimalX, "decimalX");
@@ -613,14 +1569,19 @@ export const takeTokEscrow = (
 			{ pubkey: mintY, isSigner: false, isWritable: false },
 			{ pubkey: escrowPDA, isSigner: false, isWritable: true },
 			{ pubkey: configPDA, isSigner: false, isWritable: true },
-			{ pubkey: tokenProg, isSigner: false, isWritable: false },
+			{ pubkey: maker, isSigner: false, isWritable: true },
+			{ pubkey: makerAtaY, isSigner: false, isWritable: true },
+			{ pubkey: vault, isSigner: false, isWritable: false },
+			{ pubkey: vaultAta, isSigner: false, isWritable: true },
+			{ pubkey: tokenProgX, isSigner: false, isWritable: false },
+			{ pubkey: tokenProgY, isSigner: false, isWritable: false },
 			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
 			{ pubkey: atokenProg, isSigner: false, isWritable: false },
 		],
 		programId: vaultProgAddr,
 		data: Buffer.from([disc, ...argData]),
 	});
-	sendTxns(svm, blockhash, [ix], [userSigner]);
+	sendTxns(svm, blockhash, [ix], [userSigner], expectedError);
 };
 export const withdrawTokEscrow = (
 	userSigner: Keypair,
@@ -632,7 +1593,8 @@ export const withdrawTokEscrow = (
 	mintY: PublicKey,
 	escrowPDA: PublicKey,
 	configPDA: PublicKey,
-	tokenProg = TOKEN_PROGRAM_ID,
+	tokenProgX = TOKEN_PROGRAM_ID,
+	tokenProgY = TOKEN_PROGRAM_ID,
 	atokenProg = ATokenGPvbd,
 ) => {
 	const disc = 17;
@@ -648,7 +1610,8 @@ export const withdrawTokEscrow = (
 			{ pubkey: mintY, isSigner: false, isWritable: false },
 			{ pubkey: escrowPDA, isSigner: false, isWritable: true },
 			{ pubkey: configPDA, isSigner: false, isWritable: true },
-			{ pubkey: tokenProg, isSigner: false, isWritable: false },
+			{ pubkey: tokenProgX, isSigner: false, isWritable: false },
+			{ pubkey: tokenProgY, isSigner: false, isWritable: false },
 			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
 			{ pubkey: atokenProg, isSigner: false, isWritable: false },
 		],
@@ -667,7 +1630,8 @@ export const cancelTokEscrow = (
 	mintY: PublicKey,
 	escrowPDA: PublicKey,
 	configPDA: PublicKey,
-	tokenProg = TOKEN_PROGRAM_ID,
+	tokenProgX = TOKEN_PROGRAM_ID,
+	tokenProgY = TOKEN_PROGRAM_ID,
 	atokenProg = ATokenGPvbd,
 ) => {
 	const disc = 18;
@@ -683,7 +1647,8 @@ export const cancelTokEscrow = (
 			{ pubkey: mintY, isSigner: false, isWritable: false },
 			{ pubkey: escrowPDA, isSigner: false, isWritable: true },
 			{ pubkey: configPDA, isSigner: false, isWritable: true },
-			{ pubkey: tokenProg, isSigner: false, isWritable: false },
+			{ pubkey: tokenProgX, isSigner: false, isWritable: false },
+			{ pubkey: tokenProgY, isSigner: false, isWritable: false },
 			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
 			{ pubkey: atokenProg, isSigner: false, isWritable: false },
 		],
@@ -692,6 +1657,46 @@ export const cancelTokEscrow = (
 	});
 	sendTxns(svm, blockhash, [ix], [makerSigner]);
 };
+export const expireTokEscrow = (
+	crankerSigner: Keypair,
+	maker: PublicKey,
+	makerAtaX: PublicKey,
+	makerAtaY: PublicKey,
+	escrowAtaX: PublicKey,
+	escrowAtaY: PublicKey,
+	mintX: PublicKey,
+	mintY: PublicKey,
+	escrowPDA: PublicKey,
+	configPDA: PublicKey,
+	tokenProgX = TOKEN_PROGRAM_ID,
+	tokenProgY = TOKEN_PROGRAM_ID,
+	atokenProg = ATokenGPvbd,
+	expectedError = "",
+) => {
+	const disc = 20;
+	const blockhash = svm.latestBlockhash();
+	const ix = new TransactionInstruction({
+		keys: [
+			{ pubkey: crankerSigner.publicKey, isSigner: true, isWritable: true },
+			{ pubkey: maker, isSigner: false, isWritable: true },
+			{ pubkey: makerAtaX, isSigner: false, isWritable: true },
+			{ pubkey: makerAtaY, isSigner: false, isWritable: true },
+			{ pubkey: escrowAtaX, isSigner: false, isWritable: true },
+			{ pubkey: escrowAtaY, isSigner: false, isWritable: true },
+			{ pubkey: mintX, isSigner: false, isWritable: false },
+			{ pubkey: mintY, isSigner: false, isWritable: false },
+			{ pubkey: escrowPDA, isSigner: false, isWritable: true },
+			{ pubkey: configPDA, isSigner: false, isWritable: true },
+			{ pubkey: tokenProgX, isSigner: false, isWritable: false },
+			{ pubkey: tokenProgY, isSigner: false, isWritable: false },
+			{ pubkey: SYSTEM_PROGRAM, isSigner: false, isWritable: false },
+			{ pubkey: atokenProg, isSigner: false, isWritable: false },
+		],
+		programId: vaultProgAddr,
+		data: Buffer.from([disc]),
+	});
+	sendTxns(svm, blockhash, [ix], [crankerSigner], expectedError);
+};
 
 //-------------==
 //When you want to make Mint without the Mint Keypair. E.g. UsdtMintKp;
@@ -703,8 +1708,16 @@ export const setMint = (
 	mintAuthority = owner,
 	freezeAuthority = owner,
 	programId = TOKEN_PROGRAM_ID,
+	tlvExtensions: number[] = [],
 ) => {
-	const rawMintAcctData = Buffer.alloc(MINT_SIZE);
+	//Token2022 extensions follow the padding to 165 bytes and the account type
+	const rawMintAcctData = Buffer.alloc(
+		tlvExtensions.length > 0 ? 166 + tlvExtensions.length : MINT_SIZE,
+	);
+	if (tlvExtensions.length > 0) {
+		rawMintAcctData[165] = 1; //AccountType::Mint
+		rawMintAcctData.set(tlvExtensions, 166);
+	}
 	MintLayout.encode(
 		{
 			mintAuthorityOption: 1, //0,
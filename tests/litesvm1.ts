@@ -1,19 +1,41 @@
 /** biome-ignore-all lint/style/noNonNullAssertion: <> */
 import { expect, test } from "bun:test";
+import { TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID } from "@solana/spl-token";
 //Tutorial: <https://litesvm.github.io/litesvm/tutorial.html>
-import { Connection, type Keypair, type PublicKey } from "@solana/web3.js";
+import { Connection, Keypair, PublicKey } from "@solana/web3.js";
 import type { AccountInfoBytes } from "litesvm";
-import { Status, solanaKitDecodeEscrowDev } from "./decoder";
+import {
+	ActionKind,
+	AdminPermission,
+	defaultExtDeny,
+	extBit,
+	MintExtension,
+	Status,
+	solanaKitDecodeDev,
+	solanaKitDecodeEscrowDev,
+	solanaKitDecodeMintReg,
+	solanaKitDecodeUser,
+} from "./decoder";
 import {
 	acctExists,
 	acctIsNull,
+	actionEnqueue,
+	actionExecute,
+	adminGrant,
 	ataBalCk,
 	ataBalc,
 	cancelTokEscrow,
 	configPDA,
+	configReconcile,
+	configSetExtPolicy,
+	configSetStatus,
 	depositSol,
+	expireTokEscrow,
+	findAdmin,
 	findEscrow,
+	findMintReg,
 	findPdaV1,
+	findUser,
 	getAta,
 	initConfig,
 	initSolBalc,
@@ -23,22 +45,51 @@ import {
 	lgcMintToken,
 	lgcPay,
 	lgcRedeem,
+	lgcVoucher,
 	lgcWithdraw,
 	makeTokEscrow,
+	MetadataField,
+	mintAdd,
+	mintDisable,
+	mintEnable,
+	mintRemove,
 	type PdaOut,
+	poolDepositSol,
+	poolPDA,
+	poolWithdrawSol,
 	sendSol,
+	setAta,
 	setAtaCheck,
 	setMint,
+	shareMint,
+	solVault1,
+	solVaultO,
 	svm,
 	takeTokEscrow,
+	tlvExtensionData,
+	tlvExtensionTypes,
+	tok22HarvestFees,
+	tok22InitAta,
+	tok22InitMint,
+	tok22MintToken,
+	tok22UpdateMetadata,
+	tok22UpdateRate,
 	vault1,
 	vaultAta1,
 	vaultO,
 	withdrawSol,
 	withdrawTokEscrow,
 } from "./litesvm-utils";
-import { as6zBn, as9zBn, bigintAmt, ll, zero } from "./utils";
 import {
+	as6zBn,
+	as9zBn,
+	bigintAmt,
+	bigintToBytes,
+	ll,
+	zero,
+} from "./utils";
+import {
+	ATokenGPvbd,
 	admin,
 	adminKp,
 	dgcAuthorityKp,
@@ -49,6 +100,8 @@ import {
 	owner,
 	ownerKp,
 	pyusdMint,
+	SYSTEM_PROGRAM,
+	tok22Mint,
 	usdcMint,
 	usdgMint,
 	usdtMint,
@@ -98,9 +151,12 @@ let amountY: bigint;
 let id: bigint;
 let balcAf: bigint | null;
 const vaultRent = 1002240n; //from Rust
+const solVaultRent = 1176240n; //from Rust
 const decDgc = 9;
 const initDgcBalc = bigintAmt(9000, decDgc);
 const initUsdcBalc = bigintAmt(1000, 6);
+const feeBps = 30n;
+const feeOf = (amt: bigint) => (amt * feeBps + 9999n) / 10000n;
 
 const balcBf = svm.getBalance(admin);
 ll("admin SOL:", balcBf);
@@ -116,47 +172,70 @@ test("transfer SOL", () => {
 	expect(balcAf).toStrictEqual(amount + initSolBalc);
 });
 
+test("InitConfig", () => {
+	ll("\n------== InitConfig");
+	ll("vault1:", vault1.toBase58());
+	ll(`configPDA: ${configPDA}`);
+	signerKp = user1Kp;
+	const progOwner = owner;
+	const fee = feeBps;
+	const isAuthorized = true;
+	const status = Status.Active;
+	const str = "MoonDog to the Moon!";
+
+	ll("progOwner:", progOwner.toBase58(), progOwner.toBytes());
+	initConfig(progOwner, isAuthorized, status, fee, str, signerKp);
+});
+
 test("Owner Deposits SOL to VaultPDA", () => {
 	ll("\n------== Owner Deposits SOL to VaultPDA");
-	ll("vaultO:", vaultO.toBase58());
+	ll("solVaultO:", solVaultO.toBase58());
 	signerKp = ownerKp;
 	amtDeposit = as9zBn(0.46);
 
-	depositSol(vaultO, amtDeposit, signerKp);
-	balcAf = svm.getBalance(vaultO);
-	ll("vaultO SOL:", balcAf);
-	expect(balcAf).toStrictEqual(vaultRent + amtDeposit);
+	depositSol(vaultO, amtDeposit, signerKp, "0x35"); //VaultPDA
+	depositSol(solVaultO, amtDeposit, signerKp);
+	balcAf = svm.getBalance(solVaultO);
+	ll("solVaultO SOL:", balcAf);
+	expect(balcAf).toStrictEqual(solVaultRent + amtDeposit);
+	expect(svm.getAccount(solVaultO)?.data.subarray(9)).toEqual(
+		configPDA.toBytes(),
+	);
 });
 
 test("User1 Deposits SOL to vault1", () => {
 	ll("\n------== User1 Deposits SOL to vault1");
-	ll("vault1:", vault1.toBase58());
+	ll("solVault1:", solVault1.toBase58());
 	signerKp = user1Kp;
 	amtDeposit = as9zBn(1.23); //1230000000n
 
-	depositSol(vault1, amtDeposit, signerKp);
-	balcAf = svm.getBalance(vault1);
-	ll("vault1 SOL:", balcAf);
-	expect(balcAf).toStrictEqual(vaultRent + amtDeposit);
+	depositSol(solVault1, amtDeposit, signerKp);
+	balcAf = svm.getBalance(solVault1);
+	ll("solVault1 SOL:", balcAf);
+	expect(balcAf).toStrictEqual(solVaultRent + amtDeposit);
 });
 
 test("User1 Withdraws SOL from vault1", () => {
 	ll("\n------== User1 Withdraws SOL from vault1");
-	ll("vault1:", vault1.toBase58());
+	ll("solVault1:", solVault1.toBase58());
 	signerKp = user1Kp;
 	amtWithdraw = as9zBn(0.48); //480000000n
 
-	withdrawSol(vault1, amtWithdraw, signerKp);
-	balcAf = svm.getBalance(vault1);
-	ll("vault1 SOL:", balcAf);
-	expect(balcAf).toStrictEqual(vaultRent + amtDeposit - amtWithdraw);
+	withdrawSol(solVault1, amtWithdraw, signerKp);
+	balcAf = svm.getBalance(solVault1);
+	ll("solVault1 SOL:", balcAf);
+	expect(balcAf).toStrictEqual(solVaultRent + amtDeposit - amtWithdraw);
+	const decoded = solanaKitDecodeDev(svm.getAccount(configPDA)?.data);
+	expect(decoded.solBalance).toEqual(
+		as9zBn(0.46) + amtDeposit - amtWithdraw,
+	);
 });
 //test.failing
 test("hacker cannot withdraw SOL from  vault1", () => {
 	ll("\n------== Hacker cannot withdraw SOL from vault1");
 	signerKp = hackerKp;
 	amtWithdraw = as9zBn(0.48); //480000000n
-	withdrawSol(vault1, amtWithdraw, signerKp, "0x35");
+	withdrawSol(solVault1, amtWithdraw, signerKp, "0x35");
 });
 
 //------------------==
@@ -212,31 +291,21 @@ test("Set USDT Mint and ATAs", () => {
 	setAtaCheck(usdcMint, hacker, initUsdcBalc, "Hacker USDC");
 });
 
-test("InitConfig", () => {
-	ll("\n------== InitConfig");
-	ll("vault1:", vault1.toBase58());
-	ll(`configPDA: ${configPDA}`);
-	signerKp = user1Kp;
+test("Register Mints", () => {
+	ll("\n------== Register Mints");
 	const mints = [usdcMint, usdtMint, pyusdMint, usdgMint];
-	const progOwner = owner;
-	const progAdmin = user1;
-	const fee = 111000000n;
-	const isAuthorized = true;
-	const status = Status.Active;
-	const str = "MoonDog to the Moon!";
-
-	ll("progOwner:", progOwner.toBase58(), progOwner.toBytes());
-	ll("progAdmin:", progAdmin.toBase58(), progAdmin.toBytes());
-	initConfig(
-		mints,
-		progOwner,
-		progAdmin,
-		isAuthorized,
-		status,
-		fee,
-		str,
-		signerKp,
+	mintAdd(user1Kp, usdcMint, 0n, findAdmin(user1).pda, "0x3e"); //AdminDataLengh
+	for (const mint of mints) {
+		mintAdd(ownerKp, mint, 0n);
+	}
+	const decoded = solanaKitDecodeMintReg(
+		svm.getAccount(findMintReg(usdcMint).pda)?.data,
 	);
+	expect(decoded.config).toEqual(configPDA.toBase58());
+	expect(decoded.mint).toEqual(usdcMint.toBase58());
+	expect(decoded.decimals).toEqual(6);
+	expect(decoded.isEnabled).toEqual(true);
+	expect(decoded.depositCap).toEqual(0n);
 });
 
 test("Deposit Lgc Tokens", () => {
@@ -251,6 +320,16 @@ test("Deposit Lgc Tokens", () => {
 	vaultOut = findPdaV1(signer, "signerVault");
 	toAta = getAta(mint, vaultOut.pda);
 
+	const userPda = findUser(signer, mint).pda;
+
+	ll("a Mint Registry of another config cannot book deposits on this config");
+	const regRaw = svm.getAccount(findMintReg(mint).pda);
+	if (!regRaw) throw new Error("mintReg is null");
+	const otherConfig = PublicKey.unique();
+	const foreignReg = Uint8Array.from(regRaw.data);
+	foreignReg.set(otherConfig.toBytes(), 9);
+	const foreignRegPda = findMintReg(mint, otherConfig).pda;
+	svm.setAccount(foreignRegPda, { ...regRaw, data: foreignReg });
 	lgcDeposit(
 		signerKp,
 		fromAta,
@@ -258,11 +337,37 @@ test("Deposit Lgc Tokens", () => {
 		vaultOut.pda,
 		mint,
 		configPDA,
+		foreignRegPda,
+		userPda,
+		decimals,
+		amt,
+		TOKEN_PROGRAM_ID,
+		ATokenGPvbd,
+		"0x84",
+	); //MintRegConfig
+
+	lgcDeposit(
+		signerKp,
+		fromAta,
+		toAta,
+		vaultOut.pda,
+		mint,
+		configPDA,
+		findMintReg(mint).pda,
+		userPda,
 		decimals,
 		amt,
 	);
 	ataBalCk(toAta, as6zBn(370), "vault1");
 	ataBalCk(fromAta, as6zBn(630), "user1 ");
+
+	const decoded = solanaKitDecodeUser(svm.getAccount(userPda)?.data);
+	expect(decoded.config).toEqual(configPDA.toBase58());
+	expect(decoded.user).toEqual(signer.toBase58());
+	expect(decoded.mint).toEqual(mint.toBase58());
+	expect(decoded.deposited).toEqual(as6zBn(370));
+	expect(decoded.totalDeposited).toEqual(as6zBn(370));
+	expect(decoded.updatedAt).toBeGreaterThan(0);
 });
 test("Withdraw Lgc Tokens", () => {
 	ll("\n------== Withdraw Lgc Tokens");
@@ -276,9 +381,71 @@ test("Withdraw Lgc Tokens", () => {
 	vaultOut = findPdaV1(signer, "signerVault");
 	fromAta = getAta(mint, vaultOut.pda);
 
-	lgcWithdraw(signerKp, fromAta, toAta, vaultOut.pda, mint, decimals, amt);
+	const userPda = findUser(signer, mint).pda;
+
+	lgcWithdraw(
+		signerKp,
+		fromAta,
+		toAta,
+		vaultOut.pda,
+		mint,
+		configPDA,
+		findMintReg(mint).pda,
+		userPda,
+		decimals,
+		amt,
+	);
 	ataBalCk(fromAta, as6zBn(250), "vault1");
 	ataBalCk(toAta, as6zBn(750), "user1 ");
+
+	const decoded = solanaKitDecodeUser(svm.getAccount(userPda)?.data);
+	expect(decoded.deposited).toEqual(as6zBn(250));
+	expect(decoded.totalDeposited).toEqual(as6zBn(370));
+	expect(decoded.totalWithdrawn).toEqual(as6zBn(120));
+
+	ll("deposits made before the ledger can be withdrawn without a User PDA");
+	const userRaw = svm.getAccount(userPda);
+	const fromAtaRaw = svm.getAccount(fromAta);
+	const toAtaRaw = svm.getAccount(toAta);
+	if (!userRaw || !fromAtaRaw || !toAtaRaw) throw new Error("account is null");
+	svm.setAccount(userPda, {
+		...userRaw,
+		lamports: 0,
+		data: new Uint8Array(0),
+		owner: SYSTEM_PROGRAM,
+	});
+	lgcWithdraw(
+		signerKp,
+		fromAta,
+		toAta,
+		vaultOut.pda,
+		mint,
+		configPDA,
+		findMintReg(mint).pda,
+		findUser(signer, mint, user2).pda,
+		decimals,
+		as6zBn(10),
+		TOKEN_PROGRAM_ID,
+		ATokenGPvbd,
+		"0x37",
+	); //UserPDA
+	lgcWithdraw(
+		signerKp,
+		fromAta,
+		toAta,
+		vaultOut.pda,
+		mint,
+		configPDA,
+		findMintReg(mint).pda,
+		userPda,
+		decimals,
+		as6zBn(10),
+	);
+	ataBalCk(fromAta, as6zBn(240), "vault1");
+	ataBalCk(toAta, as6zBn(760), "user1 ");
+	svm.setAccount(userPda, userRaw);
+	svm.setAccount(fromAta, fromAtaRaw);
+	svm.setAccount(toAta, toAtaRaw);
 });
 
 test("Pay Lgc Tokens", () => {
@@ -292,9 +459,29 @@ test("Pay Lgc Tokens", () => {
 	fromAta = getAta(mint, signer);
 	toAta = getAta(mint, vaultO);
 
-	lgcPay(signerKp, fromAta, toAta, vaultO, mint, configPDA, decimals, amt);
+	const userPda = findUser(signer, mint).pda;
+
+	lgcPay(
+		signerKp,
+		fromAta,
+		toAta,
+		vaultO,
+		mint,
+		configPDA,
+		findMintReg(mint).pda,
+		userPda,
+		decimals,
+		amt,
+	);
 	ataBalCk(toAta, amt, "vaultO");
 	ataBalCk(fromAta, as6zBn(424), "user1 ");
+	expect(solanaKitDecodeDev(svm.getAccount(configPDA)?.data).tokenBalance).toEqual(
+		amt,
+	);
+
+	const decoded = solanaKitDecodeUser(svm.getAccount(userPda)?.data);
+	expect(decoded.totalPaid).toEqual(amt);
+	expect(decoded.deposited).toEqual(as6zBn(250));
 });
 test("Redeem Lgc Tokens", () => {
 	ll("\n------== Redeem Lgc Tokens");
@@ -307,6 +494,7 @@ test("Redeem Lgc Tokens", () => {
 	toAta = getAta(mint, signer);
 	vaultOut = findPdaV1(owner, "Vault");
 	fromAta = getAta(mint, vaultOut.pda);
+	const userPda = findUser(signer, mint).pda;
 
 	lgcRedeem(
 		signerKp,
@@ -315,11 +503,212 @@ test("Redeem Lgc Tokens", () => {
 		vaultOut.pda,
 		configPDA,
 		mint,
+		findMintReg(mint).pda,
+		userPda,
 		decimals,
 		amt,
 	);
 	ataBalCk(fromAta, as6zBn(289), "vaultO");
 	ataBalCk(toAta, as6zBn(461), "user1 ");
+
+	const decoded = solanaKitDecodeUser(svm.getAccount(userPda)?.data);
+	expect(decoded.totalRedeemed).toEqual(amt);
+	expect(decoded.redeemable).toEqual(as6zBn(289));
+	expect(solanaKitDecodeDev(svm.getAccount(configPDA)?.data).tokenBalance).toEqual(
+		as6zBn(289),
+	);
+});
+test("Redeem Lgc Tokens beyond redeemable", () => {
+	ll("\n------== Redeem Lgc Tokens beyond redeemable");
+	mint = usdcMint;
+	decimals = 6;
+	vaultOut = findPdaV1(owner, "Vault");
+	fromAta = getAta(mint, vaultOut.pda);
+
+	signerKp = user2Kp;
+	signer = signerKp.publicKey;
+	toAta = getAta(mint, signer);
+	const userPda = findUser(signer, mint).pda;
+	const redeem = (amount: bigint, expectedError = "") =>
+		lgcRedeem(
+			signerKp,
+			fromAta,
+			toAta,
+			vaultOut.pda,
+			configPDA,
+			mint,
+			findMintReg(mint).pda,
+			userPda,
+			decimals,
+			amount,
+			undefined,
+			undefined,
+			expectedError,
+		);
+	redeem(as6zBn(1), "0x3b"); //UserInsuffRedeemable
+
+	const mintReg = findMintReg(mint).pda;
+	const voucher = (voucherKp: Keypair, adminPda = configPDA, expectedError = "") =>
+		lgcVoucher(
+			voucherKp,
+			signer,
+			mint,
+			mintReg,
+			userPda,
+			as6zBn(5),
+			adminPda,
+			expectedError,
+		);
+	voucher(user1Kp, findAdmin(user1).pda, "0x3e"); //AdminDataLengh
+	voucher(ownerKp, configPDA, "0x4"); //OnlyUser: no User PDA made for someone else
+
+	ll("a pre-funded User PDA address is still made by the user's own deposit");
+	sendSol(userPda, 1000n, hackerKp);
+	const user2Vault = findPdaV1(signer, "user2Vault").pda;
+	const user2VaultAta = getAta(mint, user2Vault);
+	lgcDeposit(
+		signerKp,
+		toAta,
+		user2VaultAta,
+		user2Vault,
+		mint,
+		configPDA,
+		mintReg,
+		userPda,
+		decimals,
+		as6zBn(1),
+	);
+	lgcWithdraw(
+		signerKp,
+		user2VaultAta,
+		toAta,
+		user2Vault,
+		mint,
+		configPDA,
+		mintReg,
+		userPda,
+		decimals,
+		as6zBn(1),
+	);
+	expect(solanaKitDecodeUser(svm.getAccount(userPda)?.data).config).toEqual(
+		configPDA.toBase58(),
+	);
+	voucher(ownerKp);
+	const decoded = solanaKitDecodeUser(svm.getAccount(userPda)?.data);
+	expect(decoded.redeemable).toEqual(as6zBn(5));
+
+	redeem(as6zBn(6), "0x3b"); //UserInsuffRedeemable
+
+	ll("a token_balance drifted below the redeem saturates instead of trapping it");
+	const setTokenBalance = (amount: bigint) => {
+		const configRaw = svm.getAccount(configPDA);
+		if (!configRaw) throw new Error("config is null");
+		const data = Uint8Array.from(configRaw.data);
+		new DataView(data.buffer).setBigUint64(185, amount, true); //token_balance
+		svm.setAccount(configPDA, { ...configRaw, data });
+	};
+	setTokenBalance(as6zBn(2));
+	redeem(as6zBn(5));
+	ataBalCk(fromAta, as6zBn(284), "vaultO");
+	expect(solanaKitDecodeDev(svm.getAccount(configPDA)?.data).tokenBalance).toEqual(0n);
+	setTokenBalance(as6zBn(284));
+});
+
+test("Mint Registry", () => {
+	ll("\n------== Mint Registry");
+	signerKp = user1Kp;
+	mint = usdcMint;
+	decimals = 6;
+	amt = as6zBn(10);
+	signer = signerKp.publicKey;
+	vaultOut = findPdaV1(signer, "signerVault");
+	const userAta = getAta(mint, signer);
+	const vaultAta = getAta(mint, vaultOut.pda);
+	const mintReg = findMintReg(mint).pda;
+	const userPda = findUser(signer, mint).pda;
+	const deposit = (expectedError = "") =>
+		lgcDeposit(
+			signerKp,
+			userAta,
+			vaultAta,
+			vaultOut.pda,
+			mint,
+			configPDA,
+			mintReg,
+			userPda,
+			decimals,
+			amt,
+			undefined,
+			undefined,
+			expectedError,
+		);
+	let decoded = solanaKitDecodeMintReg(svm.getAccount(mintReg)?.data);
+	expect(decoded.deposited).toEqual(as6zBn(250));
+
+	mintDisable(user1Kp, mint, findAdmin(user1).pda, "0x3e"); //AdminDataLengh
+	mintDisable(ownerKp, mint);
+	deposit("0x44"); //MintDisabled
+	ll("withdraw is allowed from a disabled mint");
+	lgcWithdraw(
+		signerKp,
+		vaultAta,
+		userAta,
+		vaultOut.pda,
+		mint,
+		configPDA,
+		mintReg,
+		userPda,
+		decimals,
+		amt,
+	);
+	mintEnable(ownerKp, mint);
+	deposit();
+	decoded = solanaKitDecodeMintReg(svm.getAccount(mintReg)?.data);
+	expect(decoded.deposited).toEqual(as6zBn(250));
+	expect(decoded.isEnabled).toEqual(true);
+
+	mintRemove(ownerKp, mint, undefined, "0x57"); //MintRegHasDeposits
+	mintRemove(ownerKp, usdtMint);
+	acctIsNull(findMintReg(usdtMint).pda);
+	mintAdd(ownerKp, usdtMint, as6zBn(1000));
+	decoded = solanaKitDecodeMintReg(
+		svm.getAccount(findMintReg(usdtMint).pda)?.data,
+	);
+	expect(decoded.depositCap).toEqual(as6zBn(1000));
+});
+
+test("Reconcile Config Balances", () => {
+	ll("\n------== Reconcile Config Balances");
+	const vaultAtaO = getAta(usdcMint, vaultO);
+	configReconcile(user1Kp, [solVault1], findAdmin(user1).pda, "0x3e"); //AdminDataLengh
+	configReconcile(ownerKp, [solVaultO, solVault1, solVault1], configPDA, "0x58"); //DuplicateAccount
+
+	let logs = configReconcile(ownerKp, [solVaultO, solVault1, vaultAtaO]);
+	expect(logs.some((log) => log.includes("sol_balance") && log.includes("surplus 0"))).toEqual(true);
+	expect(logs.some((log) => log.includes("token_balance") && log.includes("surplus 0"))).toEqual(true);
+
+	ll("a SOL vault left out shows a shortfall");
+	logs = configReconcile(ownerKp, [solVault1, vaultAtaO]);
+	expect(logs.some((log) => log.includes(`shortfall ${as9zBn(0.46)}`))).toEqual(true);
+
+	ll("only SOL vaults bound to this config are counted");
+	configReconcile(ownerKp, [vault1, vaultAtaO], configPDA, "0x3d"); //VaultDataLengh
+	const vaultRaw = svm.getAccount(solVault1);
+	if (!vaultRaw) throw new Error("solVault1 is null");
+	const foreignVault = Uint8Array.from(vaultRaw.data);
+	foreignVault.set(user2.toBytes(), 9);
+	const foreignVaultPda = new Keypair().publicKey;
+	svm.setAccount(foreignVaultPda, { ...vaultRaw, data: foreignVault });
+	configReconcile(ownerKp, [foreignVaultPda], configPDA, "0x89"); //VaultConfig
+
+	ll("SOL in a vault seeded by the user alone is withdrawn without touching sol_balance");
+	const legacyRaw = svm.getAccount(vault1);
+	if (!legacyRaw) throw new Error("vault1 is null");
+	svm.setAccount(vault1, { ...legacyRaw, lamports: legacyRaw.lamports + 2000 });
+	const solBalance = solanaKitDecodeDev(svm.getAccount(configPDA)?.data).solBalance;
+	withdrawSol(vault1, 1000n, user1Kp);
+	expect(svm.getBalance(vault1)).toEqual(BigInt(legacyRaw.lamports + 1000));
+	expect(solanaKitDecodeDev(svm.getAccount(configPDA)?.data).solBalance).toEqual(solBalance);
 });
 
 test("Make Token Escrow", () => {
@@ -360,6 +749,7 @@ test("Make Token Escrow", () => {
 	ll("rawAccountData:", rawAccountData);
 
 	const decoded = solanaKitDecodeEscrowDev(rawAccountData);
+	expect(decoded.config).toEqual(configPDA);
 	expect(decoded.maker).toEqual(signer);
 	expect(decoded.mintX).toEqual(mintX);
 	expect(decoded.mintY).toEqual(mintY);
@@ -372,16 +762,16 @@ test("Make Token Escrow", () => {
 	ataBalCk(escrowAtaX, amountX, "Escrow");
 	ataBalCk(makerAtaX, prevBalcX - amountX, "user1 ");
 });
-test("Take Token Escrow", () => {
-	ll("\n------== Take Token Escrow");
+test("Take Token Escrow Partially", () => {
+	ll("\n------== Take Token Escrow Partially");
 	signerKp = user2Kp;
 	//args below should be taken from EscrowPDA
 	mintX = usdcMint;
 	mintY = dragonCoin;
 	decimalX = 6;
 	decimalY = decDgc;
-	amountX = bigintAmt(326, decimalX);
-	amountY = bigintAmt(2100, decimalY);
+	amountX = bigintAmt(163, decimalX);
+	amountY = bigintAmt(1050, decimalY);
 	id = BigInt(1);
 	escrowPDA = escrowU1_1;
 
@@ -391,7 +781,42 @@ test("Take Token Escrow", () => {
 
 	takerAtaY = getAta(mintY, signer);
 	escrowAtaY = getAta(mintY, escrowPDA);
+	makerAtaY = getAta(mintY, user1);
 	prevBalcX = ataBalc(takerAtaX, "takerAtaX");
+	const prevTokenBalance = solanaKitDecodeDev(
+		svm.getAccount(configPDA)?.data,
+	).tokenBalance;
+
+	ll("an escrow cannot be taken against another config");
+	const configRaw = svm.getAccount(configPDA);
+	if (!configRaw) throw new Error("config is null");
+	const otherConfig = PublicKey.unique();
+	svm.setAccount(otherConfig, configRaw);
+	takeTokEscrow(
+		signerKp,
+		takerAtaX,
+		takerAtaY,
+		escrowAtaX,
+		escrowAtaY,
+		mintX,
+		mintY,
+		escrowPDA,
+		otherConfig,
+		user1,
+		makerAtaY,
+		vaultO,
+		getAta(mintY, vaultO),
+		decimalX,
+		amountX,
+		decimalY,
+		amountY,
+		id,
+		TOKEN_PROGRAM_ID,
+		TOKEN_PROGRAM_ID,
+		ATokenGPvbd,
+		"0x86",
+	); //EscrowConfig
+
 	takeTokEscrow(
 		signerKp,
 		takerAtaX,
@@ -402,6 +827,10 @@ test("Take Token Escrow", () => {
 		mintY,
 		escrowPDA,
 		configPDA,
+		user1,
+		makerAtaY,
+		vaultO,
+		getAta(mintY, vaultO),
 		decimalX,
 		amountX,
 		decimalY,
@@ -413,9 +842,16 @@ test("Take Token Escrow", () => {
 	const rawAccountData = pdaRaw?.data;
 	ll("rawAccountData:", rawAccountData);
 
-	const _decoded = solanaKitDecodeEscrowDev(rawAccountData);
-	ataBalCk(escrowAtaX, zero, "Escrow X");
-	ataBalCk(escrowAtaY, amountY, "Escrow Y");
+	const decoded = solanaKitDecodeEscrowDev(rawAccountData);
+	expect(decoded.remainingX).toEqual(bigintAmt(326, decimalX) - amountX);
+	expect(decoded.receivedY).toEqual(amountY - feeOf(amountY));
+	ataBalCk(escrowAtaX, bigintAmt(326, decimalX) - amountX, "Escrow X");
+	ataBalCk(escrowAtaY, amountY - feeOf(amountY), "Escrow Y");
+	ataBalCk(getAta(mintY, vaultO), feeOf(amountY), "Vault Y");
+	const decodedConfig = solanaKitDecodeDev(svm.getAccount(configPDA)?.data);
+	expect(decodedConfig.tokenBalance).toEqual(
+		prevTokenBalance + feeOf(amountY),
+	);
 	ataBalCk(takerAtaX, prevBalcX + amountX, "Taker X");
 });
 test("Withdraw TokenY on Escrow", () => {
@@ -446,10 +882,56 @@ test("Withdraw TokenY on Escrow", () => {
 		escrowPDA,
 		configPDA,
 	);
-	ataBalCk(escrowAtaX, zero, "Escrow X");
+	ataBalCk(escrowAtaX, amountX, "Escrow X");
 	ataBalCk(escrowAtaY, zero, "Escrow Y");
 	ataBalCk(makerAtaX, prevBalcX, "user1 X");
-	ataBalCk(makerAtaY, prevBalcY + amountY, "user1 Y");
+	ataBalCk(makerAtaY, prevBalcY + amountY - feeOf(amountY), "user1 Y");
+	const decoded = solanaKitDecodeEscrowDev(svm.getAccount(escrowPDA)?.data);
+	expect(decoded.remainingX).toEqual(amountX);
+	expect(decoded.receivedY).toEqual(zero);
+});
+test("Take the Rest of Token Escrow", () => {
+	ll("\n------== Take the Rest of Token Escrow");
+	signerKp = user2Kp;
+	mintX = usdcMint;
+	mintY = dragonCoin;
+	decimalX = 6;
+	decimalY = decDgc;
+	amountX = bigintAmt(163, decimalX);
+	amountY = bigintAmt(1050, decimalY);
+	id = BigInt(1);
+	escrowPDA = escrowU1_1;
+
+	signer = signerKp.publicKey;
+	takerAtaX = getAta(mintX, signer);
+	escrowAtaX = getAta(mintX, escrowPDA);
+	takerAtaY = getAta(mintY, signer);
+	escrowAtaY = getAta(mintY, escrowPDA);
+	makerAtaY = getAta(mintY, user1);
+	prevBalcX = ataBalc(takerAtaX, "takerAtaX");
+	prevBalcY = ataBalc(makerAtaY, "makerAtaY");
+	takeTokEscrow(
+		signerKp,
+		takerAtaX,
+		takerAtaY,
+		escrowAtaX,
+		escrowAtaY,
+		mintX,
+		mintY,
+		escrowPDA,
+		configPDA,
+		user1,
+		makerAtaY,
+		vaultO,
+		getAta(mintY, vaultO),
+		decimalX,
+		amountX,
+		decimalY,
+		amountY,
+		id,
+	);
+	ataBalCk(takerAtaX, prevBalcX + amountX, "Taker X");
+	ataBalCk(makerAtaY, prevBalcY + amountY - feeOf(amountY), "user1 Y");
 	rawAccount = svm.getAccount(escrowAtaX);
 	expect(rawAccount).toBeNull();
 	rawAccount = svm.getAccount(escrowAtaY);
@@ -493,6 +975,15 @@ test("Make & Cancel Token Escrow", () => {
 	ataBalCk(escrowAtaX, amountX, "Escrow");
 	ataBalCk(makerAtaX, prevBalcX - amountX, "user1 ");
 
+	ll("the maker can still cancel after the config is closed");
+	const configRaw = svm.getAccount(configPDA);
+	if (!configRaw) throw new Error("configPDA is null");
+	svm.setAccount(configPDA, {
+		...configRaw,
+		lamports: 0,
+		data: new Uint8Array(0),
+		owner: SYSTEM_PROGRAM,
+	});
 	cancelTokEscrow(
 		signerKp,
 		makerAtaX,
@@ -504,6 +995,7 @@ test("Make & Cancel Token Escrow", () => {
 		escrowPDA,
 		configPDA,
 	);
+	svm.setAccount(configPDA, configRaw);
 	ataBalCk(escrowAtaX, zero, "Escrow");
 	ataBalCk(makerAtaX, prevBalcX, "user1 ");
 	rawAccount = svm.getAccount(escrowAtaX);
@@ -514,6 +1006,977 @@ test("Make & Cancel Token Escrow", () => {
 	expect(rawAccount).toBeNull();
 });
 
+test("Make & Take Token Escrow with Token2022 Mint X", () => {
+	ll("\n------== Make & Take Token Escrow with Token2022 Mint X");
+	mintX = tok22Mint;
+	mintY = dragonCoin;
+	setMint(mintX, 6, 9_000_000_000_000n, owner, owner, TOKEN_2022_PROGRAM_ID);
+	setAta(mintX, user1, initUsdcBalc, true, TOKEN_2022_PROGRAM_ID);
+	signerKp = user1Kp;
+	decimalX = 6;
+	decimalY = decDgc;
+	amountX = bigintAmt(100, decimalX);
+	amountY = bigintAmt(500, decimalY);
+	id = BigInt(2);
+	signer = signerKp.publicKey;
+	escrowOut = findEscrow(signer, id);
+	escrowPDA = escrowOut.pda;
+
+	makerAtaX = getAta(mintX, signer, true, TOKEN_2022_PROGRAM_ID);
+	escrowAtaX = getAta(mintX, escrowPDA, true, TOKEN_2022_PROGRAM_ID);
+	makeTokEscrow(
+		signerKp,
+		makerAtaX,
+		escrowAtaX,
+		mintX,
+		mintY,
+		escrowPDA,
+		configPDA,
+		decimalX,
+		amountX,
+		decimalY,
+		amountY,
+		id,
+		TOKEN_2022_PROGRAM_ID,
+	);
+	ataBalCk(escrowAtaX, amountX, "Escrow X");
+	ataBalCk(makerAtaX, initUsdcBalc - amountX, "user1 X");
+
+	signerKp = user2Kp;
+	signer = signerKp.publicKey;
+	takerAtaX = getAta(mintX, signer, true, TOKEN_2022_PROGRAM_ID);
+	takerAtaY = getAta(mintY, signer);
+	escrowAtaY = getAta(mintY, escrowPDA);
+	makerAtaY = getAta(mintY, user1);
+	prevBalcY = ataBalc(makerAtaY, "makerAtaY");
+	takeTokEscrow(
+		signerKp,
+		takerAtaX,
+		takerAtaY,
+		escrowAtaX,
+		escrowAtaY,
+		mintX,
+		mintY,
+		escrowPDA,
+		configPDA,
+		user1,
+		makerAtaY,
+		vaultO,
+		getAta(mintY, vaultO),
+		decimalX,
+		amountX,
+		decimalY,
+		amountY,
+		id,
+		TOKEN_2022_PROGRAM_ID,
+	);
+	ataBalCk(takerAtaX, amountX, "Taker X");
+	ataBalCk(makerAtaY, prevBalcY + amountY - feeOf(amountY), "user1 Y");
+	rawAccount = svm.getAccount(escrowAtaX);
+	expect(rawAccount).toBeNull();
+	rawAccount = svm.getAccount(escrowPDA);
+	expect(rawAccount).toBeNull();
+});
+
+test("Deposit, Withdraw, Pay & Redeem Token2022 Tokens", () => {
+	ll("\n------== Deposit, Withdraw, Pay & Redeem Token2022 Tokens");
+	signerKp = user1Kp;
+	signer = signerKp.publicKey;
+	mint = tok22Mint;
+	decimals = 6;
+	mintAdd(ownerKp, mint, 0n);
+	const tok22 = TOKEN_2022_PROGRAM_ID;
+	const userAta = getAta(mint, signer, true, tok22);
+	const initBalc = ataBalc(userAta, "user1");
+	vaultOut = findPdaV1(signer, "signerVault");
+	const vaultAta = getAta(mint, vaultOut.pda, true, tok22);
+	const userPda = findUser(signer, mint).pda;
+	const mintReg = findMintReg(mint).pda;
+
+	amt = as6zBn(200);
+	lgcDeposit(
+		signerKp,
+		userAta,
+		vaultAta,
+		vaultOut.pda,
+		mint,
+		configPDA,
+		mintReg,
+		userPda,
+		decimals,
+		amt,
+		TOKEN_PROGRAM_ID,
+		undefined,
+		"0x49", //MintOrTokenProgram
+	);
+	lgcDeposit(
+		signerKp,
+		userAta,
+		vaultAta,
+		vaultOut.pda,
+		mint,
+		configPDA,
+		mintReg,
+		userPda,
+		decimals,
+		amt,
+		tok22,
+	);
+	ataBalCk(vaultAta, amt, "vault1");
+	ataBalCk(userAta, initBalc - amt, "user1 ");
+
+	amt = as6zBn(50);
+	lgcWithdraw(
+		signerKp,
+		vaultAta,
+		userAta,
+		vaultOut.pda,
+		mint,
+		configPDA,
+		mintReg,
+		userPda,
+		decimals,
+		amt,
+		tok22,
+	);
+	ataBalCk(vaultAta, as6zBn(150), "vault1");
+	let decoded = solanaKitDecodeUser(svm.getAccount(userPda)?.data);
+	expect(decoded.deposited).toEqual(as6zBn(150));
+
+	const tokenBalcBf = solanaKitDecodeDev(
+		svm.getAccount(configPDA)?.data,
+	).tokenBalance;
+	const vaultOAta = getAta(mint, vaultO, true, tok22);
+	amt = as6zBn(100);
+	lgcPay(
+		signerKp,
+		userAta,
+		vaultOAta,
+		vaultO,
+		mint,
+		configPDA,
+		mintReg,
+		userPda,
+		decimals,
+		amt,
+		tok22,
+	);
+	ataBalCk(vaultOAta, amt, "vaultO");
+
+	amt = as6zBn(40);
+	lgcRedeem(
+		signerKp,
+		vaultOAta,
+		userAta,
+		vaultO,
+		configPDA,
+		mint,
+		mintReg,
+		userPda,
+		decimals,
+		amt,
+		tok22,
+	);
+	ataBalCk(vaultOAta, as6zBn(60), "vaultO");
+	ataBalCk(userAta, initBalc - as6zBn(210), "user1 ");
+	decoded = solanaKitDecodeUser(svm.getAccount(userPda)?.data);
+	expect(decoded.totalPaid).toEqual(as6zBn(100));
+	expect(decoded.redeemable).toEqual(as6zBn(60));
+	expect(solanaKitDecodeDev(svm.getAccount(configPDA)?.data).tokenBalance).toEqual(
+		tokenBalcBf + as6zBn(60),
+	);
+});
+
+test("Token2022 Mint with Metadata", () => {
+	ll("\n------== Token2022 Mint with Metadata");
+	mintKp = new Keypair();
+	mint = mintKp.publicKey;
+	mintAuthorityKp = dgcAuthorityKp;
+	tok22InitMint(
+		ownerKp,
+		mintKp,
+		mintAuthorityKp,
+		9,
+		"MoonDog",
+		"MDC",
+		"http://moondog.com",
+	);
+	const metadataStr = () =>
+		Buffer.from(svm.getAccount(mint)?.data ?? []).toString("latin1");
+	rawAccount = svm.getAccount(mint);
+	expect(rawAccount?.owner).toEqual(TOKEN_2022_PROGRAM_ID);
+	const initLen = rawAccount?.data.length ?? 0;
+	expect(initLen).toBeGreaterThan(234);
+	expect(metadataStr()).toContain("MoonDog");
+	expect(metadataStr()).toContain("http://moondog.com");
+	//the MetadataPointer points to the mint itself
+	expect(metadataStr()).toContain(
+		Buffer.from(mint.toBytes()).toString("latin1"),
+	);
+
+	tok22UpdateMetadata(
+		mintAuthorityKp,
+		mint,
+		4,
+		"",
+		"MoonCat",
+		"0x74", //MetadataField
+	);
+	tok22UpdateMetadata(
+		mintAuthorityKp,
+		mint,
+		MetadataField.key,
+		"",
+		"moondog.com",
+		"0x74", //MetadataField
+	);
+	tok22UpdateMetadata(mintAuthorityKp, mint, MetadataField.name, "", "MoonCat");
+	expect(metadataStr()).toContain("MoonCat");
+	expect(metadataStr()).not.toContain("MoonDog");
+
+	tok22UpdateMetadata(
+		mintAuthorityKp,
+		mint,
+		MetadataField.key,
+		"website",
+		"moondog.com",
+	);
+	expect(metadataStr()).toContain("website");
+	expect(metadataStr()).toContain("moondog.com");
+	rawAccount = svm.getAccount(mint);
+	expect(rawAccount?.data.length ?? 0).toBeGreaterThan(initLen);
+	expect(BigInt(rawAccount?.lamports ?? 0)).toBeGreaterThanOrEqual(
+		svm.minimumBalanceForRentExemption(BigInt(rawAccount?.data.length ?? 0)),
+	);
+});
+
+test("Token2022 Transfer Fee: Deposit, Withdraw & Harvest", () => {
+	ll("\n------== Token2022 Transfer Fee: Deposit, Withdraw & Harvest");
+	const tok22 = TOKEN_2022_PROGRAM_ID;
+	signerKp = user1Kp;
+	signer = signerKp.publicKey;
+	mintAuthorityKp = dgcAuthorityKp;
+	decimals = 6;
+	const transferFee = {
+		basisPoints: 100,
+		maximumFee: as6zBn(1000),
+		withdrawAuthority: vaultO,
+	};
+	tok22InitMint(
+		ownerKp,
+		new Keypair(),
+		mintAuthorityKp,
+		decimals,
+		"FeeCoin",
+		"FEE",
+		"http://feecoin.com",
+		"0x75", //TransferFeeBasisPoints
+		{ transferFee: { ...transferFee, basisPoints: 10_001 } },
+	);
+	mintKp = new Keypair();
+	mint = mintKp.publicKey;
+	tok22InitMint(
+		ownerKp,
+		mintKp,
+		mintAuthorityKp,
+		decimals,
+		"FeeCoin",
+		"FEE",
+		"http://feecoin.com",
+		"",
+		{ transferFee },
+	);
+	tok22MintToken(mintAuthorityKp, signer, mint, decimals, as6zBn(1000));
+	const userAta = getAta(mint, signer, true, tok22);
+	ataBalCk(userAta, as6zBn(1000), "user1");
+	mintAdd(ownerKp, mint, 0n);
+
+	vaultOut = findPdaV1(signer, "signerVault");
+	const vaultAta = getAta(mint, vaultOut.pda, true, tok22);
+	const userPda = findUser(signer, mint).pda;
+	const mintReg = findMintReg(mint).pda;
+	amt = as6zBn(200);
+	lgcDeposit(
+		signerKp,
+		userAta,
+		vaultAta,
+		vaultOut.pda,
+		mint,
+		configPDA,
+		mintReg,
+		userPda,
+		decimals,
+		amt,
+		tok22,
+	);
+	//1% fee is withheld in the vault ATA
+	ataBalCk(vaultAta, as6zBn(198), "vault1");
+	ataBalCk(userAta, as6zBn(800), "user1");
+	const decoded = solanaKitDecodeUser(svm.getAccount(userPda)?.data);
+	expect(decoded.deposited).toEqual(as6zBn(198));
+
+	amt = as6zBn(100);
+	lgcWithdraw(
+		signerKp,
+		vaultAta,
+		userAta,
+		vaultOut.pda,
+		mint,
+		configPDA,
+		mintReg,
+		userPda,
+		decimals,
+		amt,
+		tok22,
+	);
+	ataBalCk(vaultAta, as6zBn(98), "vault1");
+	ataBalCk(userAta, as6zBn(899), "user1");
+
+	const vaultOAta = getAta(mint, vaultO, true, tok22);
+	tok22HarvestFees(
+		signerKp,
+		tok22Mint,
+		getAta(tok22Mint, vaultO, true, tok22),
+		[],
+		"0x76", //TransferFeeConfig
+	);
+	const tokenBalcBf = solanaKitDecodeDev(
+		svm.getAccount(configPDA)?.data,
+	).tokenBalance;
+	tok22HarvestFees(signerKp, mint, vaultOAta, [vaultAta, userAta]);
+	ataBalCk(vaultOAta, as6zBn(3), "vaultO");
+	const tokenBalcAf = solanaKitDecodeDev(
+		svm.getAccount(configPDA)?.data,
+	).tokenBalance;
+	expect(tokenBalcAf).toEqual(tokenBalcBf + as6zBn(3));
+});
+test("Token2022 NonTransferable badges", () => {
+	ll("\n------== Token2022 NonTransferable badges");
+	const tok22 = TOKEN_2022_PROGRAM_ID;
+	mintKp = new Keypair();
+	mint = mintKp.publicKey;
+	mintAuthorityKp = dgcAuthorityKp;
+	decimals = 0;
+	tok22InitMint(
+		ownerKp,
+		mintKp,
+		mintAuthorityKp,
+		decimals,
+		"Member",
+		"MBR",
+		"http://members.com",
+		"",
+		{ nonTransferable: true },
+	);
+	expect(tlvExtensionTypes(svm.getAccount(mint)?.data)).toContain(
+		MintExtension.nonTransferable,
+	);
+
+	signerKp = user2Kp;
+	signer = signerKp.publicKey;
+	const userAta = tok22InitAta(signerKp, signer, mint, true);
+	expect(tlvExtensionTypes(svm.getAccount(userAta)?.data)).toContain(
+		MintExtension.immutableOwner,
+	);
+	tok22MintToken(mintAuthorityKp, signer, mint, decimals, 1n);
+	ataBalCk(userAta, 1n, "user2", decimals);
+
+	ll("badges cannot be deposited under the default extension policy");
+	vaultOut = findPdaV1(signer, "signerVault");
+	lgcDeposit(
+		signerKp,
+		userAta,
+		getAta(mint, vaultOut.pda, true, tok22),
+		vaultOut.pda,
+		mint,
+		configPDA,
+		findMintReg(mint).pda,
+		findUser(signer, mint).pda,
+		decimals,
+		1n,
+		tok22,
+		undefined,
+		"0x79", //MintExtensionDenied
+	);
+	ataBalCk(userAta, 1n, "user2", decimals);
+});
+
+test("Token2022 InterestBearing rate set by config admins", () => {
+	ll("\n------== Token2022 InterestBearing rate set by config admins");
+	mintKp = new Keypair();
+	mint = mintKp.publicKey;
+	mintAuthorityKp = dgcAuthorityKp;
+	decimals = 6;
+	const initSavings = (rateAdminPda = configPDA, expectedError = "") =>
+		tok22InitMint(
+			ownerKp,
+			mintKp,
+			mintAuthorityKp,
+			decimals,
+			"Savings",
+			"SAV",
+			"http://savings.com",
+			expectedError,
+			{ interestRate: 500, rateAdminPda },
+		);
+	ll("the mint authority needs the FEE permission to make the config its rate authority");
+	initSavings(configPDA, "0x3e"); //AdminDataLengh
+	const rateAdminPda = findAdmin(mintAuthorityKp.publicKey).pda;
+	adminGrant(ownerKp, rateAdminPda, mintAuthorityKp.publicKey, AdminPermission.fee);
+	svm.expireBlockhash();
+	initSavings(rateAdminPda);
+	//InterestBearingConfig: rate_authority 32, init_ts 8, pre_update_average_rate 2, last_update_ts 8, current_rate 2
+	const interestConfig = () =>
+		tlvExtensionData(
+			svm.getAccount(mint)?.data,
+			MintExtension.interestBearingConfig,
+		);
+	let config = interestConfig();
+	expect(config?.subarray(0, 32)).toEqual(configPDA.toBuffer());
+	expect(config?.readInt16LE(50)).toEqual(500);
+
+	ll("only config admins can update the rate");
+	tok22UpdateRate(-100, mint, hackerKp, configPDA, "0x1"); //AdminPermission
+	tok22UpdateRate(-100, mint, ownerKp);
+	config = interestConfig();
+	expect(config?.readInt16LE(50)).toEqual(-100);
+
+	ll("under a timelock the rate changes through a queued SetRate action");
+	const delay = 3600;
+	const setDelay = (seconds: number, id: bigint) => {
+		const payload = [ActionKind.setDelay, ...bigintToBytes(seconds, 32)];
+		actionEnqueue(ownerKp, id, payload);
+		const clock = svm.getClock();
+		clock.unixTimestamp += BigInt(delay);
+		svm.setClock(clock);
+		actionExecute(ownerKp, owner, id, payload);
+	};
+	setDelay(delay, 0n);
+	tok22UpdateRate(300, mint, ownerKp, configPDA, "0x6c"); //TimelockRequired
+	const setRate = [ActionKind.setRate, ...mint.toBytes(), 300 & 0xff, 300 >> 8];
+	actionEnqueue(mintAuthorityKp, 1n, setRate, rateAdminPda);
+	const clock = svm.getClock();
+	clock.unixTimestamp += BigInt(delay);
+	svm.setClock(clock);
+	actionExecute(
+		ownerKp,
+		mintAuthorityKp.publicKey,
+		1n,
+		setRate,
+		[mint, TOKEN_2022_PROGRAM_ID],
+		"",
+		rateAdminPda,
+	);
+	config = interestConfig();
+	expect(config?.readInt16LE(50)).toEqual(300);
+	setDelay(0, 2n);
+
+	ll("mints without the extension are refused, as are extra accounts");
+	const plainMintKp = new Keypair();
+	const initPlain = (extraAccounts: PublicKey[], expectedError = "") =>
+		tok22InitMint(
+			ownerKp,
+			plainMintKp,
+			mintAuthorityKp,
+			decimals,
+			"Plain",
+			"PLN",
+			"http://plain.com",
+			expectedError,
+			{ extraAccounts },
+		);
+	initPlain([configPDA], "0x81"); //UnexpectedAccounts
+	initPlain([]);
+	tok22UpdateRate(
+		100,
+		plainMintKp.publicKey,
+		ownerKp,
+		configPDA,
+		"0x7b", //InterestBearingConfig
+	);
+});
+
+test("Mint extension policy gates Deposits and Escrow offers", () => {
+	ll("\n------== Mint extension policy gates Deposits and Escrow offers");
+	const tok22 = TOKEN_2022_PROGRAM_ID;
+	signerKp = user1Kp;
+	signer = signerKp.publicKey;
+	decimals = 6;
+	ll("a PermanentDelegate can claw back tokens from any account");
+	mint = new Keypair().publicKey;
+	const permanentDelegate = [
+		MintExtension.permanentDelegate,
+		0,
+		32, //length
+		0,
+		...hacker.toBytes(),
+	];
+	setMint(mint, decimals, undefined, owner, owner, tok22, permanentDelegate);
+	mintAdd(ownerKp, mint, 0n);
+	const userAta = setAta(mint, signer, as6zBn(100), true, tok22).ata;
+	vaultOut = findPdaV1(signer, "signerVault");
+	const vaultAta = getAta(mint, vaultOut.pda, true, tok22);
+	const userPda = findUser(signer, mint).pda;
+	const mintReg = findMintReg(mint).pda;
+	const deposit = (expectedError = "") =>
+		lgcDeposit(
+			signerKp,
+			userAta,
+			vaultAta,
+			vaultOut.pda,
+			mint,
+			configPDA,
+			mintReg,
+			userPda,
+			decimals,
+			as6zBn(10),
+			tok22,
+			undefined,
+			expectedError,
+		);
+	deposit("0x79"); //MintExtensionDenied
+
+	id = 901n;
+	escrowPDA = findEscrow(signer, id).pda;
+	makeTokEscrow(
+		signerKp,
+		getAta(usdcMint, signer),
+		getAta(usdcMint, escrowPDA),
+		usdcMint,
+		mint,
+		escrowPDA,
+		configPDA,
+		6,
+		as6zBn(1),
+		decimals,
+		as6zBn(1),
+		id,
+		undefined,
+		undefined,
+		undefined,
+		undefined,
+		"0x79", //MintExtensionDenied
+	);
+
+	ll("only MINTS admins or the owner set the policy");
+	configSetExtPolicy(0n, false, hackerKp, undefined, "0x1"); //AdminPermission
+	ll("an allowlist without PermanentDelegate also refuses it");
+	configSetExtPolicy(extBit(MintExtension.transferFeeConfig), true, ownerKp);
+	svm.expireBlockhash();
+	deposit("0x79"); //MintExtensionDenied
+
+	configSetExtPolicy(extBit(MintExtension.permanentDelegate), true, ownerKp);
+	deposit();
+	ataBalCk(vaultAta, as6zBn(10), "vault1");
+
+	ll("an offer made under a looser policy cannot be taken once it tightens");
+	makeTokEscrow(
+		signerKp,
+		getAta(usdcMint, signer),
+		getAta(usdcMint, escrowPDA),
+		usdcMint,
+		mint,
+		escrowPDA,
+		configPDA,
+		6,
+		as6zBn(1),
+		decimals,
+		as6zBn(1),
+		id,
+	);
+	configSetExtPolicy(defaultExtDeny, false, ownerKp);
+	const taker = user2Kp.publicKey;
+	takeTokEscrow(
+		user2Kp,
+		getAta(usdcMint, taker),
+		setAta(mint, taker, as6zBn(1), true, tok22).ata,
+		getAta(usdcMint, escrowPDA),
+		getAta(mint, escrowPDA, true, tok22),
+		usdcMint,
+		mint,
+		escrowPDA,
+		configPDA,
+		signer,
+		getAta(mint, signer, true, tok22),
+		vaultO,
+		getAta(mint, vaultO, true, tok22),
+		6,
+		as6zBn(1),
+		decimals,
+		as6zBn(1),
+		id,
+		TOKEN_PROGRAM_ID,
+		tok22,
+		undefined,
+		"0x79", //MintExtensionDenied
+	);
+
+	const decoded = solanaKitDecodeDev(svm.getAccount(configPDA)?.data);
+	expect(decoded.extMask).toEqual(defaultExtDeny);
+	expect(decoded.extAllowlist).toEqual(false);
+});
+test("Expired Token Escrow cannot be taken and is closed by anyone", () => {
+	ll("\n------== Expired Token Escrow");
+	signerKp = user1Kp;
+	mintX = usdcMint;
+	mintY = dragonCoin;
+	decimalX = 6;
+	decimalY = decDgc;
+	amountX = bigintAmt(50, decimalX);
+	amountY = bigintAmt(300, decimalY);
+	id = BigInt(3);
+	signer = signerKp.publicKey;
+	escrowOut = findEscrow(signer, id);
+	escrowPDA = escrowOut.pda;
+	const clock = svm.getClock();
+	const expiresAt = Number(clock.unixTimestamp) + 100;
+
+	escrowAtaX = getAta(mintX, escrowPDA);
+	escrowAtaY = getAta(mintY, escrowPDA);
+	makerAtaX = getAta(mintX, signer);
+	makerAtaY = getAta(mintY, signer);
+	prevBalcX = ataBalc(makerAtaX, "makerAtaX");
+	makeTokEscrow(
+		signerKp,
+		makerAtaX,
+		escrowAtaX,
+		mintX,
+		mintY,
+		escrowPDA,
+		configPDA,
+		decimalX,
+		amountX,
+		decimalY,
+		amountY,
+		id,
+		undefined,
+		undefined,
+		expiresAt,
+	);
+	const decoded = solanaKitDecodeEscrowDev(svm.getAccount(escrowPDA)?.data);
+	expect(decoded.expiresAt).toEqual(expiresAt);
+	ataBalCk(makerAtaX, prevBalcX - amountX, "user1 X");
+
+	ll("anyone cannot close an escrow before it expires");
+	expireTokEscrow(
+		user2Kp,
+		user1,
+		makerAtaX,
+		makerAtaY,
+		escrowAtaX,
+		escrowAtaY,
+		mintX,
+		mintY,
+		escrowPDA,
+		configPDA,
+		undefined,
+		undefined,
+		undefined,
+		"0x11", //EscrowNotExpired
+	);
+
+	clock.unixTimestamp = BigInt(expiresAt);
+	svm.setClock(clock);
+	ll("taker cannot take an expired escrow");
+	takeTokEscrow(
+		user2Kp,
+		getAta(mintX, user2),
+		getAta(mintY, user2),
+		escrowAtaX,
+		escrowAtaY,
+		mintX,
+		mintY,
+		escrowPDA,
+		configPDA,
+		user1,
+		makerAtaY,
+		vaultO,
+		getAta(mintY, vaultO),
+		decimalX,
+		amountX,
+		decimalY,
+		amountY,
+		id,
+		undefined,
+		undefined,
+		undefined,
+		"0x4b", //EscrowExpired
+	);
+
+	expireTokEscrow(
+		user2Kp,
+		user1,
+		makerAtaX,
+		makerAtaY,
+		escrowAtaX,
+		escrowAtaY,
+		mintX,
+		mintY,
+		escrowPDA,
+		configPDA,
+	);
+	ataBalCk(makerAtaX, prevBalcX, "user1 X");
+	rawAccount = svm.getAccount(escrowAtaX);
+	expect(rawAccount).toBeNull();
+	rawAccount = svm.getAccount(escrowPDA);
+	expect(rawAccount).toBeNull();
+});
+
+test("Private Token Escrow can only be taken by its designated taker", () => {
+	ll("\n------== Private Token Escrow");
+	signerKp = user1Kp;
+	mintX = usdcMint;
+	mintY = dragonCoin;
+	decimalX = 6;
+	decimalY = decDgc;
+	amountX = bigintAmt(20, decimalX);
+	amountY = bigintAmt(100, decimalY);
+	id = BigInt(4);
+	signer = signerKp.publicKey;
+	escrowOut = findEscrow(signer, id);
+	escrowPDA = escrowOut.pda;
+
+	escrowAtaX = getAta(mintX, escrowPDA);
+	escrowAtaY = getAta(mintY, escrowPDA);
+	makerAtaX = getAta(mintX, signer);
+	makerAtaY = getAta(mintY, signer);
+	makeTokEscrow(
+		signerKp,
+		makerAtaX,
+		escrowAtaX,
+		mintX,
+		mintY,
+		escrowPDA,
+		configPDA,
+		decimalX,
+		amountX,
+		decimalY,
+		amountY,
+		id,
+		undefined,
+		undefined,
+		0,
+		user2,
+	);
+	const decoded = solanaKitDecodeEscrowDev(svm.getAccount(escrowPDA)?.data);
+	expect(decoded.taker).toEqual(user2);
+
+	ll("admin cannot take an escrow designated to user2");
+	takeTokEscrow(
+		adminKp,
+		getAta(mintX, admin),
+		getAta(mintY, admin),
+		escrowAtaX,
+		escrowAtaY,
+		mintX,
+		mintY,
+		escrowPDA,
+		configPDA,
+		user1,
+		makerAtaY,
+		vaultO,
+		getAta(mintY, vaultO),
+		decimalX,
+		amountX,
+		decimalY,
+		amountY,
+		id,
+		undefined,
+		undefined,
+		undefined,
+		"0x12", //EscrowTaker
+	);
+
+	takerAtaX = getAta(mintX, user2);
+	prevBalcX = ataBalc(takerAtaX, "takerAtaX");
+	prevBalcY = ataBalc(makerAtaY, "makerAtaY");
+	takeTokEscrow(
+		user2Kp,
+		takerAtaX,
+		getAta(mintY, user2),
+		escrowAtaX,
+		escrowAtaY,
+		mintX,
+		mintY,
+		escrowPDA,
+		configPDA,
+		user1,
+		makerAtaY,
+		vaultO,
+		getAta(mintY, vaultO),
+		decimalX,
+		amountX,
+		decimalY,
+		amountY,
+		id,
+	);
+	ataBalCk(takerAtaX, prevBalcX + amountX, "Taker X");
+	ataBalCk(makerAtaY, prevBalcY + amountY - feeOf(amountY), "user1 Y");
+	rawAccount = svm.getAccount(escrowPDA);
+	expect(rawAccount).toBeNull();
+});
+
+test("Paused program refuses deposits but allows withdrawals", () => {
+	ll("\n------== Paused program");
+	const pause = (status: Status) => {
+		configSetStatus(status, ownerKp);
+		const decoded = solanaKitDecodeDev(svm.getAccount(configPDA)?.data);
+		expect(decoded.status).toEqual(status);
+	};
+	ll("an escrow made before the pause");
+	id = 902n;
+	escrowPDA = findEscrow(user1, id).pda;
+	makeTokEscrow(
+		user1Kp,
+		getAta(usdcMint, user1),
+		getAta(usdcMint, escrowPDA),
+		usdcMint,
+		dragonCoin,
+		escrowPDA,
+		configPDA,
+		6,
+		as6zBn(1),
+		decDgc,
+		bigintAmt(1, decDgc),
+		id,
+	);
+	const activeConfig = svm.getAccount(configPDA);
+	if (!activeConfig) throw new Error("config is null");
+	pause(Status.Paused);
+
+	ll("cannot be taken while its config is paused, nor via an active config");
+	const otherConfig = PublicKey.unique();
+	svm.setAccount(otherConfig, activeConfig);
+	const take = (config: PublicKey, expectedError = "") =>
+		takeTokEscrow(
+			user2Kp,
+			getAta(usdcMint, user2),
+			getAta(dragonCoin, user2),
+			getAta(usdcMint, escrowPDA),
+			getAta(dragonCoin, escrowPDA),
+			usdcMint,
+			dragonCoin,
+			escrowPDA,
+			config,
+			user1,
+			getAta(dragonCoin, user1),
+			vaultO,
+			getAta(dragonCoin, vaultO),
+			6,
+			as6zBn(1),
+			decDgc,
+			bigintAmt(1, decDgc),
+			id,
+			undefined,
+			undefined,
+			undefined,
+			expectedError,
+		);
+	take(configPDA, "0x6"); //ProgramPaused
+	take(otherConfig, "0x86"); //EscrowConfig
+
+	signerKp = user1Kp;
+	mint = usdcMint;
+	decimals = 6;
+	amt = as6zBn(10);
+	signer = signerKp.publicKey;
+	vaultOut = findPdaV1(signer, "signerVault");
+	const userAta = getAta(mint, signer);
+	const vaultAta = getAta(mint, vaultOut.pda);
+	const userPda = findUser(signer, mint).pda;
+	lgcDeposit(
+		signerKp,
+		userAta,
+		vaultAta,
+		vaultOut.pda,
+		mint,
+		configPDA,
+		findMintReg(mint).pda,
+		userPda,
+		decimals,
+		amt,
+		undefined,
+		undefined,
+		"0x6", //ProgramPaused
+	);
+
+	prevBalcX = ataBalc(userAta, "userAta");
+	lgcWithdraw(
+		signerKp,
+		vaultAta,
+		userAta,
+		vaultOut.pda,
+		mint,
+		configPDA,
+		findMintReg(mint).pda,
+		userPda,
+		decimals,
+		amt,
+	);
+	ataBalCk(userAta, prevBalcX + amt, "user1 ");
+
+	pause(Status.Active);
+	take(configPDA);
+	expect(svm.getAccount(escrowPDA)?.lamports ?? 0).toEqual(0);
+});
+
+test("Pooled SOL deposits mint shares and withdrawals burn them", () => {
+	ll("\n------== Pooled SOL deposits mint shares and withdrawals burn them");
+	const shareDecimals = 9;
+	//the pool rounds down in its favor, with one virtual share and lamport
+	const sharesFor = (lamports: bigint, assets: bigint, supply: bigint) =>
+		(lamports * (supply + 1n)) / (assets + 1n);
+	const lamportsFor = (shares: bigint, assets: bigint, supply: bigint) =>
+		(shares * (assets + 1n)) / (supply + 1n);
+	const poolAssets = () => (svm.getBalance(poolPDA) ?? 0n) - vaultRent;
+	const supply = () =>
+		Buffer.from(svm.getAccount(shareMint)?.data ?? []).readBigUInt64LE(36); //Mint supply
+	const user1Ata = getAta(shareMint, user1);
+	const user2Ata = getAta(shareMint, user2);
+	const solBalanceBf = solanaKitDecodeDev(svm.getAccount(configPDA)?.data)
+		.solBalance;
+
+	ll("lamports sent to the PDAs beforehand do not block making them");
+	sendSol(poolPDA, 1000n, hackerKp);
+	sendSol(shareMint, 1000n, hackerKp);
+
+	ll("the first deposit makes the pool and share mint, one share per lamport");
+	const amt1 = as9zBn(2);
+	poolDepositSol(amt1, amt1, user1Kp);
+	expect(poolAssets()).toEqual(amt1);
+	ataBalCk(user1Ata, amt1, "user1", shareDecimals);
+	const poolData = Buffer.from(svm.getAccount(poolPDA)?.data ?? []);
+	expect(poolData.subarray(0, 8).toString()).toEqual("pool\0\0\0\0");
+	expect(poolData[8]).toEqual(1); //version
+	ll("the pool vault is not a SOL vault of the config");
+	configReconcile(ownerKp, [poolPDA], configPDA, "0x7d"); //PoolPDA
+
+	ll("a donation raises the share price");
+	sendSol(poolPDA, as9zBn(1), adminKp);
+	let assets = poolAssets();
+	const shares = sharesFor(as9zBn(1), assets, supply());
+	ll("slippage limit");
+	poolDepositSol(as9zBn(1), shares + 1n, user2Kp, "0x80"); //PoolSlippage
+	ll("deposits too small for one share are refused");
+	poolDepositSol(1n, 0n, user2Kp, "0x7f"); //PoolZeroOut
+	poolDepositSol(as9zBn(1), shares, user2Kp);
+	ataBalCk(user2Ata, shares, "user2", shareDecimals);
+	expect(shares).toBeLessThan(as9zBn(1));
+
+	ll("withdrawals burn shares for their part of the pool");
+	assets = poolAssets();
+	const amtOut = lamportsFor(amt1, assets, supply());
+	poolWithdrawSol(amt1, amtOut + 1n, user1Kp, "0x80"); //PoolSlippage
+	poolWithdrawSol(amt1, amtOut, user1Kp);
+	ataBalCk(user1Ata, 0n, "user1", shareDecimals);
+	expect(poolAssets()).toEqual(assets - amtOut);
+	expect(supply()).toEqual(shares);
+
+	ll("pool lamports are not booked in the config sol_balance");
+	const decoded = solanaKitDecodeDev(svm.getAccount(configPDA)?.data);
+	expect(decoded.solBalance).toEqual(solBalanceBf);
+});
+
 test.skip("copy accounts from devnet", async () => {
 	//https://litesvm.github.io/litesvm/tutorial.html#copying-accounts-from-a-live-environment
 	const connection = new Connection("https://api.devnet.solana.com");
@@ -1,19 +1,53 @@
 /** biome-ignore-all lint/style/noNonNullAssertion: <> */
 import { expect, test } from "bun:test";
-import type { Keypair, PublicKey } from "@solana/web3.js";
+import { type Keypair, PublicKey } from "@solana/web3.js";
 import type { Clock } from "litesvm";
-import { Status, solanaKitDecodeDev } from "./decoder";
+import {
+	ActionKind,
+	AdminPermission,
+	defaultExtDeny,
+	Status,
+	solanaKitDecodeAction,
+	solanaKitDecodeAdmin,
+	solanaKitDecodeDev,
+	solanaKitDecodeMultisig,
+	solanaKitDecodeMultisigTx,
+} from "./decoder";
 import {
 	acctExists,
+	acctIsNull,
+	actionCancel,
+	actionEnqueue,
+	actionExecute,
+	adminGrant,
+	adminRevoke,
 	closeConfig,
 	configBump,
+	configMigrate,
 	configPDA,
-	configResize,
+	configSetAuthorized,
+	configSetFee,
+	configSetLabel,
+	configSetRaw,
+	configSetStatus,
+	findAction,
+	findAdmin,
+	findMultisig,
+	findMultisigTx,
 	initConfig,
 	initSolBalc,
+	mintDisable,
+	multisigApprove,
+	multisigCancel,
+	multisigExecute,
+	multisigInit,
+	multisigPropose,
+	ownerAccept,
+	ownerCancel,
+	ownerPropose,
+	payloadVersion,
 	setMint,
 	svm,
-	updateConfig,
 	vault1,
 	vaultO,
 } from "./litesvm-utils";
@@ -21,21 +55,24 @@ import {
 	as9zBn,
 	bigintToBytes,
 	getTime,
+	boolToByte,
 	ll,
 	statusToByte,
-	u32Bytes,
-	u64Bytes,
+	strToU8Fixed,
 } from "./utils";
 import {
 	admin,
+	adminKp,
 	owner,
 	ownerKp,
 	pyusdMint,
+	SYSTEM_PROGRAM,
 	usdcMint,
 	usdgMint,
 	usdtMint,
 	user1,
 	user1Kp,
+	user2Kp,
 	vaultProgAddr,
 } from "./web3jsSetup";
 
@@ -46,24 +83,14 @@ expect(adminBalc).toStrictEqual(initSolBalc);
 let signerKp: Keypair;
 let _authorityKp: Keypair;
 let _authority: PublicKey;
-let mints: PublicKey[];
 let _vault: PublicKey;
 let progOwner: PublicKey;
-let firstProgOwner: PublicKey;
-let progAdmin: PublicKey;
 let dest: PublicKey;
-let tokenAmount: bigint;
 let fee: bigint;
-let newSize: bigint;
 let isAuthorized = false;
 let status: Status;
 let str: string;
-let funcSelector: number;
 let time: number;
-let bytes4bools: number[];
-let bytes4u8s: number[];
-let bytes4u32s: number[];
-let bytes4u64s: number[];
 
 let clock: Clock;
 
@@ -83,26 +110,14 @@ test("InitConfig", () => {
 	ll("vault1:", vault1.toBase58());
 	ll(`configPDA: ${configPDA}`);
 	signerKp = user1Kp;
-	mints = [usdcMint, usdtMint, pyusdMint, usdgMint];
 	progOwner = owner;
-	progAdmin = user1;
-	fee = 111000000n;
+	fee = 50n;
 	isAuthorized = true;
 	status = Status.Active;
 	str = "MoonDog to the Moon!";
 
 	ll("progOwner:", progOwner.toBase58(), progOwner.toBytes());
-	ll("progAdmin:", progAdmin.toBase58(), progAdmin.toBytes());
-	initConfig(
-		mints,
-		progOwner,
-		progAdmin,
-		isAuthorized,
-		status,
-		fee,
-		str,
-		signerKp,
-	);
+	initConfig(progOwner, isAuthorized, status, fee, str, signerKp);
 
 	const pdaRaw = svm.getAccount(configPDA);
 	expect(pdaRaw).not.toBeNull();
@@ -111,13 +126,8 @@ test("InitConfig", () => {
 	expect(pdaRaw?.owner).toEqual(vaultProgAddr);
 
 	const decoded = solanaKitDecodeDev(rawAccountData);
-	expect(decoded.mint0).toEqual(mints[0]!);
-	expect(decoded.mint1).toEqual(mints[1]!);
-	expect(decoded.mint2).toEqual(mints[2]!);
-	expect(decoded.mint3).toEqual(mints[3]!);
 	expect(decoded.vault).toEqual(vaultO);
 	expect(decoded.progOwner).toEqual(progOwner);
-	expect(decoded.admin).toEqual(progAdmin);
 	expect(decoded.str).toEqual(str);
 	expect(decoded.fee).toEqual(fee);
 	expect(decoded.solBalance).toEqual(0n);
@@ -126,86 +136,450 @@ test("InitConfig", () => {
 	expect(decoded.isAuthorized).toEqual(isAuthorized);
 	expect(decoded.status).toEqual(status);
 	expect(decoded.bump).toEqual(configBump);
+	expect(decoded.version).toEqual(1);
+	expect(decoded.seedOwner).toEqual(progOwner);
+	expect(decoded.timelockDelay).toEqual(0);
+	expect(decoded.extMask).toEqual(defaultExtDeny);
+	expect(decoded.extAllowlist).toEqual(false);
+	expect(Buffer.from(rawAccountData?.slice(0, 8) ?? []).toString()).toEqual(
+		"config\0\0",
+	);
 });
 
-test("updateConfig + time travel", () => {
-	ll("\n------== updateConfig + time travel");
+test("Config with a wrong discriminator or version is refused", () => {
+	ll("\n------== Config with a wrong discriminator or version is refused");
+	const pdaRaw = svm.getAccount(configPDA);
+	if (!pdaRaw) throw new Error("configPDA is null");
+	const original = Uint8Array.from(pdaRaw.data);
+	const setConfigData = (data: Uint8Array) =>
+		svm.setAccount(configPDA, { ...pdaRaw, data });
+
+	const wrongDisc = Uint8Array.from(original);
+	wrongDisc.set(Buffer.from("escrow"), 0);
+	setConfigData(wrongDisc);
+	configSetStatus(Status.Waiting, ownerKp, configPDA, "0x24"); //AccountDiscriminator
+
+	const wrongVersion = Uint8Array.from(original);
+	wrongVersion[8] = 5;
+	setConfigData(wrongVersion);
+	configSetStatus(Status.Expired, ownerKp, configPDA, "0x25"); //AccountVersion
+
+	setConfigData(original);
+	expect(solanaKitDecodeDev(svm.getAccount(configPDA)?.data).version).toEqual(1);
+});
+
+test("typed Config setters + time travel", () => {
+	ll("\n------== typed Config setters + time travel");
 	ll(`configPDA: ${configPDA}`);
-	ll("vault1:", vault1.toBase58());
 	signerKp = ownerKp;
-	const acct1 = admin;
-	const acct2 = admin;
-	fee = 123000000n;
-	//const fee2 = bytesToBigint(bigintToBytes(fee));	ll("fee2:", fee2);
-	isAuthorized = true;
+	fee = 30n;
+	isAuthorized = false;
 	status = Status.Paused;
 	str = "MoonDog to the Marzzz!";
-	funcSelector = 1; //0 status, 1 fee, 2 admin
-
-	bytes4bools = [0, 0, 0, 0];
-	bytes4u8s = [funcSelector, statusToByte(status), 0, 0];
-	tokenAmount = as9zBn(274);
 	time = getTime();
-	bytes4u32s = [
-		...bigintToBytes(time, 32),
-		...u32Bytes,
-		...u32Bytes,
-		...u32Bytes,
-	];
-	bytes4u64s = [
-		...bigintToBytes(fee),
-		...bigintToBytes(tokenAmount),
-		...u64Bytes,
-		...u64Bytes,
-	];
 	clock = svm.getClock();
 	clock.unixTimestamp = BigInt(time);
 	svm.setClock(clock);
 
-	updateConfig(
-		bytes4bools,
-		bytes4u8s,
-		bytes4u32s,
-		bytes4u64s,
-		acct1,
-		acct2,
-		str,
-		signerKp,
-	);
-
-	const pdaRaw = svm.getAccount(configPDA);
-	expect(pdaRaw).not.toBeNull();
-	const rawAccountData = pdaRaw?.data;
-	ll("rawAccountData:", rawAccountData);
-
-	const decoded = solanaKitDecodeDev(rawAccountData);
+	configSetFee(fee, true, signerKp);
+	let decoded = solanaKitDecodeDev(svm.getAccount(configPDA)?.data);
 	expect(decoded.fee).toEqual(fee);
+	expect(decoded.feeOnX).toEqual(true);
 	expect(decoded.updatedAt).toEqual(time);
+	ll("setting the fee leaves other fields alone");
+	expect(decoded.status).toEqual(Status.Active);
+	expect(decoded.str).toEqual("MoonDog to the Moon!");
+
+	configSetStatus(status, signerKp);
+	configSetLabel(str, signerKp);
+	configSetAuthorized(isAuthorized, signerKp);
+	decoded = solanaKitDecodeDev(svm.getAccount(configPDA)?.data);
+	expect(decoded.fee).toEqual(fee);
 	expect(decoded.status).toEqual(status);
 	expect(decoded.str).toEqual(str);
-	expect(decoded.admin).toEqual(acct1);
+	expect(decoded.isAuthorized).toEqual(isAuthorized);
+
+	ll("payloads are length and version checked");
+	const feeData = [payloadVersion, ...bigintToBytes(fee)];
+	configSetRaw(13, feeData, [configPDA], signerKp, "0x28"); //InputDataLen
+	const statusData = [2, statusToByte(status)];
+	configSetRaw(32, statusData, [configPDA], signerKp, "0x23"); //PayloadVersion
+	configSetRaw(34, [payloadVersion, 0, 0], [], signerKp, "0x28"); //InputDataLen
+	configSetRaw(35, [payloadVersion, 2], [], signerKp, "0x2a"); //ByteForBool
+	ll("only the prog_owner sets the label and authorization flag");
+	configSetLabel(str, user1Kp, "0x2"); //OnlyProgOwner
+	configSetAuthorized(true, user1Kp, "0x2"); //OnlyProgOwner
+	configSetAuthorized(true, signerKp);
 });
 
-test("extend configPDA", () => {
-	ll("\n------== Extend configPDA");
-	let rawAccount = svm.getAccount(configPDA);
-	const prevSize = rawAccount?.data.byteLength;
-	ll("prevSize:", prevSize);
+test("grant and revoke admin permissions", () => {
+	ll("\n------== Grant and Revoke Admin Permissions");
+	const adminOut = findAdmin(user1);
+	const adminPda = adminOut.pda;
+	adminGrant(ownerKp, adminPda, user1, AdminPermission.pause);
+	const decoded = solanaKitDecodeAdmin(svm.getAccount(adminPda)?.data);
+	expect(decoded.config.toString()).toEqual(configPDA.toBase58());
+	expect(decoded.admin.toString()).toEqual(user1.toBase58());
+	expect(decoded.permissions).toEqual(AdminPermission.pause);
+	expect(decoded.bump).toEqual(adminOut.bump);
 
-	signerKp = user1Kp;
-	firstProgOwner = owner;
-	newSize = 1000000n;
-	configResize(signerKp, configPDA, firstProgOwner, newSize);
-	rawAccount = svm.getAccount(configPDA);
-	const newSize1 = rawAccount?.data.byteLength;
-	ll("newSize1:", newSize1);
+	ll("admin with pause permission can update status");
+	configSetStatus(Status.Active, user1Kp, adminPda);
+	expect(solanaKitDecodeDev(svm.getAccount(configPDA)?.data).status).toEqual(
+		Status.Active,
+	);
+	ll("admin without fee permission cannot update fee");
+	configSetFee(30n, false, user1Kp, adminPda, "0x1"); //AdminPermission
+
+	ll("an Admin PDA granted on another config has no permission here");
+	const adminRaw = svm.getAccount(adminPda);
+	if (!adminRaw) throw new Error("adminPda is null");
+	const otherConfig = PublicKey.unique();
+	const foreignAdmin = Uint8Array.from(adminRaw.data);
+	foreignAdmin.set(otherConfig.toBytes(), 9);
+	const foreignAdminPda = findAdmin(user1, otherConfig).pda;
+	svm.setAccount(foreignAdminPda, { ...adminRaw, data: foreignAdmin });
+	configSetStatus(Status.Active, user1Kp, foreignAdminPda, "0x82"); //AdminConfig
+	adminRevoke(ownerKp, foreignAdminPda, AdminPermission.pause, "0x82"); //AdminConfig
+
+	adminRevoke(ownerKp, adminPda, AdminPermission.pause);
+	expect(svm.getAccount(adminPda)).toBeNull();
+	ll("revoked admin cannot update status");
+	configSetStatus(Status.Active, user1Kp, adminPda, "0x3e"); //AdminDataLengh
+});
+
+test("two-step program owner transfer", () => {
+	ll("\n------== Two-step Program Owner Transfer");
+	const pendingOwner = () =>
+		solanaKitDecodeDev(svm.getAccount(configPDA)?.data).pendingOwner;
+
+	ownerPropose(user1Kp, user1, "0x2"); //OnlyProgOwner
+	ownerPropose(ownerKp, user1);
+	expect(pendingOwner()).toEqual(user1);
+
+	ownerCancel(user1Kp, "0x2"); //OnlyProgOwner
+	ownerCancel(ownerKp);
+	expect(pendingOwner()).toEqual(PublicKey.default);
+	ownerAccept(user1Kp, "0x13"); //OnlyPendingOwner
+
+	ownerPropose(ownerKp, user1);
+	ownerAccept(user1Kp);
+	let decoded = solanaKitDecodeDev(svm.getAccount(configPDA)?.data);
+	expect(decoded.progOwner).toEqual(user1);
+	expect(decoded.seedOwner).toEqual(owner);
+	expect(decoded.pendingOwner).toEqual(PublicKey.default);
+
+	ll("transfer the ownership back");
+	ownerPropose(user1Kp, owner);
+	ownerAccept(ownerKp);
+	decoded = solanaKitDecodeDev(svm.getAccount(configPDA)?.data);
+	expect(decoded.progOwner).toEqual(owner);
+});
+
+test("timelocked Actions", () => {
+	ll("\n------== Timelocked Actions");
+	const delay = 3600;
+	const setDelay = [ActionKind.setDelay, ...bigintToBytes(delay, 32)];
+
+	ll("an action of a revoked admin cannot be executed");
+	const adminPda = findAdmin(user1).pda;
+	adminGrant(ownerKp, adminPda, user1, AdminPermission.fee);
+	const adminFee = [ActionKind.setFee, ...bigintToBytes(60n), 0];
+	actionEnqueue(user1Kp, 0n, adminFee, adminPda);
+	adminRevoke(ownerKp, adminPda, AdminPermission.fee);
+	actionExecute(user1Kp, user1, 0n, adminFee, [], "0x3e", adminPda); //AdminDataLengh
+	actionCancel(ownerKp, user1, 0n);
+
+	actionEnqueue(user1Kp, 0n, setDelay, configPDA, "0x2"); //OnlyProgOwner
+	actionEnqueue(ownerKp, 0n, setDelay);
+	let action = solanaKitDecodeAction(svm.getAccount(findAction(owner, 0n).pda)?.data);
+	expect(action.config.toString()).toEqual(configPDA.toBase58());
+	expect(action.proposer.toString()).toEqual(owner.toBase58());
+	expect(action.kind).toEqual(ActionKind.setDelay);
+	actionExecute(user1Kp, owner, 0n, setDelay);
+	acctIsNull(findAction(owner, 0n).pda);
+	let decoded = solanaKitDecodeDev(svm.getAccount(configPDA)?.data);
+	expect(decoded.timelockDelay).toEqual(delay);
+
+	ll("sensitive setters and admin changes now need to be queued");
+	configSetFee(50n, false, ownerKp, configPDA, "0x6c"); //TimelockRequired
+	adminGrant(ownerKp, adminPda, user1, AdminPermission.fee, "0x6c"); //TimelockRequired
+	ownerPropose(ownerKp, user1, "0x6c"); //TimelockRequired
+	mintDisable(ownerKp, usdcMint, configPDA, "0x6c"); //TimelockRequired
+
+	const setFee = [ActionKind.setFee, ...bigintToBytes(50n), 0];
+	actionEnqueue(ownerKp, 1n, setFee);
+	action = solanaKitDecodeAction(svm.getAccount(findAction(owner, 1n).pda)?.data);
+	const eta = action.eta;
+	actionExecute(user1Kp, owner, 1n, setFee, [], "0x6f"); //ActionNotReady
+	const wrongFee = [ActionKind.setFee, ...bigintToBytes(51n), 0];
+	actionExecute(user1Kp, owner, 1n, wrongFee, [], "0x70"); //ActionPayloadHash
+
+	clock = svm.getClock();
+	clock.unixTimestamp = BigInt(eta);
+	svm.setClock(clock);
+	svm.expireBlockhash(); //the same transaction failed above
+	actionExecute(user1Kp, owner, 1n, setFee);
+	decoded = solanaKitDecodeDev(svm.getAccount(configPDA)?.data);
+	expect(decoded.fee).toEqual(50n);
+	expect(decoded.feeOnX).toEqual(false);
+
+	ll("the prog owner can cancel a queued action");
+	const setFee2 = [ActionKind.setFee, ...bigintToBytes(70n), 0];
+	actionEnqueue(ownerKp, 2n, setFee2);
+	actionCancel(user1Kp, owner, 2n, "0x2"); //OnlyProgOwner
+	actionCancel(ownerKp, owner, 2n);
+	acctIsNull(findAction(owner, 2n).pda);
+	expect(solanaKitDecodeDev(svm.getAccount(configPDA)?.data).fee).toEqual(50n);
+
+	ll("admin grants are queued, while revokes take effect at once");
+	const grantFee = [
+		ActionKind.adminGrant,
+		...user1.toBytes(),
+		...bigintToBytes(AdminPermission.fee, 32),
+	];
+	actionEnqueue(user1Kp, 4n, grantFee, configPDA, "0x2"); //OnlyProgOwner
+	actionEnqueue(ownerKp, 4n, grantFee);
+
+	ll("turn the timelock off again");
+	const noDelay = [ActionKind.setDelay, ...bigintToBytes(0, 32)];
+	actionEnqueue(ownerKp, 3n, noDelay);
+	clock.unixTimestamp += BigInt(delay);
+	svm.setClock(clock);
+	actionExecute(user1Kp, owner, 4n, grantFee, [adminPda, SYSTEM_PROGRAM]);
+	expect(solanaKitDecodeAdmin(svm.getAccount(adminPda)?.data).permissions).toEqual(
+		AdminPermission.fee,
+	);
+	adminRevoke(ownerKp, adminPda, AdminPermission.fee);
+	acctIsNull(adminPda);
+	actionExecute(user1Kp, owner, 3n, noDelay);
+	expect(solanaKitDecodeDev(svm.getAccount(configPDA)?.data).timelockDelay).toEqual(0);
+});
+
+test("multisig authority", () => {
+	ll("\n------== Multisig authority");
+	const pdaRaw = svm.getAccount(configPDA);
+	if (!pdaRaw) throw new Error("configPDA is null");
+	const signers = [owner, user1, admin];
+	multisigInit(ownerKp, 0n, 4, signers, "0x59"); //MultisigThreshold
+	multisigInit(ownerKp, 0n, 2, [owner, user1, owner], "0x58"); //DuplicateAccount
+	multisigInit(ownerKp, 0n, 2, signers);
+	const multisig = findMultisig(owner, 0n).pda;
+	const decodedMs = solanaKitDecodeMultisig(svm.getAccount(multisig)?.data);
+	expect(decodedMs.threshold).toEqual(2);
+	expect(decodedMs.signerCount).toEqual(3);
+
+	ll("the multisig acts as an admin with the fee permission");
+	adminGrant(ownerKp, findAdmin(multisig).pda, multisig, AdminPermission.fee);
+	const setFee = [ActionKind.setFee, ...bigintToBytes(70n), 1];
+	multisigPropose(user2Kp, multisig, 0n, setFee, "0x63"); //MultisigSigner
+	multisigPropose(user1Kp, multisig, 0n, setFee);
+	multisigExecute(user2Kp, multisig, user1, 0n, setFee, [], "0x73"); //MultisigNotApproved
+	multisigApprove(user1Kp, multisig, 0n, "0x65"); //MultisigApproved
+	ll("the tx is bound to the config it was proposed on");
+	const otherConfig = PublicKey.unique();
+	svm.setAccount(otherConfig, pdaRaw);
+	multisigApprove(adminKp, multisig, 0n, "0x87", otherConfig); //MultisigTxConfig
+	multisigApprove(adminKp, multisig, 0n);
+	let tx = solanaKitDecodeMultisigTx(svm.getAccount(findMultisigTx(multisig, 0n).pda)?.data);
+	expect(tx.config).toEqual(configPDA.toBase58());
+	expect(tx.approvals).toEqual(0b110);
+	svm.expireBlockhash(); //the same transaction failed above
+	multisigExecute(user2Kp, multisig, user1, 0n, setFee, [], "0x87", otherConfig); //MultisigTxConfig
+	svm.expireBlockhash();
+	multisigExecute(user2Kp, multisig, user1, 0n, setFee);
+	acctIsNull(findMultisigTx(multisig, 0n).pda);
+	let decoded = solanaKitDecodeDev(svm.getAccount(configPDA)?.data);
+	expect(decoded.fee).toEqual(70n);
+	expect(decoded.feeOnX).toEqual(true);
+
+	ll("owner-only kinds need the multisig to be the prog owner");
+	const setDelay = [ActionKind.setDelay, ...bigintToBytes(60, 32)];
+	multisigPropose(ownerKp, multisig, 1n, setDelay);
+	multisigApprove(user1Kp, multisig, 1n);
+	multisigExecute(user2Kp, multisig, owner, 1n, setDelay, [], "0x2"); //OnlyProgOwner
+	multisigCancel(user1Kp, multisig, 1n, "0x63"); //MultisigSigner
+	multisigCancel(ownerKp, multisig, 1n);
+	acctIsNull(findMultisigTx(multisig, 1n).pda);
+
+	ll("hand the program ownership to the multisig");
+	const acceptOwner = [ActionKind.acceptOwner];
+	multisigPropose(adminKp, multisig, 2n, acceptOwner);
+	multisigApprove(ownerKp, multisig, 2n);
+	multisigExecute(user2Kp, multisig, admin, 2n, acceptOwner, [], "0x13"); //OnlyPendingOwner
+	ownerPropose(ownerKp, multisig);
+	svm.expireBlockhash();
+	multisigExecute(user2Kp, multisig, admin, 2n, acceptOwner);
+	decoded = solanaKitDecodeDev(svm.getAccount(configPDA)?.data);
+	expect(decoded.progOwner).toEqual(multisig);
+	configSetLabel("single key", ownerKp, "0x2"); //OnlyProgOwner
+
+	ll("the multisig prog owner runs owner-only changes as wrapped kinds");
+	const run = (id: bigint, payload: number[], remaining: PublicKey[] = []) => {
+		multisigPropose(user1Kp, multisig, id, payload);
+		multisigApprove(adminKp, multisig, id);
+		multisigExecute(user2Kp, multisig, user1, id, payload, remaining);
+	};
+	run(3n, [ActionKind.setLabel, ...strToU8Fixed("multisig owned")]);
+	run(4n, [ActionKind.setStatus, statusToByte(Status.Paused)]);
+	run(5n, [ActionKind.setAuthorized, boolToByte(false)]);
+	decoded = solanaKitDecodeDev(svm.getAccount(configPDA)?.data);
+	expect(decoded.str).toEqual("multisig owned");
+	expect(decoded.status).toEqual(Status.Paused);
+	expect(decoded.isAuthorized).toEqual(false);
+
+	const adminPda = findAdmin(user2Kp.publicKey).pda;
+	const grantFee = [
+		ActionKind.adminGrant,
+		...user2Kp.publicKey.toBytes(),
+		...bigintToBytes(AdminPermission.fee, 32),
+	];
+	run(6n, grantFee, [adminPda, SYSTEM_PROGRAM]);
+	expect(solanaKitDecodeAdmin(svm.getAccount(adminPda)?.data).permissions).toEqual(
+		AdminPermission.fee,
+	);
+	const revokeFee = [
+		ActionKind.adminRevoke,
+		...user2Kp.publicKey.toBytes(),
+		...bigintToBytes(AdminPermission.fee, 32),
+	];
+	multisigPropose(user1Kp, multisig, 7n, revokeFee);
+	multisigApprove(adminKp, multisig, 7n);
+	const multisigAdminPda = findAdmin(multisig).pda;
+	multisigExecute(user2Kp, multisig, user1, 7n, revokeFee, [multisigAdminPda], "0x36"); //AdminPDA
+	multisigExecute(user2Kp, multisig, user1, 7n, revokeFee, [adminPda]);
+	acctIsNull(adminPda);
+
+	run(8n, [ActionKind.migrateConfig, ...owner.toBytes()], [SYSTEM_PROGRAM]);
+	expect(solanaKitDecodeDev(svm.getAccount(configPDA)?.data).progOwner).toEqual(multisig);
+
+	run(9n, [ActionKind.proposeOwner, ...owner.toBytes()]);
+	expect(solanaKitDecodeDev(svm.getAccount(configPDA)?.data).pendingOwner).toEqual(owner);
+
+	run(10n, setDelay);
+	expect(solanaKitDecodeDev(svm.getAccount(configPDA)?.data).timelockDelay).toEqual(60);
+
+	ll("the multisig prog owner closes the config to the dest in its payload");
+	const closeRaw = svm.getAccount(configPDA);
+	if (!closeRaw) throw new Error("configPDA is null");
+	const closeData = Uint8Array.from(closeRaw.data);
+	closeData.fill(0, 215, 223); //open_escrows, open_positions
+	svm.setAccount(configPDA, { ...closeRaw, data: closeData });
+	const closeToUser1 = [ActionKind.closeConfig, boolToByte(true), ...user1.toBytes()];
+	multisigPropose(user1Kp, multisig, 11n, closeToUser1);
+	multisigApprove(adminKp, multisig, 11n);
+	clock = svm.getClock();
+	clock.unixTimestamp += 60n;
+	svm.setClock(clock);
+	multisigExecute(user2Kp, multisig, user1, 11n, closeToUser1, [owner], "0x26"); //DestNotWallet
+	const balcBf = svm.getBalance(user1) ?? 0n;
+	multisigExecute(user2Kp, multisig, user1, 11n, closeToUser1, [user1]);
+	acctIsNull(configPDA);
+	expect((svm.getBalance(user1) ?? 0n) > balcBf + BigInt(closeRaw.lamports)).toEqual(true);
+
+	svm.setAccount(configPDA, pdaRaw);
+});
+
+test("migrate configPDA in place", () => {
+	ll("\n------== Migrate configPDA in place");
+	const pdaRaw = svm.getAccount(configPDA);
+	if (!pdaRaw) throw new Error("configPDA is null");
+	const original = Uint8Array.from(pdaRaw.data);
+	const configLen = original.length;
+	const rentMin = svm.minimumBalanceForRentExemption(BigInt(configLen));
+	expect(BigInt(pdaRaw.lamports)).toEqual(rentMin);
+	const setConfig = (data: Uint8Array, lamports: bigint) =>
+		svm.setAccount(configPDA, { ...pdaRaw, data, lamports: Number(lamports) });
+	const expectMigrated = () => {
+		const rawAccount = svm.getAccount(configPDA);
+		expect(rawAccount?.data.byteLength).toEqual(configLen);
+		expect(BigInt(rawAccount?.lamports ?? 0)).toEqual(rentMin);
+		return solanaKitDecodeDev(rawAccount?.data);
+	};
+	configMigrate(user1Kp, "0x2"); //OnlyProgOwner
+
+	ll("an oversized config is shrunk and the rent surplus refunded");
+	const surplus = 5_000_000n;
+	const oversized = new Uint8Array(configLen + 100);
+	oversized.set(original);
+	setConfig(oversized, rentMin + surplus);
+	let balcBf = svm.getBalance(owner) ?? 0n;
+	configMigrate(ownerKp);
+	let decoded = expectMigrated();
+	expect(decoded.progOwner).toEqual(owner);
+	expect(decoded.bump).toEqual(configBump);
+	expect((svm.getBalance(owner) ?? 0n) > balcBf).toEqual(true);
+
+	ll("a baseline config without a header is rewritten field by field and shrunk");
+	const baseline = new Uint8Array(288);
+	baseline.set(original.subarray(9, 41), 128); //vault
+	baseline.set(original.subarray(41, 73), 160); //prog_owner
+	baseline.set(user2Kp.publicKey.toBytes(), 192); //admin, dropped
+	baseline.set(original.subarray(137, 198), 224); //str_u8array, fee, sol_balance, token_balance, updated_at, is_authorized
+	baseline.set(original.subarray(199, 202), 285); //status, vault_bump, bump
+	setConfig(baseline, svm.minimumBalanceForRentExemption(288n));
+	configMigrate(user1Kp, "0x2"); //OnlyProgOwner
+	configMigrate(ownerKp, "0x34", user1); //ConfigPDA
+	balcBf = svm.getBalance(owner) ?? 0n;
+	configMigrate(ownerKp, "", owner);
+	decoded = expectMigrated();
+	const decodedOriginal = solanaKitDecodeDev(original);
+	expect(decoded.version).toEqual(1);
+	expect(decoded.vault).toEqual(decodedOriginal.vault);
+	expect(decoded.progOwner).toEqual(owner);
+	expect(decoded.seedOwner).toEqual(owner);
+	expect(decoded.fee).toEqual(decodedOriginal.fee);
+	expect(decoded.status).toEqual(decodedOriginal.status);
+	expect(svm.getAccount(configPDA)?.data[200]).toEqual(original[200]); //vault_bump
+	expect(decoded.bump).toEqual(configBump);
+	expect(decoded.timelockDelay).toEqual(0);
+	expect(decoded.extMask).toEqual(defaultExtDeny);
+	expect((svm.getBalance(owner) ?? 0n) > balcBf).toEqual(true);
+
+	ll("a config from a newer program version is refused");
+	const newer = Uint8Array.from(original);
+	newer[8] = 2;
+	setConfig(newer, rentMin);
+	configMigrate(ownerKp, "0x25"); //AccountVersion
+
+	setConfig(original, rentMin);
 });
 
 test("close configPDA", () => {
 	ll("\n------== Close configPDA");
 	signerKp = ownerKp;
-	dest = signerKp.publicKey;
-	closeConfig(signerKp, configPDA, dest);
+	dest = user1;
+	closeConfig(user1Kp, configPDA, dest, false, "0x2"); //OnlyProgOwner
+	closeConfig(signerKp, configPDA, usdcMint, false, "0x26"); //DestNotWallet
+	closeConfig(signerKp, configPDA, configPDA, false, "0x26"); //DestNotWallet
+
+	ll("a config still recording balances needs the force flag");
+	const pdaRaw = svm.getAccount(configPDA);
+	if (!pdaRaw) throw new Error("configPDA is null");
+	const data = Uint8Array.from(pdaRaw.data);
+	const solBalanceOffset = 9 + 32 * 5 + 8;
+	data[solBalanceOffset] = 1;
+	svm.setAccount(configPDA, { ...pdaRaw, data });
+	expect(solanaKitDecodeDev(svm.getAccount(configPDA)?.data).solBalance).toEqual(1n);
+	closeConfig(signerKp, configPDA, dest, false, "0x27"); //ConfigHasBalances
+
+	ll("open escrows or positions block the close even with the force flag");
+	const openEscrowsOffset = solBalanceOffset + 8 * 2 + 4 + 5 + 4 + 8 + 1;
+	const counted = Uint8Array.from(data);
+	counted[openEscrowsOffset] = 1;
+	svm.setAccount(configPDA, { ...pdaRaw, data: counted });
+	expect(solanaKitDecodeDev(svm.getAccount(configPDA)?.data).openEscrows).toEqual(1);
+	closeConfig(signerKp, configPDA, dest, true, "0x88"); //ConfigHasOpenAccounts
+	counted[openEscrowsOffset] = 0;
+	counted[openEscrowsOffset + 4] = 1;
+	svm.setAccount(configPDA, { ...pdaRaw, data: counted });
+	expect(solanaKitDecodeDev(svm.getAccount(configPDA)?.data).openPositions).toEqual(1);
+	closeConfig(signerKp, configPDA, dest, true, "0x88"); //ConfigHasOpenAccounts
+	svm.setAccount(configPDA, { ...pdaRaw, data });
+
+	const balcBf = svm.getBalance(dest) ?? 0n;
+	closeConfig(signerKp, configPDA, dest, true);
 	const rawAccount = svm.getAccount(configPDA);
 	expect(rawAccount).toBeNull();
+	expect(svm.getBalance(dest)).toEqual(balcBf + BigInt(pdaRaw.lamports));
 });
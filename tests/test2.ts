@@ -35,7 +35,7 @@ describe("Vault Program", () => {
 		const methodIx = vault.getToken2022InitMintInstruction({
 			payer: adminKp,
 			mint: mint22Kp,
-			mintAuthority: mintAuthority,
+			mintAuthority: mintAuthorityKp,
 			freezeAuthorityOpt: mintAuthority,
 			tokenProgram: TOKEN_2022_PROGRAM_ADDRESS,
 			systemProgram: SYSTEM_PROGRAM_ADDRESS,
@@ -15,7 +15,6 @@ import {
 import {
 	ATokenGPvbd,
 	as9zBn,
-	getTime,
 	ll,
 	pyusdMint,
 	strToU8Array,
@@ -63,35 +62,34 @@ test("InitConfig", async () => {
 	expect(configData.fee).toEqual(fee);
 }, 10000); //Timeouts
 
-test("UpdateConfig", async () => {
-	ll("\n------== UpdateConfig");
+test("ConfigSetFee and ConfigSetLabel", async () => {
+	ll("\n------== ConfigSetFee and ConfigSetLabel");
 	ll("payer:", adminAddr);
-	const bools = new Uint8Array([0, 1, 0, 1]);
-	const u8s = new Uint8Array([1, 2, 7, 8]);
-	const time = getTime();
-	ll("time:", time, ", u64a", as9zBn(37));
 	const str1 = "SOL to the moon!";
 	const u8array = strToU8Array(str1);
 	const _str1b = u8ArrayToStr(u8array);
 	const newFee = as9zBn(112);
-	const newTokBalc = as9zBn(243);
 
-	const methodIx = vault.getUpdateConfigInstruction({
-		authority: adminKp,
+	const feeIx = vault.getConfigSetFeeInstruction({
+		signer: adminKp,
+		configPda: configPDA,
+		adminPda: configPDA,
+		version: 1,
+		fee: newFee,
+		feeOnX: true,
+	});
+	await sendTxn(feeIx, adminKp);
+	const labelIx = vault.getConfigSetLabelInstruction({
+		progOwner: adminKp,
 		configPda: configPDA,
-		account1: configPDA,
-		account2: configPDA,
-		bools,
-		u8s,
-		u32s: [time, time + 1, time + 2, time + 3],
-		u64s: [newFee, newTokBalc, as9zBn(39), as9zBn(40)],
+		version: 1,
 		strU8: u8array,
 	});
-	await sendTxn(methodIx, adminKp);
+	await sendTxn(labelIx, adminKp);
 	ll("program execution successful");
 
 	const configData = await readConfigData(configPDA, "configPDA");
 	expect(configData.admin).toEqual(adminAddr);
 	expect(configData.fee).toEqual(newFee);
-	expect(configData.tokenBalance).toEqual(newTokBalc);
+	expect(configData.feeOnX).toEqual(true);
 });
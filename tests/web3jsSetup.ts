@@ -10,6 +10,7 @@ export const user3Kp = new Keypair();
 export const hackerKp = new Keypair();
 export const dgcAuthorityKp = new Keypair();
 export const dragonCoinKp = new Keypair();
+export const tok22MintKp = new Keypair();
 export const owner = ownerKp.publicKey;
 export const admin = adminKp.publicKey;
 export const user1 = user1Kp.publicKey;
@@ -18,6 +19,7 @@ export const user3 = user3Kp.publicKey;
 export const hacker = hackerKp.publicKey;
 export const dgcAuthority = dgcAuthorityKp.publicKey;
 export const dragonCoin = dragonCoinKp.publicKey;
+export const tok22Mint = tok22MintKp.publicKey;
 
 export const vaultProgAddr = new PublicKey(
 	"7EKqBVYSCmJbt2T8tGSmwzNKnpL29RqcJcyUr9aEEr6e",